@@ -16,6 +16,7 @@ pub fn primary_localnet() -> anyhow::Result<LocalnetConfiguration> {
         TestAdminLolly.to_localnet_account(),
         TestUser1.to_localnet_account(),
         TestUserUsdc1.to_localnet_account(),
+        TestUserLolly1.to_localnet_account(),
         TestUser2.to_localnet_account(),
         TestUserUsdc2.to_localnet_account(),
         TestUsdc.to_localnet_account(),
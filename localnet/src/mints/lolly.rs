@@ -13,7 +13,7 @@ impl GeneratedAccount for TestLolly {
     }
 
     fn generate(&self) -> Self::Data {
-        Mint::new(Some(TestAdmin.address()), 100_000_000_000, 6)
+        Mint::new(Some(TestAdmin.address()), 200_000_000_000, 6)
     }
 
     fn owner(&self) -> Pubkey {
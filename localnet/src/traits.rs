@@ -67,6 +67,22 @@ pub trait HasMockRuntime {
         result
     }
 
+    /// Executes `ixs` and returns the compute units consumed by every top level
+    /// invocation of `program_id`, in order, as reported in the program logs.
+    fn execute_measuring_compute_units(
+        &self,
+        ixs: impl Into<Vec<Instruction>>,
+        program_id: &Pubkey,
+    ) -> Vec<u64> {
+        let result = self.execute(ixs);
+        let prefix = format!("Program {} consumed ", program_id);
+        result
+            .logs
+            .iter()
+            .filter_map(|log| log.strip_prefix(&prefix)?.split(' ').next()?.parse().ok())
+            .collect()
+    }
+
     fn get_account(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
         self.runtime().get_account(pubkey)
     }
@@ -8,7 +8,7 @@ use solana_sdk::signature::{Keypair, Signer};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::state::AccountState;
 
-use crate::mints::TestUsdc;
+use crate::mints::{TestLolly, TestUsdc};
 
 lazy_static! {
     pub static ref TEST_USER_KEYPAIR_1: Keypair = Keypair::new();
@@ -60,6 +60,32 @@ impl GeneratedAccount for TestUserUsdc1 {
     }
 }
 
+pub struct TestUserLolly1;
+impl GeneratedAccount for TestUserLolly1 {
+    type Data = TokenAccount;
+
+    fn address(&self) -> Pubkey {
+        get_associated_token_address(&TestUser1.address(), &TestLolly.address())
+    }
+
+    fn generate(&self) -> Self::Data {
+        TokenAccount::from(spl_token::state::Account {
+            mint: TestLolly.address(),
+            owner: TestUser1.address(),
+            amount: 100_000_000_000,
+            delegate: Default::default(),
+            state: AccountState::Initialized,
+            is_native: Default::default(),
+            delegated_amount: 0,
+            close_authority: Default::default(),
+        })
+    }
+
+    fn owner(&self) -> Pubkey {
+        spl_token::ID
+    }
+}
+
 pub struct TestUser2;
 impl GeneratedAccount for TestUser2 {
     type Data = SystemAccount;
@@ -1,29 +1,58 @@
 mod utils;
 
-use lolly_lotto_localnet::{traits::HasMockRuntime, TestAdmin, TestAdminLolly};
+use lolly_lotto_localnet::{
+    traits::HasMockRuntime, TestAdmin, TestAdminLolly, TestAdminUsdc, TestUserLolly1,
+    TEST_USER_KEYPAIR_1,
+};
+use mock_swap::Pool as MockSwapPool;
 use solana_devtools_localnet::GeneratedAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{pubkey::Pubkey, rent::Rent};
+use solana_sdk::{account::ReadableAccount, signature::Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use switchboard_on_demand::RandomnessAccountData;
 
 use lollys_lotto::{
-    constants::LOLLY_MINT,
+    constants::{JUPITER_V6_PROGRAM_ID, LOLLY_MINT},
+    errors::LollysLottoError,
     state::{
-        EventEmitter, LollysLotto, LottoGame, LottoGameState, LottoGameVersion,
-        LottoGameWinningNumbers, LottoTicketNumbers, UserTier, WinningAmountDisbursedState,
-        WinningNumberUpdateState,
+        derive_raffle_winning_ticket_number, DrawRecord, EventEmitter, LollyBurnState, LollysLotto,
+        LottoGame, LottoGameLiabilities, LottoGameState, LottoGameVersion, LottoGameWinningNumbers,
+        LottoTicketCombination, LottoTicketNumbers, PaymentAsset, RandomnessProvider, RoundBurn,
+        StakeDiscountTier, StakePool, SweepDestination, TicketPurchaseIntent, UserMetadata,
+        UserTier, WinningAmountDisbursedState, WinningNumberUpdateState,
     },
 };
 
-use utils::test_state::TestState;
+use utils::test_state::{drawn_winning_numbers, TestRound, TestState};
 
 use lollys_lotto_rust_sdk::{
     constants::USDC_DEVNET_MINT,
+    instructions::{
+        add_to_prize_pool, burn_lolly, buy_lotto_ticket, buy_lotto_ticket_with_intent,
+        buy_lotto_ticket_with_swap, claim_all_user_rewards, claim_user_rewards, close_lotto_ticket,
+        close_lotto_tickets, commit_winning_numbers, crank_close_lotto_ticket_combination,
+        crank_distribute_protocol_fees, crank_expand_winning_numbers, crank_fulfil_subscription,
+        crank_lotto_game_closed, crank_lotto_game_finished, crank_rerequest_winning_numbers,
+        crank_settle_winning_ticket, crank_transfer_to_buy_and_burn_vault,
+        create_ticket_subscription, expire_user_rewards, fund_min_jackpot, migrate_lollys_lotto,
+        process_winning_numbers, reveal_winning_numbers, set_burn_cap, set_claim_authority,
+        set_lolly_payment_discount, set_stake_pool_config, set_swap_programs,
+        set_sweep_destination, start_lotto_game, swap_and_burn, swap_usdc_lolly,
+        sweep_lotto_game_vault, test_emit_winning_numbers, unstake_lolly,
+        verify_ticket_purchase_intent,
+    },
     pda::{
-        get_lolly_burn_state_lolly_vault, get_lolly_burn_state_pda,
+        get_draw_record_pda, get_lolly_burn_state_lolly_vault, get_lolly_burn_state_pda,
         get_lolly_burn_state_pda_and_bump, get_lolly_burn_state_usdc_vault,
-        get_lotto_game_pda_and_bump, get_lotto_game_vault_pda,
+        get_lotto_game_pda_and_bump, get_lotto_game_vault_pda, get_lotto_game_vault_pda_for_mint,
         get_lotto_game_vault_signer_pda_and_bump, get_lotto_ticket_pda_and_bump,
+        get_stake_pool_pda, get_stake_pool_reward_vault, get_stake_pool_stake_vault,
+        get_stake_position_pda, get_ticket_subscription_pda_and_bump,
+        get_ticket_subscription_vault_address, get_ticket_subscription_vault_address_for_mint,
         get_user_metadata_pda_and_bump, get_user_rewards_vault_address,
+        get_user_rewards_vault_address_for_mint,
     },
+    state::{estimate_winning_amounts, round_burns, verify_winning_numbers},
 };
 
 #[test]
@@ -62,6 +91,7 @@ fn test_create_lollys_lotto() {
     let round1: u64 = 0;
     let ticket_price1: u64 = 1000000;
     let game_duration1: u64 = 3600;
+    let sales_close_buffer1: u64 = 0;
     let round_name1 = "Round 1".to_string();
 
     let (lotto_game_pda1, lotto_game_bump1) =
@@ -69,6 +99,7 @@ fn test_create_lollys_lotto() {
     let (lotto_game_vault_signer1, lotto_game_vault_signer_bump1) =
         get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda1);
     let lotto_game_vault_pda1 = get_lotto_game_vault_pda(&lotto_game_pda1);
+    let draw_record_pda1 = get_draw_record_pda(&lotto_game_pda1);
     println!("lotto_game_pda1: {:?}", lotto_game_pda1);
     println!("lotto_game_vault_signer1: {:?}", lotto_game_vault_signer1);
     println!("lotto_game_vault_pda1: {:?}", lotto_game_vault_pda1);
@@ -77,12 +108,15 @@ fn test_create_lollys_lotto() {
         round1,
         ticket_price1,
         game_duration1,
+        sales_close_buffer1,
+        86400,
         round_name1,
         &test_state.test_admin,
         &test_state.lollys_lotto,
         &lotto_game_pda1,
         &lotto_game_vault_signer1,
         &lotto_game_vault_pda1,
+        &draw_record_pda1,
         &test_state.test_usdc,
         &event_emitter_pda,
     );
@@ -104,34 +138,28 @@ fn test_create_lollys_lotto() {
         (lotto_game1.end_date - lotto_game1.start_date) as u64,
         game_duration1
     );
+    assert_eq!(lotto_game1.sales_close_buffer, sales_close_buffer1);
     assert_eq!(lotto_game1.ticket_price, ticket_price1);
     assert_eq!(lotto_game1.tickets_sold, 0);
     assert_eq!(lotto_game1.lotto_game_mint, test_state.test_usdc);
     assert_eq!(lotto_game1.lotto_game_vault, lotto_game_vault_pda1);
     assert_eq!(lotto_game1.jackpot_winning_ticket, Pubkey::default());
     assert_eq!(lotto_game1.max_numbers_in_ticket, [9, 9, 9, 9, 9, 49]);
+    assert_eq!(lotto_game1.winning_numbers_filled, 0);
     assert_eq!(
-        lotto_game1.jackpot_winning_numbers,
+        lotto_game1.jackpot_winning_numbers(),
         LottoGameWinningNumbers::default()
     );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_1_WINNERS_V1]
-    );
-    assert_eq!(
-        lotto_game1.tier_2_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_2_WINNERS_V1]
-    );
-    assert_eq!(
-        lotto_game1.tier_3_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_3_WINNERS_V1]
-    );
+    assert!(lotto_game1
+        .winning_numbers_index
+        .iter()
+        .all(|&entry| entry == 0));
 
     let lotto_game_vault_balance1 = test_state.get_ata_balance(lotto_game_vault_pda1);
     assert_eq!(lotto_game_vault_balance1, 0);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 2);
+    assert_eq!(event_emitter.event_id, 3);
 
     // 4. Start Lotto Game Round 2 while Round 1 is still open
     let round2: u64 = 1;
@@ -144,6 +172,7 @@ fn test_create_lollys_lotto() {
     let (lotto_game_vault_signer2, lotto_game_vault_signer_bump2) =
         get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda2);
     let lotto_game_vault_pda2 = get_lotto_game_vault_pda(&lotto_game_pda2);
+    let draw_record_pda2 = get_draw_record_pda(&lotto_game_pda2);
     println!("lotto_game_pda2: {:?}", lotto_game_pda2);
     println!("lotto_game_vault_signer2: {:?}", lotto_game_vault_signer2);
     println!("lotto_game_vault_pda2: {:?}", lotto_game_vault_pda2);
@@ -152,12 +181,15 @@ fn test_create_lollys_lotto() {
         round2,
         ticket_price2,
         game_duration2,
+        0,
+        86400,
         round_name2,
         &test_state.test_admin,
         &test_state.lollys_lotto,
         &lotto_game_pda2,
         &lotto_game_vault_signer2,
         &lotto_game_vault_pda2,
+        &draw_record_pda2,
         &test_state.test_usdc,
         &event_emitter_pda,
     );
@@ -185,25 +217,18 @@ fn test_create_lollys_lotto() {
     assert_eq!(lotto_game2.lotto_game_vault, lotto_game_vault_pda2);
     assert_eq!(lotto_game2.jackpot_winning_ticket, Pubkey::default());
     assert_eq!(lotto_game2.max_numbers_in_ticket, [9, 9, 9, 9, 9, 49]);
+    assert_eq!(lotto_game2.winning_numbers_filled, 0);
     assert_eq!(
-        lotto_game2.jackpot_winning_numbers,
+        lotto_game2.jackpot_winning_numbers(),
         LottoGameWinningNumbers::default()
     );
-    assert_eq!(
-        lotto_game2.tier_1_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_1_WINNERS_V1]
-    );
-    assert_eq!(
-        lotto_game2.tier_2_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_2_WINNERS_V1]
-    );
-    assert_eq!(
-        lotto_game2.tier_3_winning_numbers,
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_3_WINNERS_V1]
-    );
+    assert!(lotto_game2
+        .winning_numbers_index
+        .iter()
+        .all(|&entry| entry == 0));
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 3);
+    assert_eq!(event_emitter.event_id, 5);
 
     let lotto_game_vault_balance2 = test_state.get_ata_balance(lotto_game_vault_pda2);
     assert_eq!(lotto_game_vault_balance2, 0);
@@ -238,17 +263,11 @@ fn test_create_lollys_lotto() {
     assert_eq!(user_metadata.referral_revenue, 0);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 4);
+    assert_eq!(event_emitter.event_id, 6);
 
-    // 6. Buy Lotto Ticket for user1 for Round 1
-    let numbers1: LottoTicketNumbers = LottoTicketNumbers {
-        number1: 1,
-        number2: 2,
-        number3: 3,
-        number4: 4,
-        number5: 5,
-        jackpot_number: 6,
-    };
+    // 6. Buy Lotto Ticket for user1 for Round 1, the jackpot drawn from the stubbed seed
+    let seed = [7u8; 32];
+    let [numbers1, numbers3] = drawn_winning_numbers(&seed);
     let (lotto_ticket_pda1, _lotto_ticket_bump1) =
         get_lotto_ticket_pda_and_bump(lotto_game_pda1, user_metadata_pda1, numbers1);
     let prev_user_usdc_balance1 = test_state.get_ata_balance(test_state.test_user_usdc1);
@@ -301,16 +320,12 @@ fn test_create_lollys_lotto() {
     );
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 5);
+    assert_eq!(event_emitter.event_id, 7);
 
     // 7. Buy another Lotto Ticket for user1 for Round 1
     let numbers2: LottoTicketNumbers = LottoTicketNumbers {
-        number1: 1,
-        number2: 2,
-        number3: 3,
-        number4: 4,
-        number5: 5,
-        jackpot_number: 7,
+        jackpot_number: numbers1.jackpot_number + 1,
+        ..numbers1
     };
     let (lotto_ticket_pda2, _lotto_ticket_bump2) =
         get_lotto_ticket_pda_and_bump(lotto_game_pda1, user_metadata_pda1, numbers2);
@@ -359,7 +374,7 @@ fn test_create_lollys_lotto() {
     );
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 6);
+    assert_eq!(event_emitter.event_id, 8);
 
     // 8. Create User Metadata for user2
     let (user_metadata_pda2, user_metadata_bump2) =
@@ -392,17 +407,9 @@ fn test_create_lollys_lotto() {
     assert_eq!(user_metadata2.referral_revenue, 0);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 7);
+    assert_eq!(event_emitter.event_id, 9);
 
-    // 9. Buy Lotto Ticket for user2 for Round 1
-    let numbers3: LottoTicketNumbers = LottoTicketNumbers {
-        number1: 1,
-        number2: 2,
-        number3: 3,
-        number4: 4,
-        number5: 5,
-        jackpot_number: 8,
-    };
+    // 9. Buy Lotto Ticket for user2 for Round 1, the first tier 1 combination drawn
     let (lotto_ticket_pda3, _lotto_ticket_bump3) =
         get_lotto_ticket_pda_and_bump(lotto_game_pda1, user_metadata_pda2, numbers3);
     let prev_user_usdc_balance2 = test_state.get_ata_balance(test_state.test_user_usdc2);
@@ -450,7 +457,7 @@ fn test_create_lollys_lotto() {
     );
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 8);
+    assert_eq!(event_emitter.event_id, 10);
 
     // 10. Move the time to end_date of Round 1
     let intial_slot = test_state.runtime().working_bank().clock().slot;
@@ -483,183 +490,64 @@ fn test_create_lollys_lotto() {
     assert_eq!(lotto_game1.state, LottoGameState::Closed);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 9);
+    assert_eq!(event_emitter.event_id, 12);
 
-    // 12. Emit Jackpot Winning Numbers for Round 1
-    let winning_numbers1: Vec<u8> = vec![
-        numbers1.number1,
-        numbers1.number2,
-        numbers1.number3,
-        numbers1.number4,
-        numbers1.number5,
-        numbers1.jackpot_number,
-    ];
+    // 12. Draw the winning numbers of Round 1 from the stubbed seed
+    let winning_numbers1: Vec<u8> = numbers1.to_array().to_vec();
+    let winning_numbers2: Vec<u8> = numbers3.to_array().to_vec();
     test_state.execute_test_emit_winning_numbers_ix(
-        winning_numbers1.clone(),
+        seed.to_vec(),
         &test_state.test_admin,
         &lotto_game_pda1,
         &event_emitter_pda,
     );
 
     let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
+    assert_eq!(lotto_game1.state, LottoGameState::Drawn);
+    let jackpot_winning_numbers1 = lotto_game1.jackpot_winning_numbers();
+    assert_eq!(jackpot_winning_numbers1.number1, winning_numbers1[0]);
+    assert_eq!(jackpot_winning_numbers1.number2, winning_numbers1[1]);
+    assert_eq!(jackpot_winning_numbers1.number3, winning_numbers1[2]);
+    assert_eq!(jackpot_winning_numbers1.number4, winning_numbers1[3]);
+    assert_eq!(jackpot_winning_numbers1.number5, winning_numbers1[4]);
+    assert_eq!(jackpot_winning_numbers1.jackpot_number, winning_numbers1[5]);
     assert_eq!(
-        lotto_game1.jackpot_winning_numbers.number1,
-        winning_numbers1[0]
-    );
-    assert_eq!(
-        lotto_game1.jackpot_winning_numbers.number2,
-        winning_numbers1[1]
-    );
-    assert_eq!(
-        lotto_game1.jackpot_winning_numbers.number3,
-        winning_numbers1[2]
-    );
-    assert_eq!(
-        lotto_game1.jackpot_winning_numbers.number4,
-        winning_numbers1[3]
-    );
-    assert_eq!(
-        lotto_game1.jackpot_winning_numbers.number5,
-        winning_numbers1[4]
-    );
-    assert_eq!(
-        lotto_game1.jackpot_winning_numbers.jackpot_number,
-        winning_numbers1[5]
-    );
-    assert_eq!(
-        lotto_game1
-            .jackpot_winning_numbers
-            .switchboard_random_numbers_updated,
+        jackpot_winning_numbers1.switchboard_random_numbers_updated,
         WinningNumberUpdateState::Updated
     );
     assert_eq!(
-        lotto_game1.jackpot_winning_numbers.winning_amount_disbursed,
+        jackpot_winning_numbers1.winning_amount_disbursed,
         WinningAmountDisbursedState::NotDisbursed
     );
 
-    let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 10);
-
-    // 13. Emit Tier 1 Winning Numbers for Round 1
-    let winning_numbers2: Vec<u8> = vec![
-        numbers3.number1,
-        numbers3.number2,
-        numbers3.number3,
-        numbers3.number4,
-        numbers3.number5,
-        numbers3.jackpot_number,
-    ];
-    test_state.execute_test_emit_winning_numbers_ix(
-        winning_numbers2.clone(),
-        &test_state.test_admin,
-        &lotto_game_pda1,
-        &event_emitter_pda,
-    );
-
-    let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].number1,
-        winning_numbers2[0]
-    );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].number2,
-        winning_numbers2[1]
-    );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].number3,
-        winning_numbers2[2]
-    );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].number4,
-        winning_numbers2[3]
-    );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].number5,
-        winning_numbers2[4]
-    );
-    assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].jackpot_number,
-        winning_numbers2[5]
-    );
+    let tier_1_winning_numbers1 = lotto_game1.get_tier_1_winning_numbers_by_index(0).unwrap();
+    assert_eq!(tier_1_winning_numbers1.number1, winning_numbers2[0]);
+    assert_eq!(tier_1_winning_numbers1.number2, winning_numbers2[1]);
+    assert_eq!(tier_1_winning_numbers1.number3, winning_numbers2[2]);
+    assert_eq!(tier_1_winning_numbers1.number4, winning_numbers2[3]);
+    assert_eq!(tier_1_winning_numbers1.number5, winning_numbers2[4]);
+    assert_eq!(tier_1_winning_numbers1.jackpot_number, winning_numbers2[5]);
     assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].switchboard_random_numbers_updated,
+        tier_1_winning_numbers1.switchboard_random_numbers_updated,
         WinningNumberUpdateState::Updated
     );
     assert_eq!(
-        lotto_game1.tier_1_winning_numbers[0].winning_amount_disbursed,
+        tier_1_winning_numbers1.winning_amount_disbursed,
         WinningAmountDisbursedState::NotDisbursed
     );
 
-    let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 11);
-
-    // 14. Crank LottoGame Winning Numbers for Round 1
-    let winning_numbers_array1: LottoTicketNumbers = numbers1;
-    let winning_numbers_index1 = [0, -1, -1, -1];
-
-    test_state.execute_crank_lotto_game_winner_ix(
-        round1,
-        winning_numbers_array1,
-        winning_numbers_index1,
-        &test_state.test_admin,
-        &test_state.test_user1,
-        &user_metadata_pda1,
-        &lotto_game_pda1,
-        &lotto_game_vault_signer1,
-        &lotto_game_vault_pda1,
-        &lotto_ticket_pda1,
-        &event_emitter_pda,
-    );
-
-    let lotto_ticket1 = test_state.get_lotto_ticket(lotto_ticket_pda1);
-    let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
-    let jackpot_winning_amount = lotto_game1.final_jackpot_winning_amount().unwrap();
-    assert_eq!(lotto_ticket1.is_winner, 1);
-    assert_eq!(lotto_ticket1.prize, jackpot_winning_amount);
-    assert_eq!(lotto_game1.state, LottoGameState::Closed);
-
-    let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 12);
-
-    // 15. Crank LottoGame Winning Numbers for Round 1
-    let winning_numbers_array2: LottoTicketNumbers = numbers3;
-    let winning_numbers_index2 = [-1, 0, -1, -1];
-
-    test_state.execute_crank_lotto_game_winner_ix(
-        round1,
-        winning_numbers_array2,
-        winning_numbers_index2,
-        &test_state.test_admin,
-        &test_state.test_user2,
-        &user_metadata_pda2,
-        &lotto_game_pda1,
-        &lotto_game_vault_signer1,
-        &lotto_game_vault_pda1,
-        &lotto_ticket_pda3,
-        &event_emitter_pda,
-    );
-
-    let lotto_ticket3 = test_state.get_lotto_ticket(lotto_ticket_pda3);
-    let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
-    let tier_1_winning_amount = lotto_game1.final_tier_1_winning_amount().unwrap();
-    assert_eq!(lotto_ticket3.is_winner, 1);
-    assert_eq!(lotto_ticket3.prize, tier_1_winning_amount);
-    assert_eq!(lotto_game1.state, LottoGameState::Closed);
-
-    let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 13);
+    // Closed -> Drawing, one event per expansion and Drawing -> Drawn
+    let expansions = (LottoGame::MAX_WINNING_NUMBERS_V1 as u64)
+        .div_ceil(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE as u64);
+    let draw_event_id = test_state.get_event_emitter(event_emitter_pda).event_id;
+    assert!(draw_event_id >= 12 + 1 + expansions + 1);
 
-    // 16. Crank Transfer Winning Amount to User Rewards Vault of Jackpot winner
+    // 13. Settle the Jackpot winner of Round 1
     let prev_user_rewards_vault_balance1 = test_state.get_ata_balance(user_rewards_vault1);
-    println!(
-        "prev_user_rewards_vault_balance1: {:?}",
-        prev_user_rewards_vault_balance1
-    );
     let prev_user_metadata_total_amount_won = user_metadata.total_amount_won;
-    test_state.execute_crank_transfer_winning_amount_to_user_rewards_vault_ix(
+    test_state.execute_crank_settle_winning_ticket_ix(
         round1,
         numbers1,
-        0,
         &test_state.test_admin,
         &test_state.test_user1,
         &user_metadata_pda1,
@@ -671,23 +559,29 @@ fn test_create_lollys_lotto() {
         &event_emitter_pda,
     );
 
-    let after_user_rewards_vault_balance1 = test_state.get_ata_balance(user_rewards_vault1);
-    assert_eq!(
-        after_user_rewards_vault_balance1,
-        prev_user_rewards_vault_balance1 + jackpot_winning_amount
-    );
-
     let lotto_game = test_state.get_lotto_game(lotto_game_pda1);
-    assert_eq!(lotto_game.state, LottoGameState::Closed);
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    assert_eq!(lotto_game.state, LottoGameState::Paying);
     assert_eq!(
-        lotto_game.jackpot_winning_numbers.winning_amount_disbursed,
+        lotto_game
+            .jackpot_winning_numbers()
+            .winning_amount_disbursed,
         WinningAmountDisbursedState::Disbursed
     );
     assert_eq!(
-        lotto_game.tier_1_winning_numbers[0].winning_amount_disbursed,
+        lotto_game
+            .get_tier_1_winning_numbers_by_index(0)
+            .unwrap()
+            .winning_amount_disbursed,
         WinningAmountDisbursedState::NotDisbursed
     );
 
+    let after_user_rewards_vault_balance1 = test_state.get_ata_balance(user_rewards_vault1);
+    assert_eq!(
+        after_user_rewards_vault_balance1,
+        prev_user_rewards_vault_balance1 + jackpot_winning_amount
+    );
+
     let lotto_ticket = test_state.get_lotto_ticket(lotto_ticket_pda1);
     assert_eq!(lotto_ticket.prize, jackpot_winning_amount);
     assert_eq!(lotto_ticket.is_winner, 1);
@@ -699,20 +593,16 @@ fn test_create_lollys_lotto() {
         prev_user_metadata_total_amount_won + jackpot_winning_amount
     );
 
+    // Drawn -> Paying and the settlement
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 14);
+    assert_eq!(event_emitter.event_id, draw_event_id + 2);
 
-    // 17. Crank Transfer Winning Amount to User Rewards Vault of Tier 1 winner
+    // 14. Settle the Tier 1 winner of Round 1
     let prev_user_rewards_vault_balance2 = test_state.get_ata_balance(user_rewards_vault2);
-    println!(
-        "prev_user_rewards_vault_balance2: {:?}",
-        prev_user_rewards_vault_balance2
-    );
     let prev_user_metadata_total_amount_won = user_metadata2.total_amount_won;
-    test_state.execute_crank_transfer_winning_amount_to_user_rewards_vault_ix(
+    test_state.execute_crank_settle_winning_ticket_ix(
         round1,
         numbers3,
-        0,
         &test_state.test_admin,
         &test_state.test_user2,
         &user_metadata_pda2,
@@ -724,21 +614,21 @@ fn test_create_lollys_lotto() {
         &event_emitter_pda,
     );
 
-    let after_user_rewards_vault_balance2 = test_state.get_ata_balance(user_rewards_vault2);
-    assert_eq!(
-        after_user_rewards_vault_balance2,
-        prev_user_rewards_vault_balance2 + tier_1_winning_amount
-    );
-
     let lotto_game = test_state.get_lotto_game(lotto_game_pda1);
-    assert_eq!(lotto_game.state, LottoGameState::Closed);
+    let tier_1_winning_amount = lotto_game.final_tier_1_winning_amount().unwrap();
+    assert_eq!(lotto_game.state, LottoGameState::Paying);
     assert_eq!(
-        lotto_game.jackpot_winning_numbers.winning_amount_disbursed,
+        lotto_game
+            .get_tier_1_winning_numbers_by_index(0)
+            .unwrap()
+            .winning_amount_disbursed,
         WinningAmountDisbursedState::Disbursed
     );
+
+    let after_user_rewards_vault_balance2 = test_state.get_ata_balance(user_rewards_vault2);
     assert_eq!(
-        lotto_game.tier_1_winning_numbers[0].winning_amount_disbursed,
-        WinningAmountDisbursedState::Disbursed
+        after_user_rewards_vault_balance2,
+        prev_user_rewards_vault_balance2 + tier_1_winning_amount
     );
 
     let lotto_ticket = test_state.get_lotto_ticket(lotto_ticket_pda3);
@@ -753,9 +643,9 @@ fn test_create_lollys_lotto() {
     );
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 15);
+    assert_eq!(event_emitter.event_id, draw_event_id + 3);
 
-    // 18. Claim Winning Amount for Jackpot Winner
+    // 15. Claim Winning Amount for Jackpot Winner
     let prev_user_usdc_balance1 = test_state.get_ata_balance(test_state.test_user_usdc1);
     println!("prev_user_usdc_balance1: {:?}", prev_user_usdc_balance1);
     let prev_user_rewards_vault_balance1 = test_state.get_ata_balance(user_rewards_vault1);
@@ -806,9 +696,9 @@ fn test_create_lollys_lotto() {
     assert_eq!(user_metadata.last_claimed_at, test_state.clock().unix_timestamp);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 16);
+    assert_eq!(event_emitter.event_id, draw_event_id + 4);
 
-    // 19. Create Lolly Burn State Account
+    // 16. Create Lolly Burn State Account
     let (lolly_burn_state_pda, lolly_burn_state_bump) =
         get_lolly_burn_state_pda_and_bump(test_state.test_admin);
     let lolly_burn_state_usdc_vault = get_lolly_burn_state_usdc_vault(test_state.test_admin);
@@ -830,9 +720,9 @@ fn test_create_lollys_lotto() {
     assert_eq!(lolly_burn_state.total_lolly_burnt, 0);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 17);
+    assert_eq!(event_emitter.event_id, draw_event_id + 5);
 
-    // 20. Crank Transfer to Buy and Burn vault
+    // 17. Crank Transfer to Buy and Burn vault
     let lolly_burn_state = get_lolly_burn_state_pda(test_state.test_admin);
     let lolly_burn_state_usdc_vault = get_lolly_burn_state_usdc_vault(test_state.test_admin);
 
@@ -854,9 +744,9 @@ fn test_create_lollys_lotto() {
     assert_eq!(lolly_burn_state.total_lolly_burnt, 0);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 18);
+    assert_eq!(event_emitter.event_id, draw_event_id + 6);
 
-    // 21. Transfer Lolly tokens from TestAdminLolly to lolly_burn_state_lolly_vault
+    // 18. Transfer Lolly tokens from TestAdminLolly to lolly_burn_state_lolly_vault
     let prev_lolly_burn_state_lolly_vault_balance = test_state.get_ata_balance(lolly_burn_state_lolly_vault);
     println!("prev_lolly_burn_state_lolly_vault_balance: {:?}", prev_lolly_burn_state_lolly_vault_balance);
     let prev_test_admin_lolly_balance = test_state.get_ata_balance(TestAdminLolly.address());
@@ -876,7 +766,7 @@ fn test_create_lollys_lotto() {
     let after_test_admin_lolly_balance = test_state.get_ata_balance(TestAdminLolly.address());
     assert_eq!(after_test_admin_lolly_balance, prev_test_admin_lolly_balance - 450000);
 
-    // 22. Burn Lolly tokens from lolly_burn_state_lolly_vault
+    // 19. Burn Lolly tokens from lolly_burn_state_lolly_vault
 
     let prev_lolly_burn_state_lolly_vault_balance = test_state.get_ata_balance(lolly_burn_state_lolly_vault);
     println!("prev_lolly_burn_state_lolly_vault_balance: {:?}", prev_lolly_burn_state_lolly_vault_balance);
@@ -896,6 +786,5118 @@ fn test_create_lollys_lotto() {
     assert_eq!(lolly_burn_state.total_lolly_burnt, 450000);
 
     let event_emitter = test_state.get_event_emitter(event_emitter_pda);
-    assert_eq!(event_emitter.event_id, 19);
+    assert_eq!(event_emitter.event_id, draw_event_id + 7);
+
+    // 20. Finish Round 1 once its claim deadline has passed. Both its winners are paid, but the
+    // winning numbers no ticket holds are never disbursed, so a V1 round can't finish earlier.
+    let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
+    assert!(!lotto_game1.check_all_winning_amounts_disbursed());
+    let finish = crank_lotto_game_finished(
+        round1,
+        &test_state.test_admin,
+        &test_state.lollys_lotto,
+        &lotto_game_pda1,
+        &lotto_game_vault_signer1,
+        &test_state.test_usdc,
+        &lotto_game_vault_pda1,
+        None,
+        &event_emitter_pda,
+        &spl_token::ID,
+    );
+    test_state.execute_expecting_err([finish], 0, LollysLottoError::ClaimPeriodNotOver);
+    test_state.set_timestamp(lotto_game1.claim_deadline);
+    test_state.execute_crank_lotto_game_finished_ix(
+        round1,
+        &test_state.test_admin,
+        &lotto_game_pda1,
+        &lotto_game_vault_signer1,
+        &lotto_game_vault_pda1,
+        None,
+        &event_emitter_pda,
+    );
+
+    let lotto_game1 = test_state.get_lotto_game(lotto_game_pda1);
+    assert_eq!(lotto_game1.state, LottoGameState::Finished);
+
+    let event_emitter = test_state.get_event_emitter(event_emitter_pda);
+    assert_eq!(event_emitter.event_id, draw_event_id + 8);
+
+}
+
+#[test]
+fn test_expand_winning_numbers() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        draw_record_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.winning_numbers_seed_set, 0);
+    assert_eq!(lotto_game.winning_numbers_filled, 0);
+
+    // 2. Expanding is only allowed while the round is drawing
+    test_state.execute_expecting_err(
+        [crank_expand_winning_numbers(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidLottoGameState,
+    );
+
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let mut lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Closed);
+
+    // 3. Expanding before the randomness seed is received fails
+    lotto_game.state = LottoGameState::Drawing;
+    test_state.set_lotto_game(lotto_game_pda, &lotto_game);
+    test_state.execute_expecting_err(
+        [crank_expand_winning_numbers(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::WinningNumbersSeedNotSet,
+    );
+
+    // 4. Stub the randomness callback by writing the seed directly
+    lotto_game.set_winning_numbers_seed(&[7u8; 32]).unwrap();
+    test_state.set_lotto_game(lotto_game_pda, &lotto_game);
+
+    // 5. Crank until every winning number slot is filled
+    let mut cranks = 0;
+    while !test_state
+        .get_lotto_game(lotto_game_pda)
+        .check_all_winning_numbers_set()
+    {
+        test_state.execute_crank_expand_winning_numbers_ix(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        );
+        cranks += 1;
+        assert!(cranks <= 2 * LottoGame::MAX_WINNING_NUMBERS_V1);
+    }
+
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game.winning_numbers_filled as usize,
+        LottoGame::MAX_WINNING_NUMBERS_V1
+    );
+    assert!(lotto_game.winning_numbers_nonce >= LottoGame::MAX_WINNING_NUMBERS_V1 as u64);
+    assert_eq!(
+        lotto_game
+            .jackpot_winning_numbers()
+            .switchboard_random_numbers_updated,
+        WinningNumberUpdateState::Updated
+    );
+    assert!((0..LottoGame::MAX_TIER_3_WINNERS_V1).all(|index| lotto_game
+        .get_tier_3_winning_numbers_by_index(index)
+        .unwrap()
+        .switchboard_random_numbers_updated
+        == WinningNumberUpdateState::Updated));
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+
+    // 6. No further expansion once the draw is complete
+    test_state.execute_expecting_err(
+        [crank_expand_winning_numbers(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidLottoGameState,
+    );
+}
+
+#[test]
+fn test_switchboard_on_demand_winning_numbers() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        draw_record_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // Close the round so the draw can start
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+
+    // 2. Stub a freshly committed Switchboard On-Demand randomness account
+    let randomness_account = Pubkey::new_unique();
+    let commit_slot = 100;
+    test_state.set_slot(commit_slot);
+    let mut randomness_data: RandomnessAccountData = bytemuck::Zeroable::zeroed();
+    randomness_data.seed_slot = commit_slot - 1;
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+
+    // 3. Committing fails while the Solana Randomness Service is the provider
+    test_state.execute_expecting_err(
+        [commit_winning_numbers(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::RandomnessProviderMismatch,
+    );
+
+    // 4. Switch to Switchboard On-Demand and commit
+    test_state.execute_set_randomness_provider_ix(
+        RandomnessProvider::SwitchboardOnDemand,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    let lollys_lotto = test_state.get_lollys_lotto(lollys_lotto_pda);
+    assert_eq!(
+        lollys_lotto.randomness_provider,
+        RandomnessProvider::SwitchboardOnDemand
+    );
+
+    // Only the LottoGame authority can commit
+    test_state.execute_expecting_err(
+        [commit_winning_numbers(
+            &test_state.test_user2,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+
+    test_state.execute_commit_winning_numbers_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.randomness_request, randomness_account);
+    assert_eq!(lotto_game.randomness_request_slot, commit_slot - 1);
+    assert_eq!(lotto_game.state, LottoGameState::Drawing);
 
+    // 5. A commit is final, it can't be replaced by another one
+    let committed_randomness_account = randomness_account;
+    let randomness_account = Pubkey::new_unique();
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_expecting_err(
+        [commit_winning_numbers(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::WinningNumbersAlreadyRequested,
+    );
+
+    // Not even once the oracle was given more time than a randomness request takes to expire
+    let expires_at = lotto_game.randomness_requested_at + LottoGame::RANDOMNESS_REQUEST_TIMEOUT;
+    test_state.set_timestamp(expires_at);
+    test_state.execute_expecting_err(
+        [commit_winning_numbers(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::WinningNumbersAlreadyRequested,
+    );
+    let randomness_account = committed_randomness_account;
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.randomness_request, randomness_account);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert_eq!(draw_record.request_count, 1);
+
+    // 6. Revealing before the oracle resolves the randomness fails
+    test_state.execute_expecting_err(
+        [reveal_winning_numbers(
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::RandomnessNotResolved,
+    );
+
+    // 7. Resolve the stubbed randomness, the reveal doesn't need the authority's signature
+    let reveal_slot = commit_slot + 2;
+    test_state.set_slot(reveal_slot);
+    randomness_data.reveal_slot = reveal_slot;
+    randomness_data.value = [42u8; 32];
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+
+    test_state.execute_reveal_winning_numbers_ix(
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert!(lotto_game.check_winning_numbers_seed_set());
+    assert_eq!(lotto_game.winning_numbers_seed, [42u8; 32]);
+    assert_eq!(
+        lotto_game.winning_numbers_nonce,
+        LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE as u64
+    );
+    assert!(lotto_game.winning_numbers_filled > 0);
+    assert_eq!(
+        lotto_game
+            .jackpot_winning_numbers()
+            .switchboard_random_numbers_updated,
+        WinningNumberUpdateState::Updated
+    );
+
+    // 8. The draw record keeps the commit and the randomness received
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert_eq!(draw_record.lotto_game, lotto_game_pda);
+    assert_eq!(draw_record.request_count, 1);
+    let request = draw_record.requests[0];
+    assert_eq!(
+        request.randomness_provider,
+        RandomnessProvider::SwitchboardOnDemand
+    );
+    assert_eq!(request.randomness_request, randomness_account);
+    assert_eq!(request.randomness, [42u8; 32]);
+    assert_eq!(request.received_slot, reveal_slot);
+    assert_eq!(request.fulfilled, 1);
+
+    // 9. Once the draw is complete, the winning numbers can be recomputed from the record
+    while !test_state
+        .get_lotto_game(lotto_game_pda)
+        .check_all_winning_numbers_set()
+    {
+        test_state.execute_crank_expand_winning_numbers_ix(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        );
+    }
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert_eq!(draw_record.mapped_slot, reveal_slot);
+    assert_eq!(
+        draw_record.winning_numbers_nonce,
+        lotto_game.winning_numbers_nonce
+    );
+    assert!(verify_winning_numbers(&draw_record, &lotto_game).unwrap());
+
+    let mut tampered_lotto_game = lotto_game;
+    // Flip the lowest bit of the packed jackpot combination, i.e. change its first number
+    tampered_lotto_game.winning_numbers[0] ^= 1;
+    assert!(!verify_winning_numbers(&draw_record, &tampered_lotto_game).unwrap());
+}
+
+#[test]
+fn test_solana_randomness_service_winning_numbers() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        draw_record_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // Close the round so the draw can start
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+
+    // 2. Request the winning numbers from the Solana Randomness Service
+    let randomness_state = test_state.set_randomness_service_state();
+    let randomness_request = Pubkey::new_unique();
+    test_state.execute_request_winning_numbers_ix(
+        &randomness_request,
+        &randomness_state,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.randomness_request, randomness_request);
+    assert_eq!(lotto_game.state, LottoGameState::Drawing);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert_eq!(draw_record.request_count, 1);
+    assert_eq!(
+        draw_record.requests[0].randomness_provider,
+        RandomnessProvider::SolanaRandomnessService
+    );
+
+    // 3. The request can't be replaced while the oracle still has time to fulfil it
+    test_state.execute_expecting_err(
+        [crank_rerequest_winning_numbers(
+            round,
+            &solana_randomness_service::ID,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &randomness_state,
+            &test_state.test_admin,
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::RandomnessRequestNotExpired,
+    );
+
+    // 4. Once expired, anyone can request again, past the size of the draw record
+    let expired_randomness_request = randomness_request;
+    let mut randomness_request = randomness_request;
+    let mut randomness_requested_at = lotto_game.randomness_requested_at;
+    for _ in 0..DrawRecord::MAX_REQUESTS {
+        randomness_request = Pubkey::new_unique();
+        randomness_requested_at += LottoGame::RANDOMNESS_REQUEST_TIMEOUT;
+        test_state.set_timestamp(randomness_requested_at);
+        test_state.execute_crank_rerequest_winning_numbers_ix(
+            round,
+            &randomness_request,
+            &randomness_state,
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        );
+    }
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.randomness_request, randomness_request);
+    assert_eq!(lotto_game.randomness_requested_at, randomness_requested_at);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert_eq!(
+        draw_record.request_count,
+        1 + DrawRecord::MAX_REQUESTS as u64
+    );
+    // The expired request was overwritten by the latest one
+    assert_eq!(
+        draw_record.requests[0].randomness_request,
+        randomness_request
+    );
+
+    // 5. The randomness of a request other than the latest one is rejected
+    test_state.set_randomness_service_request(expired_randomness_request, lotto_game_pda);
+    test_state.set_randomness_service_request(randomness_request, lotto_game_pda);
+    test_state.execute_expecting_err(
+        [process_winning_numbers(
+            &randomness_state,
+            &expired_randomness_request,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+            vec![7u8; 32],
+        )],
+        0,
+        LollysLottoError::InvalidRandomnessRequest,
+    );
+
+    // 6. The randomness of the latest request sets the seed
+    let received_slot = 1_000;
+    test_state.set_slot(received_slot);
+    test_state.execute_process_winning_numbers_ix(
+        &randomness_state,
+        &randomness_request,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &event_emitter_pda,
+        vec![42u8; 32],
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert!(lotto_game.check_winning_numbers_seed_set());
+    assert_eq!(lotto_game.winning_numbers_seed, [42u8; 32]);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    let request = draw_record.requests[0];
+    assert_eq!(request.randomness, [42u8; 32]);
+    assert_eq!(request.received_slot, received_slot);
+    assert_eq!(request.fulfilled, 1);
+
+    // A fulfilled request can't be replaced
+    test_state.set_timestamp(randomness_requested_at + LottoGame::RANDOMNESS_REQUEST_TIMEOUT);
+    test_state.execute_expecting_err(
+        [crank_rerequest_winning_numbers(
+            round,
+            &solana_randomness_service::ID,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &randomness_state,
+            &test_state.test_admin,
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::WinningNumbersSeedAlreadySet,
+    );
+
+    // 7. Once the draw is complete, the winning numbers can be recomputed from the record
+    while !test_state
+        .get_lotto_game(lotto_game_pda)
+        .check_all_winning_numbers_set()
+    {
+        test_state.execute_crank_expand_winning_numbers_ix(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &event_emitter_pda,
+        );
+    }
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert!(verify_winning_numbers(&draw_record, &lotto_game).unwrap());
+}
+
+#[test]
+fn test_sales_close_buffer() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter and Lollys Lotto
+    let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+    test_state.execute_create_event_emitter_ix(event_emitter_pda, test_state.test_admin);
+
+    let lollys_lotto_pda = LollysLotto::address(test_state.test_admin);
+    test_state.execute_create_lollys_lotto_ix(
+        test_state.test_admin,
+        lollys_lotto_pda,
+        event_emitter_pda,
+    );
+
+    let round: u64 = 0;
+    let game_duration: u64 = 3600;
+    let sales_close_buffer: u64 = 600;
+    let (lotto_game_pda, _lotto_game_bump) =
+        get_lotto_game_pda_and_bump(&test_state.test_admin, round);
+    let (lotto_game_vault_signer, _lotto_game_vault_signer_bump) =
+        get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda);
+    let lotto_game_vault_pda = get_lotto_game_vault_pda(&lotto_game_pda);
+    let draw_record_pda = get_draw_record_pda(&lotto_game_pda);
+
+    // 2. The sales close buffer has to be shorter than the game duration
+    test_state.execute_expecting_err(
+        [start_lotto_game(
+            round,
+            1000000,
+            game_duration,
+            game_duration,
+            86400,
+            "Round 1".to_string(),
+            LottoGameVersion::V1,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &lotto_game_vault_pda,
+            &draw_record_pda,
+            &test_state.test_usdc,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidSalesCloseBuffer,
+    );
+
+    // 3. Start Round 1 with a sales close buffer
+    test_state.execute_start_lotto_game_ix(
+        round,
+        1000000,
+        game_duration,
+        sales_close_buffer,
+        86400,
+        "Round 1".to_string(),
+        &test_state.test_admin,
+        &test_state.lollys_lotto,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &draw_record_pda,
+        &test_state.test_usdc,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.sales_close_buffer, sales_close_buffer);
+    assert_eq!(
+        lotto_game.sales_close_date(),
+        lotto_game.end_date - sales_close_buffer as i64
+    );
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    // 4. The round can't be closed while tickets are on sale
+    test_state.execute_expecting_err(
+        [crank_lotto_game_closed(
+            round,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::LottoGameIsStillOpen,
+    );
+
+    // 5. Tickets can't be bought once the sales close date has passed
+    test_state.set_timestamp(lotto_game.sales_close_date() + 1);
+    let numbers = LottoTicketNumbers {
+        number1: 1,
+        number2: 2,
+        number3: 3,
+        number4: 4,
+        number5: 5,
+        jackpot_number: 6,
+    };
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    test_state.execute_expecting_err(
+        [buy_lotto_ticket(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, numbers),
+            &event_emitter_pda,
+            None,
+            None,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::LottoGameSalesClosed,
+    );
+
+    // 6. The round can be closed before its end date
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Closed);
+
+    // 7. The draw can't be committed to before the end date
+    test_state.execute_set_randomness_provider_ix(
+        RandomnessProvider::SwitchboardOnDemand,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    let randomness_account = Pubkey::new_unique();
+    let commit_slot = 100;
+    test_state.set_slot(commit_slot);
+    let mut randomness_data: RandomnessAccountData = bytemuck::Zeroable::zeroed();
+    randomness_data.seed_slot = commit_slot - 1;
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_expecting_err(
+        [commit_winning_numbers(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &draw_record_pda,
+            &randomness_account,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::LottoGameIsStillOpen,
+    );
+
+    // 8. Once the end date has passed the draw can start
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_commit_winning_numbers_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawing);
+}
+
+#[test]
+fn test_settle_winning_ticket() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+
+    // 2. Buy a jackpot ticket and a tier 1 ticket
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers, tier_1_numbers] = drawn_winning_numbers(&seed);
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    let (tier_1_ticket_pda, _tier_1_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, tier_1_numbers);
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (tier_1_numbers, tier_1_ticket_pda),
+    ] {
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    // 3. Close Round 1 and draw the winning numbers from the stubbed seed
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    assert!(lotto_game
+        .jackpot_winning_numbers()
+        .validate_winning_numbers(jackpot_numbers));
+    // The draw can't be replaced once it has started
+    test_state.execute_expecting_err(
+        [test_emit_winning_numbers(
+            seed.to_vec(),
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidLottoGameState,
+    );
+
+    // 4. Declare and pay the jackpot ticket in a single instruction
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    let prev_lotto_game_vault_balance = test_state.get_ata_balance(lotto_game_vault_pda);
+    let prev_user_rewards_vault_balance = test_state.get_ata_balance(user_rewards_vault);
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Paying);
+    assert_eq!(
+        lotto_game
+            .jackpot_winning_numbers()
+            .winning_amount_disbursed,
+        WinningAmountDisbursedState::Disbursed
+    );
+    let jackpot_ticket = test_state.get_lotto_ticket(jackpot_ticket_pda);
+    assert_eq!(jackpot_ticket.is_winner, 1);
+    assert_eq!(jackpot_ticket.prize, jackpot_winning_amount);
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        prev_lotto_game_vault_balance - jackpot_winning_amount
+    );
+    assert_eq!(
+        test_state.get_ata_balance(user_rewards_vault),
+        prev_user_rewards_vault_balance + jackpot_winning_amount
+    );
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.total_amount_won, jackpot_winning_amount);
+    // The only holder of the jackpot numbers is settled, their combination is closed
+    assert!(test_state
+        .get_account(&LottoTicketCombination::address(
+            lotto_game_pda,
+            jackpot_numbers
+        ))
+        .map_or(true, |account| account.lamports() == 0));
+
+    // 5. A settled ticket can't be declared or paid again
+    test_state.execute_expecting_err(
+        [crank_settle_winning_ticket(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &user_rewards_vault,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &jackpot_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, jackpot_numbers),
+            &test_state.test_user1,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        anchor_lang::error::ErrorCode::AccountNotInitialized,
+    );
+
+    // 6. Anyone can settle the tier 1 ticket, whose tier and index are derived on-chain
+    let settle_tier_1_ticket = crank_settle_winning_ticket(
+        round,
+        tier_1_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &test_state.test_usdc,
+        &lotto_game_vault_pda,
+        &tier_1_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, tier_1_numbers),
+        &test_state.test_user1,
+        &event_emitter_pda,
+        &spl_token::ID,
+    );
+    assert!(settle_tier_1_ticket
+        .accounts
+        .iter()
+        .all(|account| !account.is_signer));
+    test_state.execute([settle_tier_1_ticket]);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let tier_1_ticket = test_state.get_lotto_ticket(tier_1_ticket_pda);
+    assert_eq!(tier_1_ticket.is_winner, 1);
+    assert_eq!(
+        tier_1_ticket.prize,
+        lotto_game.final_tier_1_winning_amount().unwrap()
+    );
+}
+
+#[test]
+fn test_settle_duplicate_winning_tickets() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // 2. Two users buy a ticket with the same numbers, both are counted on-chain
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let mut holders = vec![];
+    for (user, user_usdc) in [
+        (test_state.test_user1, test_state.test_user_usdc1),
+        (test_state.test_user2, test_state.test_user_usdc2),
+    ] {
+        let (user_metadata_pda, _user_metadata_bump) = get_user_metadata_pda_and_bump(user);
+        let user_rewards_vault = get_user_rewards_vault_address(user);
+        test_state.execute_create_user_metadata_ix(
+            &user,
+            &user_metadata_pda,
+            &test_state.test_usdc,
+            &user_rewards_vault,
+            &event_emitter_pda,
+        );
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &user,
+            &user_metadata_pda,
+            &user_usdc,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+        holders.push((
+            user,
+            user_metadata_pda,
+            user_rewards_vault,
+            lotto_ticket_pda,
+        ));
+    }
+    let lotto_ticket_combination_pda =
+        LottoTicketCombination::address(lotto_game_pda, jackpot_numbers);
+    let lotto_ticket_combination =
+        test_state.get_lotto_ticket_combination(lotto_ticket_combination_pda);
+    assert_eq!(lotto_ticket_combination.lotto_game, lotto_game_pda);
+    assert_eq!(lotto_ticket_combination.holders, 2);
+    assert_eq!(lotto_ticket_combination.holders_settled, 0);
+    assert_eq!(lotto_ticket_combination.payer, test_state.test_user1);
+
+    // 3. Close Round 1 and draw the winning numbers from the stubbed seed
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    let prize_per_holder = jackpot_winning_amount / 2;
+
+    // 4. The first holder is paid half of the jackpot, the jackpot isn't disbursed yet
+    let settle = |(user, user_metadata_pda, user_rewards_vault, lotto_ticket_pda): (
+        Pubkey,
+        Pubkey,
+        Pubkey,
+        Pubkey,
+    )| {
+        test_state.execute_crank_settle_winning_ticket_ix(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &user,
+            &user_metadata_pda,
+            &user_rewards_vault,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    };
+    let close = |(user, user_metadata_pda, _user_rewards_vault, lotto_ticket_pda): (
+        Pubkey,
+        Pubkey,
+        Pubkey,
+        Pubkey,
+    )| {
+        close_lotto_ticket(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &user,
+            &user_metadata_pda,
+            &lotto_game_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        )
+    };
+    settle(holders[0]);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game
+            .jackpot_winning_numbers()
+            .winning_amount_disbursed,
+        WinningAmountDisbursedState::NotDisbursed
+    );
+    let lotto_ticket = test_state.get_lotto_ticket(holders[0].3);
+    assert_eq!(lotto_ticket.is_winner, 1);
+    assert_eq!(lotto_ticket.is_duplicated, 1);
+    assert_eq!(lotto_ticket.prize, prize_per_holder);
+    assert_eq!(test_state.get_ata_balance(holders[0].2), prize_per_holder);
+    let lotto_ticket_combination =
+        test_state.get_lotto_ticket_combination(lotto_ticket_combination_pda);
+    assert_eq!(lotto_ticket_combination.holders_settled, 1);
+    assert_eq!(lotto_ticket_combination.prize_per_holder, prize_per_holder);
+    // A settled holder can't be paid again while the other one isn't settled
+    test_state.execute_expecting_err(
+        [crank_settle_winning_ticket(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &holders[0].0,
+            &holders[0].1,
+            &holders[0].2,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &holders[0].3,
+            &lotto_ticket_combination_pda,
+            &test_state.test_user1,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::AlreadyDeclaredWinner,
+    );
+
+    // 5. The paid holder can close their ticket, the unpaid one can't
+    test_state.execute([close(holders[0])]);
+    test_state.execute_expecting_err(
+        [close(holders[1])],
+        0,
+        LollysLottoError::LottoTicketNotSettled,
+    );
+
+    // 6. The second holder is paid the other half, which disburses the jackpot
+    // and closes the combination
+    settle(holders[1]);
+    assert!(test_state
+        .get_account(&lotto_ticket_combination_pda)
+        .map_or(true, |account| account.lamports() == 0));
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game
+            .jackpot_winning_numbers()
+            .winning_amount_disbursed,
+        WinningAmountDisbursedState::Disbursed
+    );
+    let lotto_ticket = test_state.get_lotto_ticket(holders[1].3);
+    assert_eq!(lotto_ticket.is_winner, 1);
+    assert_eq!(lotto_ticket.prize, prize_per_holder);
+    assert_eq!(test_state.get_ata_balance(holders[1].2), prize_per_holder);
+    test_state.execute([close(holders[1])]);
+}
+
+#[test]
+fn test_close_lotto_ticket_combinations() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // 2. The second user pays the rent of a jackpot, a tier 1 and a losing combination
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user2);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user2);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user2,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+    let seed = [7u8; 32];
+    let [jackpot_numbers, tier_1_numbers] = drawn_winning_numbers(&seed);
+    let losing_numbers = LottoTicketNumbers {
+        number1: 7,
+        ..jackpot_numbers
+    };
+    let mut lotto_ticket_pdas = vec![];
+    for numbers in [jackpot_numbers, tier_1_numbers, losing_numbers] {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user2,
+            &user_metadata_pda,
+            &test_state.test_user_usdc2,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+        let lotto_ticket_combination = test_state
+            .get_lotto_ticket_combination(LottoTicketCombination::address(lotto_game_pda, numbers));
+        assert_eq!(lotto_ticket_combination.payer, test_state.test_user2);
+        lotto_ticket_pdas.push(lotto_ticket_pda);
+    }
+    let get_lamports = |pubkey: &Pubkey| {
+        test_state
+            .get_account(pubkey)
+            .map_or(0, |account| account.lamports())
+    };
+
+    // 3. Close Round 1 and draw the winning numbers from the stubbed seed
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+
+    // 4. Combinations can't be closed while their holders can still be settled
+    let close_combination = |numbers: LottoTicketNumbers, payer: &Pubkey| {
+        crank_close_lotto_ticket_combination(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &LottoTicketCombination::address(lotto_game_pda, numbers),
+            payer,
+            &event_emitter_pda,
+        )
+    };
+    test_state.execute_expecting_err(
+        [close_combination(losing_numbers, &test_state.test_user2)],
+        0,
+        LollysLottoError::InvalidLottoGameState,
+    );
+
+    // 5. Settling the only holder of the jackpot refunds the rent of its combination
+    let jackpot_ticket_combination_pda =
+        LottoTicketCombination::address(lotto_game_pda, jackpot_numbers);
+    let jackpot_ticket_combination_rent = get_lamports(&jackpot_ticket_combination_pda);
+    let prev_payer_lamports = get_lamports(&test_state.test_user2);
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user2,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pdas[0],
+        &event_emitter_pda,
+    );
+    assert_eq!(get_lamports(&jackpot_ticket_combination_pda), 0);
+    assert_eq!(
+        get_lamports(&test_state.test_user2),
+        prev_payer_lamports + jackpot_ticket_combination_rent
+    );
+
+    // 6. Once the round is finished, anyone can refund the rest to their payer
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.claim_deadline);
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        None,
+        &event_emitter_pda,
+    );
+    test_state.execute_expecting_err(
+        [close_combination(losing_numbers, &test_state.test_user1)],
+        0,
+        LollysLottoError::InvalidCombinationPayer,
+    );
+    for numbers in [tier_1_numbers, losing_numbers] {
+        let lotto_ticket_combination_pda = LottoTicketCombination::address(lotto_game_pda, numbers);
+        let lotto_ticket_combination_rent = get_lamports(&lotto_ticket_combination_pda);
+        let prev_payer_lamports = get_lamports(&test_state.test_user2);
+        test_state.execute_crank_close_lotto_ticket_combination_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &lotto_game_pda,
+            &test_state.test_user2,
+            &event_emitter_pda,
+        );
+        assert_eq!(get_lamports(&lotto_ticket_combination_pda), 0);
+        assert_eq!(
+            get_lamports(&test_state.test_user2),
+            prev_payer_lamports + lotto_ticket_combination_rent
+        );
+    }
+}
+
+#[test]
+fn test_lotto_game_solvency() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities, LottoGameLiabilities::default());
+
+    // 2. Every ticket sale reserves its price across the prize and fee buckets
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let other_numbers = LottoTicketNumbers {
+        number1: 7,
+        ..jackpot_numbers
+    };
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    let (other_ticket_pda, _other_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, other_numbers);
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (other_numbers, other_ticket_pda),
+    ] {
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game.liabilities.jackpot,
+        2 * ticket_price * LottoGame::JACKPOT_WINNING_BPS as u64 / 10000
+    );
+    assert_eq!(
+        lotto_game.liabilities.buy_and_burn,
+        2 * ticket_price * LottoGame::BUY_AND_BURN_BPS as u64 / 10000
+    );
+    assert_eq!(lotto_game.liabilities.total().unwrap(), 2 * ticket_price);
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        lotto_game.liabilities.total().unwrap()
+    );
+    test_state.execute_verify_lotto_game_solvency_ix(
+        round,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+
+    // 3. Close Round 1 and draw the winning numbers from the stubbed seed
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+
+    // 4. Nothing leaves an insolvent vault, but the shortfall can still be reported
+    let solvent_lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let mut insolvent_lotto_game = solvent_lotto_game;
+    insolvent_lotto_game.liabilities.dao += 1;
+    test_state.set_lotto_game(lotto_game_pda, &insolvent_lotto_game);
+    test_state.execute_expecting_err(
+        [crank_settle_winning_ticket(
+            round,
+            jackpot_numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &user_rewards_vault,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &jackpot_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, jackpot_numbers),
+            &test_state.test_user1,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::LottoGameVaultInsolvent,
+    );
+    test_state.execute_verify_lotto_game_solvency_ix(
+        round,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+    test_state.set_lotto_game(lotto_game_pda, &solvent_lotto_game);
+
+    // 5. Paying the jackpot releases its liability and keeps the vault solvent
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let jackpot_ticket = test_state.get_lotto_ticket(jackpot_ticket_pda);
+    assert_eq!(
+        lotto_game.liabilities.jackpot,
+        solvent_lotto_game.liabilities.jackpot - jackpot_ticket.prize
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        lotto_game.liabilities.total().unwrap()
+    );
+
+    // 6. The buy and burn share can only be transferred once
+    let lolly_burn_state_pda = get_lolly_burn_state_pda(test_state.test_admin);
+    let lolly_burn_state_usdc_vault = get_lolly_burn_state_usdc_vault(test_state.test_admin);
+    test_state.execute_create_lolly_burn_state_ix(
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &LOLLY_MINT,
+        &get_lolly_burn_state_lolly_vault(test_state.test_admin),
+        &USDC_DEVNET_MINT,
+        &lolly_burn_state_usdc_vault,
+        &event_emitter_pda,
+    );
+    test_state.execute_crank_transfer_to_buy_and_burn_vault_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &lolly_burn_state_pda,
+        &lolly_burn_state_usdc_vault,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities.buy_and_burn, 0);
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(
+        lolly_burn_state.round_burn(round),
+        Some(&RoundBurn {
+            round,
+            usdc_received: test_state.get_ata_balance(lolly_burn_state_usdc_vault),
+            usdc_swapped: 0,
+            lolly_burnt: 0,
+        })
+    );
+    test_state.execute_expecting_err(
+        [crank_transfer_to_buy_and_burn_vault(
+            round,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &lolly_burn_state_pda,
+            Some(&lolly_burn_state_usdc_vault),
+            None,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::LiabilityExceeded,
+    );
+}
+
+#[test]
+fn test_sweep_lotto_game_vault() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+
+    // 2. Buy a jackpot ticket and a losing ticket
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let losing_numbers = LottoTicketNumbers {
+        number1: 7,
+        ..jackpot_numbers
+    };
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    for numbers in [jackpot_numbers, losing_numbers] {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    // 3. Draw, pay the jackpot and finish Round 1
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+
+    // 4. Round 1 can't be finished, so its vault can't be swept, before the claim deadline
+    let sweep_to = |destination_vault: &Pubkey, next_lotto_game: Option<&Pubkey>| {
+        sweep_lotto_game_vault(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            destination_vault,
+            next_lotto_game,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+    test_state.execute_expecting_err(
+        [crank_lotto_game_finished(
+            round,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            None,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::ClaimPeriodNotOver,
+    );
+    test_state.execute_expecting_err(
+        [sweep_to(&test_state.test_user_usdc1, None)],
+        0,
+        LollysLottoError::GameNotClosed,
+    );
+
+    // 5. Finish Round 1 and start Round 2 once its claim period is over
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.claim_deadline);
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        None,
+        &event_emitter_pda,
+    );
+    let next_round: u64 = 1;
+    let (next_lotto_game_pda, _next_lotto_game_bump) =
+        get_lotto_game_pda_and_bump(&test_state.test_admin, next_round);
+    let (next_lotto_game_vault_signer, _next_lotto_game_vault_signer_bump) =
+        get_lotto_game_vault_signer_pda_and_bump(&next_lotto_game_pda);
+    let next_lotto_game_vault_pda = get_lotto_game_vault_pda(&next_lotto_game_pda);
+    test_state.execute_start_lotto_game_ix(
+        next_round,
+        ticket_price,
+        3600,
+        0,
+        86400,
+        "Round 2".to_string(),
+        &test_state.test_admin,
+        &test_state.lollys_lotto,
+        &next_lotto_game_pda,
+        &next_lotto_game_vault_signer,
+        &next_lotto_game_vault_pda,
+        &get_draw_record_pda(&next_lotto_game_pda),
+        &test_state.test_usdc,
+        &event_emitter_pda,
+    );
+
+    // 6. Only the configured destination is accepted
+    test_state.execute_expecting_err(
+        [sweep_to(&test_state.test_user_usdc1, None)],
+        0,
+        LollysLottoError::InvalidSweepDestination,
+    );
+    test_state.execute_set_sweep_destination_ix(
+        SweepDestination::NextRound,
+        &test_state.test_admin,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    let lollys_lotto = test_state.get_lollys_lotto(lollys_lotto_pda);
+    assert_eq!(lollys_lotto.sweep_destination, SweepDestination::NextRound);
+    test_state.execute_expecting_err(
+        [sweep_to(&next_lotto_game_vault_pda, None)],
+        0,
+        LollysLottoError::InvalidSweepDestination,
+    );
+
+    // 7. Sweep the unclaimed tier prizes, fees and dust into the jackpot of Round 2
+    let swept_amount = test_state.get_ata_balance(lotto_game_vault_pda);
+    assert_eq!(
+        swept_amount,
+        2 * ticket_price - lotto_game.final_jackpot_winning_amount().unwrap()
+    );
+    test_state.execute_sweep_lotto_game_vault_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &next_lotto_game_vault_pda,
+        Some(&next_lotto_game_pda),
+        &event_emitter_pda,
+    );
+
+    assert_eq!(
+        test_state.get_ata_balance(next_lotto_game_vault_pda),
+        swept_amount
+    );
+    let next_lotto_game = test_state.get_lotto_game(next_lotto_game_pda);
+    assert_eq!(next_lotto_game.jackpot_rollover, swept_amount);
+    assert_eq!(next_lotto_game.liabilities.jackpot, swept_amount);
+    assert_eq!(
+        next_lotto_game.current_jackpot_winning_amount(),
+        swept_amount
+    );
+
+    // 8. The vault of Round 1 is closed, Round 1 and its DrawRecord are kept
+    assert!(test_state
+        .get_account(&lotto_game_vault_pda)
+        .map_or(true, |account| account.lamports() == 0));
+    let draw_record = test_state.get_draw_record(get_draw_record_pda(&lotto_game_pda));
+    assert_eq!(draw_record.lotto_game, lotto_game_pda);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Finished);
+    test_state.execute_expecting_err(
+        [sweep_to(
+            &next_lotto_game_vault_pda,
+            Some(&next_lotto_game_pda),
+        )],
+        0,
+        anchor_lang::error::ErrorCode::AccountNotInitialized,
+    );
+
+    // 9. The tickets of a swept round can still be closed
+    for numbers in [jackpot_numbers, losing_numbers] {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+        test_state.execute_close_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &lotto_game_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+        assert!(test_state
+            .get_account(&lotto_ticket_pda)
+            .map_or(true, |account| account.lamports() == 0));
+    }
+
+    // 10. Round 1 can be closed once its tickets are, though its vault is already closed
+    test_state.execute_close_lotto_game_ix(
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+    assert!(test_state
+        .get_account(&lotto_game_pda)
+        .map_or(true, |account| account.lamports() == 0));
+    let draw_record = test_state.get_draw_record(get_draw_record_pda(&lotto_game_pda));
+    assert_eq!(draw_record.lotto_game, lotto_game_pda);
+}
+
+#[test]
+fn test_claim_deadline() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let claim_period: u64 = 86400;
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(1000000, claim_period, LottoGameVersion::V1);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game.claim_deadline,
+        lotto_game.end_date + claim_period as i64
+    );
+
+    // 2. The claim period can't be empty
+    let (next_lotto_game_pda, _next_lotto_game_bump) =
+        get_lotto_game_pda_and_bump(&test_state.test_admin, round + 1);
+    let (next_lotto_game_vault_signer, _next_lotto_game_vault_signer_bump) =
+        get_lotto_game_vault_signer_pda_and_bump(&next_lotto_game_pda);
+    test_state.execute_expecting_err(
+        [start_lotto_game(
+            round + 1,
+            1000000,
+            3600,
+            0,
+            0,
+            "Round 2".to_string(),
+            LottoGameVersion::V1,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &next_lotto_game_pda,
+            &next_lotto_game_vault_signer,
+            &get_lotto_game_vault_pda(&next_lotto_game_pda),
+            &get_draw_record_pda(&next_lotto_game_pda),
+            &test_state.test_usdc,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidClaimPeriod,
+    );
+
+    // 3. Buy a jackpot ticket and a tier 1 ticket
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers, tier_1_numbers] = drawn_winning_numbers(&seed);
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    let (tier_1_ticket_pda, _tier_1_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, tier_1_numbers);
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (tier_1_numbers, tier_1_ticket_pda),
+    ] {
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    // 4. Draw and settle the jackpot ticket within the claim period
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let settle = |numbers: LottoTicketNumbers, lotto_ticket_pda: &Pubkey| {
+        crank_settle_winning_ticket(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &user_rewards_vault,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            lotto_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, numbers),
+            &test_state.test_user1,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+    test_state.execute([settle(jackpot_numbers, &jackpot_ticket_pda)]);
+
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.claim_deadline, lotto_game.claim_deadline);
+    assert_eq!(
+        test_state.get_ata_balance(user_rewards_vault),
+        jackpot_winning_amount
+    );
+
+    // 5. Unclaimed rewards can't be expired before the claim deadline
+    test_state.execute_set_sweep_destination_ix(
+        SweepDestination::Treasury,
+        &test_state.test_user2,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    let expire = || {
+        expire_user_rewards(
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_usdc,
+            &user_rewards_vault,
+            &test_state.test_user_usdc2,
+            None,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+    test_state.execute_expecting_err([expire()], 0, LollysLottoError::ClaimPeriodNotOver);
+
+    // 6. Once the claim deadline passes, nothing can be settled anymore
+    test_state.set_timestamp(lotto_game.claim_deadline);
+    test_state.execute_expecting_err(
+        [settle(tier_1_numbers, &tier_1_ticket_pda)],
+        0,
+        LollysLottoError::ClaimDeadlinePassed,
+    );
+
+    // 7. The unclaimed rewards are moved to the treasury
+    let prev_treasury_balance = test_state.get_ata_balance(test_state.test_user_usdc2);
+    test_state.execute([expire()]);
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc2),
+        prev_treasury_balance + jackpot_winning_amount
+    );
+    assert_eq!(test_state.get_ata_balance(user_rewards_vault), 0);
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.total_amount_expired, jackpot_winning_amount);
+    test_state.execute_expecting_err([expire()], 0, LollysLottoError::NoRewardsToClaimFromVault);
+}
+
+#[test]
+fn test_close_lotto_tickets() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // 2. Buy a jackpot ticket, a tier 1 ticket and a losing ticket
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers, tier_1_numbers] = drawn_winning_numbers(&seed);
+    let losing_numbers = LottoTicketNumbers {
+        number1: 7,
+        ..jackpot_numbers
+    };
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    let (tier_1_ticket_pda, _tier_1_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, tier_1_numbers);
+    let (losing_ticket_pda, _losing_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, losing_numbers);
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (tier_1_numbers, tier_1_ticket_pda),
+        (losing_numbers, losing_ticket_pda),
+    ] {
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    // 3. No ticket can be closed while the round is open
+    let close = |numbers: LottoTicketNumbers, lotto_ticket_pda: &Pubkey| {
+        close_lotto_ticket(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &lotto_game_pda,
+            lotto_ticket_pda,
+            &event_emitter_pda,
+        )
+    };
+    test_state.execute_expecting_err(
+        [close(losing_numbers, &losing_ticket_pda)],
+        0,
+        LollysLottoError::LottoGameIsStillOpen,
+    );
+
+    // 4. Once drawn, unpaid winning tickets still can't be closed, not even in a batch
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_expecting_err(
+        [close(jackpot_numbers, &jackpot_ticket_pda)],
+        0,
+        LollysLottoError::LottoTicketNotSettled,
+    );
+    test_state.execute_expecting_err(
+        [close_lotto_tickets(
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &event_emitter_pda,
+            &[
+                (lotto_game_pda, losing_ticket_pda),
+                (lotto_game_pda, jackpot_ticket_pda),
+            ],
+        )],
+        0,
+        LollysLottoError::LottoTicketNotSettled,
+    );
+
+    // 5. A settled winner and a confirmed loser can be closed in one batch
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_close_lotto_tickets_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &event_emitter_pda,
+        &[
+            (lotto_game_pda, losing_ticket_pda),
+            (lotto_game_pda, jackpot_ticket_pda),
+        ],
+    );
+    for closed_account in [losing_ticket_pda, jackpot_ticket_pda] {
+        assert!(test_state
+            .get_account(&closed_account)
+            .map_or(true, |account| account.lamports() == 0));
+    }
+
+    // 6. An unpaid winner can only be closed once the round is finished
+    test_state.execute_expecting_err(
+        [close(tier_1_numbers, &tier_1_ticket_pda)],
+        0,
+        LollysLottoError::LottoTicketNotSettled,
+    );
+    test_state.set_timestamp(lotto_game.claim_deadline);
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        None,
+        &event_emitter_pda,
+    );
+    test_state.execute_close_lotto_ticket_ix(
+        round,
+        tier_1_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &lotto_game_pda,
+        &tier_1_ticket_pda,
+        &event_emitter_pda,
+    );
+    assert!(test_state
+        .get_account(&tier_1_ticket_pda)
+        .map_or(true, |account| account.lamports() == 0));
+}
+
+#[test]
+fn test_winning_numbers_cranks_fit_compute_unit_limit() {
+    // Compute unit limit of an instruction when the transaction doesn't set one
+    const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u64 = 200_000;
+
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        draw_record_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    // 2. Predict the draw of a stubbed seed, and buy the jackpot and the last tier 3 combination
+    let seed = [7u8; 32];
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let mut predicted_lotto_game: Box<LottoGame> = Box::new(bytemuck::Zeroable::zeroed());
+    predicted_lotto_game.max_numbers_in_ticket = lotto_game.max_numbers_in_ticket;
+    predicted_lotto_game
+        .set_winning_numbers_seed(&seed)
+        .unwrap();
+    while !predicted_lotto_game.check_all_winning_numbers_set() {
+        predicted_lotto_game
+            .expand_winning_numbers(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE)
+            .unwrap();
+    }
+    let to_ticket_numbers = |numbers: LottoGameWinningNumbers| LottoTicketNumbers {
+        number1: numbers.number1,
+        number2: numbers.number2,
+        number3: numbers.number3,
+        number4: numbers.number4,
+        number5: numbers.number5,
+        jackpot_number: numbers.jackpot_number,
+    };
+    let jackpot_numbers = to_ticket_numbers(predicted_lotto_game.jackpot_winning_numbers());
+    let tier_3_numbers = to_ticket_numbers(
+        predicted_lotto_game
+            .get_tier_3_winning_numbers_by_index(LottoGame::MAX_TIER_3_WINNERS_V1 - 1)
+            .unwrap(),
+    );
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    let (tier_3_ticket_pda, _tier_3_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, tier_3_numbers);
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (tier_3_numbers, tier_3_ticket_pda),
+    ] {
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+    }
+
+    // 3. Close Round 1 and stub the randomness callback by writing the seed directly
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let mut lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    lotto_game.state = LottoGameState::Drawing;
+    lotto_game.set_winning_numbers_seed(&seed).unwrap();
+    test_state.set_lotto_game(lotto_game_pda, &lotto_game);
+
+    // 4. Every expansion batch fits the limit, up to the last winning numbers
+    let mut expansion_compute_units = vec![];
+    while !test_state
+        .get_lotto_game(lotto_game_pda)
+        .check_all_winning_numbers_set()
+    {
+        expansion_compute_units.extend(test_state.execute_measuring_compute_units(
+            [crank_expand_winning_numbers(
+                round,
+                &test_state.test_admin,
+                &lotto_game_pda,
+                &draw_record_pda,
+                &event_emitter_pda,
+            )],
+            &lollys_lotto::ID,
+        ));
+    }
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    assert_eq!(
+        lotto_game.winning_numbers,
+        predicted_lotto_game.winning_numbers
+    );
+
+    // Crankers don't request a higher limit
+    assert!(expansion_compute_units
+        .iter()
+        .all(|compute_units| *compute_units <= DEFAULT_INSTRUCTION_COMPUTE_UNITS));
+
+    // 5. Settling the jackpot and the last tier 3 combination fits the limit
+    let mut settlement_compute_units = vec![];
+    for (numbers, lotto_ticket_pda) in [
+        (jackpot_numbers, jackpot_ticket_pda),
+        (tier_3_numbers, tier_3_ticket_pda),
+    ] {
+        settlement_compute_units.extend(test_state.execute_measuring_compute_units(
+            [crank_settle_winning_ticket(
+                round,
+                numbers,
+                &test_state.test_admin,
+                &test_state.test_user1,
+                &user_metadata_pda,
+                &user_rewards_vault,
+                &lotto_game_pda,
+                &lotto_game_vault_signer,
+                &test_state.test_usdc,
+                &lotto_game_vault_pda,
+                &lotto_ticket_pda,
+                &LottoTicketCombination::address(lotto_game_pda, numbers),
+                &test_state.test_user1,
+                &event_emitter_pda,
+                &spl_token::ID,
+            )],
+            &lollys_lotto::ID,
+        ));
+    }
+    assert!(settlement_compute_units
+        .iter()
+        .all(|compute_units| *compute_units <= DEFAULT_INSTRUCTION_COMPUTE_UNITS));
+    let tier_3_ticket = test_state.get_lotto_ticket(tier_3_ticket_pda);
+    assert_eq!(tier_3_ticket.is_winner, 1);
+}
+
+#[test]
+fn test_claim_all_user_rewards() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+
+    // 2. Buy and settle a jackpot ticket
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.claim_authority, Pubkey::default());
+
+    let seed = [7u8; 32];
+    let [numbers] = drawn_winning_numbers(&seed);
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    test_state.execute_buy_lotto_ticket_ix(
+        round,
+        numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_user_usdc1,
+        &test_state.test_usdc,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pda,
+        &event_emitter_pda,
+    );
+
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute([crank_settle_winning_ticket(
+        round,
+        numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &test_state.test_usdc,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, numbers),
+        &test_state.test_user1,
+        &event_emitter_pda,
+        &spl_token::ID,
+    )]);
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+
+    // 3. Only the user or their claim authority can claim
+    let claim_all = |claimant: &Pubkey| {
+        claim_all_user_rewards(
+            claimant,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_usdc,
+            &user_rewards_vault,
+            &test_state.test_user_usdc2,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+    test_state.execute_expecting_err(
+        [claim_all(&test_state.test_user2)],
+        0,
+        LollysLottoError::InvalidClaimAuthority,
+    );
+    test_state.execute_set_claim_authority_ix(
+        test_state.test_user2,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &event_emitter_pda,
+    );
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.claim_authority, test_state.test_user2);
+    test_state.execute_expecting_err(
+        [claim_all(&test_state.test_admin)],
+        0,
+        LollysLottoError::InvalidClaimAuthority,
+    );
+
+    // 4. The claim authority sweeps everything into another wallet
+    let prev_destination_balance = test_state.get_ata_balance(test_state.test_user_usdc2);
+    test_state.execute([claim_all(&test_state.test_user2)]);
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc2),
+        prev_destination_balance + jackpot_winning_amount
+    );
+    assert_eq!(test_state.get_ata_balance(user_rewards_vault), 0);
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.total_amount_claimed, jackpot_winning_amount);
+
+    // 5. Nothing is left to claim
+    test_state.execute_expecting_err(
+        [claim_all(&test_state.test_user1)],
+        0,
+        LollysLottoError::NoRewardsToClaimFromVault,
+    );
+}
+
+#[test]
+fn test_buy_lotto_ticket_with_intent() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    // 2. The user signs a purchase intent off-chain, test_user2 relays it
+    let intent = TicketPurchaseIntent {
+        lotto_game: lotto_game_pda,
+        round,
+        numbers: LottoTicketNumbers {
+            number1: 1,
+            number2: 2,
+            number3: 3,
+            number4: 4,
+            number5: 5,
+            jackpot_number: 6,
+        },
+        max_ticket_price: ticket_price,
+        relayer_fee: 10000,
+        expiry: lotto_game.end_date,
+        nonce: 0,
+    };
+    let sign = |intent: &TicketPurchaseIntent| {
+        let signature = TEST_USER_KEYPAIR_1.sign_message(&intent.message());
+        <[u8; 64]>::try_from(signature.as_ref()).unwrap()
+    };
+    let relay = |intent: TicketPurchaseIntent, signed_intent: &TicketPurchaseIntent| {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, intent.numbers);
+        [
+            verify_ticket_purchase_intent(
+                &test_state.test_user1,
+                &sign(signed_intent),
+                signed_intent,
+            ),
+            buy_lotto_ticket_with_intent(
+                intent,
+                &test_state.test_user2,
+                &test_state.test_user_usdc2,
+                &test_state.test_admin,
+                &test_state.test_user1,
+                &user_metadata_pda,
+                &test_state.test_user_usdc1,
+                &test_state.test_usdc,
+                &lotto_game_pda,
+                &lotto_game_vault_pda,
+                &lotto_ticket_pda,
+                &LottoTicketCombination::address(lotto_game_pda, intent.numbers),
+                &event_emitter_pda,
+                None,
+                None,
+                &spl_token::ID,
+            ),
+        ]
+    };
+
+    // 3. The user has to delegate their USDC to their UserMetadata first
+    test_state.execute_expecting_err(
+        relay(intent, &intent),
+        1,
+        LollysLottoError::InvalidUsdcDelegate,
+    );
+    test_state.execute_approve_spl_token(
+        10 * (ticket_price + intent.relayer_fee),
+        test_state.test_user_usdc1,
+        user_metadata_pda,
+        test_state.test_user1,
+    );
+
+    // 4. The relayer can't tamper with the intent
+    let tampered_intent = TicketPurchaseIntent {
+        relayer_fee: 100 * intent.relayer_fee,
+        ..intent
+    };
+    test_state.execute_expecting_err(
+        relay(tampered_intent, &intent),
+        1,
+        LollysLottoError::InvalidIntentSignature,
+    );
+    let cheap_intent = TicketPurchaseIntent {
+        max_ticket_price: ticket_price - 1,
+        ..intent
+    };
+    test_state.execute_expecting_err(
+        relay(cheap_intent, &cheap_intent),
+        1,
+        LollysLottoError::TicketPriceAboveMax,
+    );
+
+    // 5. The relayed purchase pays the ticket and the relayer fee from the user
+    let prev_user_balance = test_state.get_ata_balance(test_state.test_user_usdc1);
+    let prev_relayer_balance = test_state.get_ata_balance(test_state.test_user_usdc2);
+    test_state.execute(relay(intent, &intent));
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_balance - ticket_price - intent.relayer_fee
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc2),
+        prev_relayer_balance + intent.relayer_fee
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        ticket_price
+    );
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, intent.numbers);
+    let lotto_ticket = test_state.get_lotto_ticket(lotto_ticket_pda);
+    assert_eq!(lotto_ticket.user, test_state.test_user1);
+    assert_eq!(lotto_ticket.numbers, intent.numbers);
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.intent_nonce, 1);
+    assert_eq!(user_metadata.total_tickets_purchased, 1);
+
+    // 6. Intents can't be replayed or submitted after their expiry
+    let replayed_intent = TicketPurchaseIntent {
+        numbers: LottoTicketNumbers {
+            jackpot_number: 7,
+            ..intent.numbers
+        },
+        ..intent
+    };
+    test_state.execute_expecting_err(
+        relay(replayed_intent, &replayed_intent),
+        1,
+        LollysLottoError::InvalidIntentNonce,
+    );
+    let expired_intent = TicketPurchaseIntent {
+        expiry: lotto_game.start_date,
+        nonce: 1,
+        ..replayed_intent
+    };
+    test_state.execute_expecting_err(
+        relay(expired_intent, &expired_intent),
+        1,
+        LollysLottoError::IntentExpired,
+    );
+}
+
+#[test]
+fn test_ticket_subscription() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and User Metadata
+    let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+    test_state.execute_create_event_emitter_ix(event_emitter_pda, test_state.test_admin);
+
+    let lollys_lotto_pda = LollysLotto::address(test_state.test_admin);
+    test_state.execute_create_lollys_lotto_ix(
+        test_state.test_admin,
+        lollys_lotto_pda,
+        event_emitter_pda,
+    );
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    let ticket_price: u64 = 1000000;
+    let start_round = |round: u64| {
+        let (lotto_game_pda, _lotto_game_bump) =
+            get_lotto_game_pda_and_bump(&test_state.test_admin, round);
+        let (lotto_game_vault_signer, _lotto_game_vault_signer_bump) =
+            get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda);
+        let lotto_game_vault_pda = get_lotto_game_vault_pda(&lotto_game_pda);
+        test_state.execute_start_lotto_game_ix(
+            round,
+            ticket_price,
+            3600,
+            0,
+            86400,
+            format!("Round {}", round + 1),
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &lotto_game_vault_pda,
+            &get_draw_record_pda(&lotto_game_pda),
+            &test_state.test_usdc,
+            &event_emitter_pda,
+        );
+        (lotto_game_pda, lotto_game_vault_pda)
+    };
+    let (lotto_game_pda, lotto_game_vault_pda) = start_round(0);
+
+    // 2. Subscribe to play the same numbers for 2 rounds
+    let cranker_fee: u64 = 10000;
+    let numbers = LottoTicketNumbers {
+        number1: 1,
+        number2: 2,
+        number3: 3,
+        number4: 4,
+        number5: 5,
+        jackpot_number: 6,
+    };
+    let subscribe = |subscription_id: u64, rounds: u64, numbers: Option<LottoTicketNumbers>| {
+        let (ticket_subscription_pda, _ticket_subscription_bump) =
+            get_ticket_subscription_pda_and_bump(
+                user_metadata_pda,
+                test_state.test_admin,
+                subscription_id,
+            );
+        create_ticket_subscription(
+            subscription_id,
+            rounds,
+            numbers,
+            ticket_price,
+            cranker_fee,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_admin,
+            &ticket_subscription_pda,
+            &test_state.test_usdc,
+            &test_state.test_user_usdc1,
+            &get_ticket_subscription_vault_address(ticket_subscription_pda),
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+    test_state.execute_expecting_err(
+        [subscribe(0, 0, Some(numbers))],
+        0,
+        LollysLottoError::InvalidSubscriptionRounds,
+    );
+    let prev_user_balance = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute([subscribe(0, 2, Some(numbers))]);
+    let (ticket_subscription_pda, _ticket_subscription_bump) =
+        get_ticket_subscription_pda_and_bump(user_metadata_pda, test_state.test_admin, 0);
+    let subscription_vault = get_ticket_subscription_vault_address(ticket_subscription_pda);
+    assert_eq!(
+        test_state.get_ata_balance(subscription_vault),
+        2 * (ticket_price + cranker_fee)
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_balance - 2 * (ticket_price + cranker_fee)
+    );
+
+    // 3. test_user2 cranks the subscription, but can't pick the numbers
+    let fulfil = |round: u64,
+                  numbers: LottoTicketNumbers,
+                  ticket_subscription_pda: &Pubkey,
+                  lotto_game_pda: &Pubkey,
+                  lotto_game_vault_pda: &Pubkey| {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(*lotto_game_pda, user_metadata_pda, numbers);
+        crank_fulfil_subscription(
+            round,
+            numbers,
+            &test_state.test_user2,
+            &test_state.test_user_usdc2,
+            &test_state.test_admin,
+            &user_metadata_pda,
+            ticket_subscription_pda,
+            &get_ticket_subscription_vault_address(*ticket_subscription_pda),
+            lotto_game_pda,
+            &test_state.test_usdc,
+            lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &LottoTicketCombination::address(*lotto_game_pda, numbers),
+            &event_emitter_pda,
+            None,
+            None,
+            &spl_token::ID,
+        )
+    };
+    let other_numbers = LottoTicketNumbers {
+        jackpot_number: 7,
+        ..numbers
+    };
+    test_state.execute_expecting_err(
+        [fulfil(
+            0,
+            other_numbers,
+            &ticket_subscription_pda,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+        )],
+        0,
+        LollysLottoError::InvalidNumbersInTicket,
+    );
+    let prev_cranker_balance = test_state.get_ata_balance(test_state.test_user_usdc2);
+    test_state.execute([fulfil(
+        0,
+        numbers,
+        &ticket_subscription_pda,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+    )]);
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        ticket_price
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc2),
+        prev_cranker_balance + cranker_fee
+    );
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    let lotto_ticket = test_state.get_lotto_ticket(lotto_ticket_pda);
+    assert_eq!(lotto_ticket.user, test_state.test_user1);
+    assert_eq!(lotto_ticket.round, 0);
+    let ticket_subscription = test_state.get_ticket_subscription(ticket_subscription_pda);
+    assert_eq!(ticket_subscription.rounds_remaining, 1);
+    assert_eq!(ticket_subscription.rounds_fulfilled, 1);
+    assert_eq!(ticket_subscription.next_round, 1);
+
+    // 4. The next round uses up the subscription
+    let (lotto_game_pda_1, lotto_game_vault_pda_1) = start_round(1);
+    let (lotto_ticket_pda_1, _lotto_ticket_bump_1) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda_1, user_metadata_pda, numbers);
+    test_state.execute_crank_fulfil_subscription_ix(
+        1,
+        numbers,
+        &test_state.test_user2,
+        &test_state.test_user_usdc2,
+        &test_state.test_admin,
+        &user_metadata_pda,
+        &ticket_subscription_pda,
+        &subscription_vault,
+        &lotto_game_pda_1,
+        &lotto_game_vault_pda_1,
+        &lotto_ticket_pda_1,
+        &event_emitter_pda,
+    );
+    let (lotto_game_pda_2, lotto_game_vault_pda_2) = start_round(2);
+    test_state.execute_expecting_err(
+        [fulfil(
+            2,
+            numbers,
+            &ticket_subscription_pda,
+            &lotto_game_pda_2,
+            &lotto_game_vault_pda_2,
+        )],
+        0,
+        LollysLottoError::SubscriptionExhausted,
+    );
+    assert_eq!(test_state.get_ata_balance(subscription_vault), 0);
+
+    // 5. A quick pick subscription is played once and cancelled
+    let (quick_pick_subscription_pda, _quick_pick_subscription_bump) =
+        get_ticket_subscription_pda_and_bump(user_metadata_pda, test_state.test_admin, 1);
+    let quick_pick_vault = get_ticket_subscription_vault_address(quick_pick_subscription_pda);
+    test_state.execute_create_ticket_subscription_ix(
+        1,
+        3,
+        None,
+        ticket_price,
+        cranker_fee,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_admin,
+        &quick_pick_subscription_pda,
+        &test_state.test_usdc,
+        &test_state.test_user_usdc1,
+        &quick_pick_vault,
+        &event_emitter_pda,
+    );
+    let quick_pick_subscription = test_state.get_ticket_subscription(quick_pick_subscription_pda);
+    let max_numbers_in_ticket = test_state
+        .get_lotto_game(lotto_game_pda_2)
+        .max_numbers_in_ticket;
+    let quick_pick_numbers = |round: u64| {
+        quick_pick_subscription.numbers_for_round(
+            quick_pick_subscription_pda,
+            round,
+            max_numbers_in_ticket,
+        )
+    };
+    test_state.execute([fulfil(
+        2,
+        quick_pick_numbers(2),
+        &quick_pick_subscription_pda,
+        &lotto_game_pda_2,
+        &lotto_game_vault_pda_2,
+    )]);
+    test_state.execute_expecting_err(
+        [fulfil(
+            1,
+            quick_pick_numbers(1),
+            &quick_pick_subscription_pda,
+            &lotto_game_pda_1,
+            &lotto_game_vault_pda_1,
+        )],
+        0,
+        LollysLottoError::SubscriptionAlreadyFulfilled,
+    );
+
+    let prev_user_balance = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute_cancel_ticket_subscription_ix(
+        &test_state.test_user1,
+        &quick_pick_subscription_pda,
+        &test_state.test_usdc,
+        &quick_pick_vault,
+        &test_state.test_user_usdc1,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_balance + 2 * (ticket_price + cranker_fee)
+    );
+    for closed_account in [quick_pick_subscription_pda, quick_pick_vault] {
+        assert!(test_state
+            .get_account(&closed_account)
+            .map_or(true, |account| account.lamports() == 0));
+    }
+}
+
+#[test]
+fn test_swap_usdc_lolly_with_mock_swap() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter and Lolly Burn State
+    let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+    test_state.execute_create_event_emitter_ix(event_emitter_pda, test_state.test_admin);
+
+    let lolly_burn_state_pda = get_lolly_burn_state_pda(test_state.test_admin);
+    let lolly_burn_state_usdc_vault = get_lolly_burn_state_usdc_vault(test_state.test_admin);
+    let lolly_burn_state_lolly_vault = get_lolly_burn_state_lolly_vault(test_state.test_admin);
+    test_state.execute_create_lolly_burn_state_ix(
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &LOLLY_MINT,
+        &lolly_burn_state_lolly_vault,
+        &USDC_DEVNET_MINT,
+        &lolly_burn_state_usdc_vault,
+        &event_emitter_pda,
+    );
+
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(
+        lolly_burn_state.swap_programs,
+        LollyBurnState::default_swap_programs()
+    );
+    assert!(lolly_burn_state.is_swap_program_allowed(JUPITER_V6_PROGRAM_ID));
+    assert!(!lolly_burn_state.is_swap_program_allowed(mock_swap::ID));
+
+    // 2. Create a USDC/LOLLY mock pool at 1 USDC = 2 LOLLY, and fund the buy and burn USDC vault
+    test_state.execute_initialize_mock_swap_pool_ix(
+        &test_state.test_admin,
+        &USDC_DEVNET_MINT,
+        &LOLLY_MINT,
+    );
+    let pool = MockSwapPool::address(USDC_DEVNET_MINT, LOLLY_MINT);
+    let mock_swap_pool = test_state.get_mock_swap_pool(pool);
+    test_state.execute_transfer_spl_token(
+        1_000_000_000,
+        TestAdminUsdc.address(),
+        mock_swap_pool.vault_a,
+        test_state.test_admin,
+    );
+    test_state.execute_transfer_spl_token(
+        2_000_000_000,
+        TestAdminLolly.address(),
+        mock_swap_pool.vault_b,
+        test_state.test_admin,
+    );
+    test_state.execute_transfer_spl_token(
+        1_000_000,
+        TestAdminUsdc.address(),
+        lolly_burn_state_usdc_vault,
+        test_state.test_admin,
+    );
+
+    let usdc_in: u64 = 1_000_000;
+    let expected_lolly_out: u64 =
+        (2_000_000_000u128 * usdc_in as u128 / (1_000_000_000 + usdc_in) as u128) as u64;
+    let route = test_state.mock_swap_route(
+        usdc_in,
+        0,
+        &lolly_burn_state_pda,
+        &pool,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &USDC_DEVNET_MINT,
+        &LOLLY_MINT,
+    );
+
+    // 3. The mock swap program is not on the allowlist yet
+    test_state.execute_expecting_err(
+        [swap_usdc_lolly(
+            0,
+            route.clone(),
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lolly_burn_state_usdc_vault,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::SwapProgramNotAllowed,
+    );
+
+    // 4. The allowlist can't name this program or exceed its capacity
+    test_state.execute_expecting_err(
+        [set_swap_programs(
+            vec![lollys_lotto::ID],
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidSwapProgramAllowlist,
+    );
+    test_state.execute_expecting_err(
+        [set_swap_programs(
+            vec![mock_swap::ID; LollyBurnState::MAX_SWAP_PROGRAMS + 1],
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidSwapProgramAllowlist,
+    );
+
+    test_state.execute_set_swap_programs_ix(
+        vec![JUPITER_V6_PROGRAM_ID, mock_swap::ID],
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert!(lolly_burn_state.is_swap_program_allowed(JUPITER_V6_PROGRAM_ID));
+    assert!(lolly_burn_state.is_swap_program_allowed(mock_swap::ID));
+
+    // 5. A failing swap CPI fails the instruction, nothing is swapped nor recorded
+    let prev_event_id = test_state.get_event_emitter(event_emitter_pda).event_id;
+    let failing_route = test_state.mock_swap_route(
+        usdc_in,
+        expected_lolly_out + 1,
+        &lolly_burn_state_pda,
+        &pool,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &USDC_DEVNET_MINT,
+        &LOLLY_MINT,
+    );
+    test_state.execute_expecting_err(
+        [swap_usdc_lolly(
+            0,
+            failing_route,
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lolly_burn_state_usdc_vault,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        mock_swap::MockSwapError::SlippageExceeded,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lolly_burn_state_usdc_vault),
+        usdc_in
+    );
+    assert_eq!(test_state.get_ata_balance(lolly_burn_state_lolly_vault), 0);
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(lolly_burn_state.total_usdc_swapped, 0);
+    assert_eq!(lolly_burn_state.total_lolly_acquired, 0);
+    assert_eq!(
+        test_state.get_event_emitter(event_emitter_pda).event_id,
+        prev_event_id
+    );
+
+    // 6. A swap returning less than min_lolly_out fails
+    test_state.execute_expecting_err(
+        [swap_usdc_lolly(
+            expected_lolly_out + 1,
+            route.clone(),
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lolly_burn_state_usdc_vault,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::SwapSlippageExceeded,
+    );
+
+    // 7. A route that does not spend the USDC vault fails, wherever the vaults are placed
+    let reversed_route = test_state.mock_swap_route(
+        1_000,
+        0,
+        &lolly_burn_state_pda,
+        &pool,
+        &lolly_burn_state_lolly_vault,
+        &lolly_burn_state_usdc_vault,
+        &LOLLY_MINT,
+        &USDC_DEVNET_MINT,
+    );
+    test_state.execute_transfer_spl_token(
+        1_000,
+        TestAdminLolly.address(),
+        lolly_burn_state_lolly_vault,
+        test_state.test_admin,
+    );
+    test_state.execute_expecting_err(
+        [swap_usdc_lolly(
+            0,
+            reversed_route,
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lolly_burn_state_usdc_vault,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidSwapBalanceChange,
+    );
+
+    // 8. Swap the vault USDC to LOLLY through the mock pool
+    let prev_lolly_vault_balance = test_state.get_ata_balance(lolly_burn_state_lolly_vault);
+    test_state.execute_swap_usdc_lolly_ix(
+        expected_lolly_out,
+        route,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &event_emitter_pda,
+    );
+
+    assert_eq!(test_state.get_ata_balance(lolly_burn_state_usdc_vault), 0);
+    assert_eq!(
+        test_state.get_ata_balance(lolly_burn_state_lolly_vault),
+        prev_lolly_vault_balance + expected_lolly_out
+    );
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(lolly_burn_state.total_usdc_swapped, usdc_in);
+    assert_eq!(lolly_burn_state.total_lolly_acquired, expected_lolly_out);
+
+    // 9. Burn the LOLLY bought
+    test_state.execute_burn_lolly_ix(
+        &LOLLY_MINT,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &lolly_burn_state_lolly_vault,
+        &event_emitter_pda,
+    );
+
+    assert_eq!(test_state.get_ata_balance(lolly_burn_state_lolly_vault), 0);
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(
+        lolly_burn_state.total_lolly_burnt,
+        prev_lolly_vault_balance + expected_lolly_out
+    );
+}
+
+#[test]
+fn test_swap_and_burn() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto, Round 1 and Lolly Burn State
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+
+    let lolly_burn_state_pda = get_lolly_burn_state_pda(test_state.test_admin);
+    let lolly_burn_state_usdc_vault = get_lolly_burn_state_usdc_vault(test_state.test_admin);
+    let lolly_burn_state_lolly_vault = get_lolly_burn_state_lolly_vault(test_state.test_admin);
+    test_state.execute_create_lolly_burn_state_ix(
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &LOLLY_MINT,
+        &lolly_burn_state_lolly_vault,
+        &USDC_DEVNET_MINT,
+        &lolly_burn_state_usdc_vault,
+        &event_emitter_pda,
+    );
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(lolly_burn_state.burn_cap_per_epoch, 0);
+    assert_eq!(lolly_burn_state.lolly_burnt_in_epoch, 0);
+
+    // 2. Create a USDC/LOLLY mock pool, allowlist it and fund the buy and burn vaults
+    test_state.execute_initialize_mock_swap_pool_ix(
+        &test_state.test_admin,
+        &USDC_DEVNET_MINT,
+        &LOLLY_MINT,
+    );
+    let pool = MockSwapPool::address(USDC_DEVNET_MINT, LOLLY_MINT);
+    let mock_swap_pool = test_state.get_mock_swap_pool(pool);
+    test_state.execute_transfer_spl_token(
+        1_000_000_000,
+        TestAdminUsdc.address(),
+        mock_swap_pool.vault_a,
+        test_state.test_admin,
+    );
+    test_state.execute_transfer_spl_token(
+        2_000_000_000,
+        TestAdminLolly.address(),
+        mock_swap_pool.vault_b,
+        test_state.test_admin,
+    );
+    test_state.execute_set_swap_programs_ix(
+        vec![mock_swap::ID],
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_transfer_spl_token(
+        2_000_000,
+        TestAdminUsdc.address(),
+        lolly_burn_state_usdc_vault,
+        test_state.test_admin,
+    );
+    // LOLLY already in the vault is not burnt by swap_and_burn
+    let idle_lolly: u64 = 500;
+    test_state.execute_transfer_spl_token(
+        idle_lolly,
+        TestAdminLolly.address(),
+        lolly_burn_state_lolly_vault,
+        test_state.test_admin,
+    );
+
+    let usdc_in: u64 = 1_000_000;
+    let expected_lolly_out = |usdc_in: u64| -> u64 {
+        let reserve_usdc = test_state.get_ata_balance(mock_swap_pool.vault_a) as u128;
+        let reserve_lolly = test_state.get_ata_balance(mock_swap_pool.vault_b) as u128;
+        (reserve_lolly * usdc_in as u128 / (reserve_usdc + usdc_in as u128)) as u64
+    };
+    let route = test_state.mock_swap_route(
+        usdc_in,
+        0,
+        &lolly_burn_state_pda,
+        &pool,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &USDC_DEVNET_MINT,
+        &LOLLY_MINT,
+    );
+    let swap_and_burn_ix = |max_usdc_in: u64, min_lolly_out: u64| {
+        swap_and_burn(
+            round,
+            max_usdc_in,
+            min_lolly_out,
+            route.clone(),
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lotto_game_pda,
+            &lolly_burn_state_usdc_vault,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )
+    };
+
+    // 3. The route can't spend more USDC than max_usdc_in, nor return less than min_lolly_out
+    test_state.execute_expecting_err(
+        [swap_and_burn_ix(usdc_in - 1, 0)],
+        0,
+        LollysLottoError::SwapAmountExceeded,
+    );
+    let lolly_out = expected_lolly_out(usdc_in);
+    test_state.execute_expecting_err(
+        [swap_and_burn_ix(usdc_in, lolly_out + 1)],
+        0,
+        LollysLottoError::SwapSlippageExceeded,
+    );
+
+    // 4. The burn can't exceed the burn cap of the epoch
+    test_state.execute_set_burn_cap_ix(
+        lolly_out - 1,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state
+            .get_lolly_burn_state(lolly_burn_state_pda)
+            .burn_cap_per_epoch,
+        lolly_out - 1
+    );
+    test_state.execute_expecting_err(
+        [swap_and_burn_ix(usdc_in, lolly_out)],
+        0,
+        LollysLottoError::BurnCapExceeded,
+    );
+
+    // 5. Swap and burn exactly the LOLLY received, within the cap
+    test_state.execute_set_burn_cap_ix(
+        lolly_out,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    let prev_lolly_supply = test_state.get_mint_supply(LOLLY_MINT);
+    test_state.execute_swap_and_burn_ix(
+        round,
+        usdc_in,
+        lolly_out,
+        route.clone(),
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &lotto_game_pda,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &event_emitter_pda,
+    );
+
+    assert_eq!(
+        test_state.get_ata_balance(lolly_burn_state_usdc_vault),
+        2_000_000 - usdc_in
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lolly_burn_state_lolly_vault),
+        idle_lolly
+    );
+    assert_eq!(
+        test_state.get_mint_supply(LOLLY_MINT),
+        prev_lolly_supply - lolly_out
+    );
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    assert_eq!(lolly_burn_state.total_usdc_swapped, usdc_in);
+    assert_eq!(lolly_burn_state.total_lolly_acquired, lolly_out);
+    assert_eq!(lolly_burn_state.total_lolly_burnt, lolly_out);
+    assert_eq!(lolly_burn_state.burn_epoch, test_state.clock().epoch);
+    assert_eq!(lolly_burn_state.lolly_burnt_in_epoch, lolly_out);
+    assert_eq!(
+        lolly_burn_state.round_burn(round),
+        Some(&RoundBurn {
+            round,
+            usdc_received: 0,
+            usdc_swapped: usdc_in,
+            lolly_burnt: lolly_out,
+        })
+    );
+
+    // 6. The cap of this epoch is used up, even for burn_lolly
+    test_state.execute_expecting_err(
+        [swap_and_burn_ix(usdc_in, 0)],
+        0,
+        LollysLottoError::BurnCapExceeded,
+    );
+    test_state.execute_expecting_err(
+        [burn_lolly(
+            &LOLLY_MINT,
+            &test_state.test_admin,
+            &lolly_burn_state_pda,
+            &lolly_burn_state_lolly_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::BurnCapExceeded,
+    );
+
+    // 7. Removing the cap allows burning the rest
+    test_state.execute_set_burn_cap_ix(
+        0,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    let lolly_out = expected_lolly_out(usdc_in);
+    test_state.execute_swap_and_burn_ix(
+        round,
+        usdc_in,
+        lolly_out,
+        route,
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &lotto_game_pda,
+        &lolly_burn_state_usdc_vault,
+        &lolly_burn_state_lolly_vault,
+        &event_emitter_pda,
+    );
+    assert_eq!(test_state.get_ata_balance(lolly_burn_state_usdc_vault), 0);
+    assert_eq!(
+        test_state.get_ata_balance(lolly_burn_state_lolly_vault),
+        idle_lolly
+    );
+    let lolly_burn_state = test_state.get_lolly_burn_state(lolly_burn_state_pda);
+    let round_burn = lolly_burn_state.round_burn(round).unwrap();
+    assert_eq!(round_burn.usdc_swapped, 2 * usdc_in);
+    assert_eq!(round_burn.lolly_burnt, lolly_burn_state.total_lolly_burnt);
+    assert_eq!(round_burns(&lolly_burn_state), vec![*round_burn]);
+}
+
+#[test]
+fn test_lolly_staking() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+
+    // 2. Create the Stake Pool, discounts can't exceed the DAO share of the ticket
+    let stake_pool_pda = get_stake_pool_pda(test_state.test_admin);
+    let stake_vault = get_stake_pool_stake_vault(test_state.test_admin);
+    let reward_vault = get_stake_pool_reward_vault(test_state.test_admin);
+    let unstake_cooldown: i64 = 86400;
+    let discount_tiers = [
+        StakeDiscountTier {
+            min_staked: 1_000_000_000,
+            discount_bps: 100,
+        },
+        StakeDiscountTier {
+            min_staked: 10_000_000_000,
+            discount_bps: 200,
+        },
+        StakeDiscountTier::default(),
+    ];
+    test_state.execute_create_stake_pool_ix(
+        unstake_cooldown,
+        discount_tiers,
+        &test_state.test_admin,
+        &stake_pool_pda,
+        &LOLLY_MINT,
+        &stake_vault,
+        &USDC_DEVNET_MINT,
+        &reward_vault,
+        &event_emitter_pda,
+    );
+    let stake_pool = test_state.get_stake_pool(stake_pool_pda);
+    assert_eq!(stake_pool.authority, test_state.test_admin);
+    assert_eq!(stake_pool.total_staked, 0);
+    assert_eq!(stake_pool.unstake_cooldown, unstake_cooldown);
+    assert_eq!(stake_pool.discount_tiers, discount_tiers);
+
+    let mut invalid_discount_tiers = discount_tiers;
+    invalid_discount_tiers[2] = StakeDiscountTier {
+        min_staked: 100_000_000_000,
+        discount_bps: StakePool::MAX_DISCOUNT_BPS + 1,
+    };
+    test_state.execute_expecting_err(
+        [set_stake_pool_config(
+            unstake_cooldown,
+            invalid_discount_tiers,
+            &test_state.test_admin,
+            &stake_pool_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidStakePoolConfig,
+    );
+
+    // 3. Stake LOLLY with User 1
+    let stake_position_pda = get_stake_position_pda(stake_pool_pda, test_state.test_user1);
+    test_state.execute_create_stake_position_ix(
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &event_emitter_pda,
+    );
+    let staked_amount: u64 = 10_000_000_000;
+    let prev_user_lolly = test_state.get_ata_balance(TestUserLolly1.address());
+    test_state.execute_stake_lolly_ix(
+        staked_amount,
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &TestUserLolly1.address(),
+        &stake_vault,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(TestUserLolly1.address()),
+        prev_user_lolly - staked_amount
+    );
+    assert_eq!(test_state.get_ata_balance(stake_vault), staked_amount);
+    let stake_position = test_state.get_stake_position(stake_position_pda);
+    assert_eq!(stake_position.staked_amount, staked_amount);
+    assert_eq!(
+        stake_position.last_staked_at,
+        test_state.clock().unix_timestamp
+    );
+    assert_eq!(
+        test_state.get_stake_pool(stake_pool_pda).total_staked,
+        staked_amount
+    );
+
+    // 4. Staked tickets are discounted from the DAO share, however they are bought
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+    let numbers = LottoTicketNumbers {
+        number1: 1,
+        number2: 2,
+        number3: 3,
+        number4: 4,
+        number5: 5,
+        jackpot_number: 6,
+    };
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    let prev_user_usdc = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute_buy_lotto_ticket_with_stake_ix(
+        round,
+        numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_user_usdc1,
+        &test_state.test_usdc,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pda,
+        &event_emitter_pda,
+        &stake_pool_pda,
+        &stake_position_pda,
+    );
+    let discount = ticket_price * 200 / 10000;
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_usdc - (ticket_price - discount)
+    );
+    assert_eq!(
+        test_state.get_lotto_ticket(lotto_ticket_pda).ticket_price,
+        ticket_price - discount
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game.liabilities.dao,
+        ticket_price * LottoGame::DAO_BPS as u64 / 10000 - discount
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        lotto_game.liabilities.total().unwrap()
+    );
+
+    // A ticket bought through a purchase intent is discounted the same way
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let intent = TicketPurchaseIntent {
+        lotto_game: lotto_game_pda,
+        round,
+        numbers: LottoTicketNumbers {
+            jackpot_number: 7,
+            ..numbers
+        },
+        max_ticket_price: ticket_price - discount,
+        relayer_fee: 0,
+        expiry: lotto_game.end_date,
+        nonce: 0,
+    };
+    let signature = TEST_USER_KEYPAIR_1.sign_message(&intent.message());
+    let (intent_ticket_pda, _intent_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, intent.numbers);
+    test_state.execute_approve_spl_token(
+        ticket_price,
+        test_state.test_user_usdc1,
+        user_metadata_pda,
+        test_state.test_user1,
+    );
+    let prev_user_usdc = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute([
+        verify_ticket_purchase_intent(
+            &test_state.test_user1,
+            &<[u8; 64]>::try_from(signature.as_ref()).unwrap(),
+            &intent,
+        ),
+        buy_lotto_ticket_with_intent(
+            intent,
+            &test_state.test_user2,
+            &test_state.test_user_usdc2,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &intent_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, intent.numbers),
+            &event_emitter_pda,
+            Some(&stake_pool_pda),
+            Some(&stake_position_pda),
+            &spl_token::ID,
+        ),
+    ]);
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_usdc - (ticket_price - discount)
+    );
+    assert_eq!(
+        test_state.get_lotto_ticket(intent_ticket_pda).ticket_price,
+        ticket_price - discount
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(
+        lotto_game.liabilities.dao,
+        2 * (ticket_price * LottoGame::DAO_BPS as u64 / 10000 - discount)
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        lotto_game.liabilities.total().unwrap()
+    );
+
+    // 5. LOLLY can't be unstaked during the cooldown
+    test_state.execute_expecting_err(
+        [unstake_lolly(
+            staked_amount,
+            &test_state.test_user1,
+            &stake_pool_pda,
+            &stake_position_pda,
+            &TestUserLolly1.address(),
+            &stake_vault,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::UnstakeCooldownNotOver,
+    );
+
+    // 6. Close Round 1 and share its protocol fees with the stakers, only once
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let protocol_fees_amount = lotto_game.liabilities.protocol_fees;
+    test_state.execute_crank_distribute_protocol_fees_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &stake_pool_pda,
+        &reward_vault,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(reward_vault),
+        protocol_fees_amount
+    );
+    assert_eq!(
+        test_state
+            .get_lotto_game(lotto_game_pda)
+            .liabilities
+            .protocol_fees,
+        0
+    );
+    let stake_pool = test_state.get_stake_pool(stake_pool_pda);
+    assert_eq!(stake_pool.total_rewards_distributed, protocol_fees_amount);
+    test_state.execute_expecting_err(
+        [crank_distribute_protocol_fees(
+            round,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &stake_pool_pda,
+            Some(&reward_vault),
+            None,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::LiabilityExceeded,
+    );
+
+    // 7. The only staker claims every protocol fee
+    let prev_user_usdc = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute_claim_stake_rewards_ix(
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &test_state.test_user_usdc1,
+        &reward_vault,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_usdc + protocol_fees_amount
+    );
+    assert_eq!(test_state.get_ata_balance(reward_vault), 0);
+    assert_eq!(
+        test_state
+            .get_stake_position(stake_position_pda)
+            .total_rewards_claimed,
+        protocol_fees_amount
+    );
+
+    // 8. Unstake everything once the cooldown is over
+    test_state.set_timestamp(stake_position.last_staked_at + unstake_cooldown);
+    test_state.execute_unstake_lolly_ix(
+        staked_amount,
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &TestUserLolly1.address(),
+        &stake_vault,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(TestUserLolly1.address()),
+        prev_user_lolly
+    );
+    assert_eq!(test_state.get_ata_balance(stake_vault), 0);
+    assert_eq!(test_state.get_stake_pool(stake_pool_pda).total_staked, 0);
+    assert_eq!(
+        test_state
+            .get_stake_position(stake_position_pda)
+            .staked_amount,
+        0
+    );
+}
+
+#[test]
+fn test_staker_discount_capped_by_dao_share() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and a Token-2022 stablecoin with a 2% transfer fee
+    let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+    test_state.execute_create_event_emitter_ix(event_emitter_pda, test_state.test_admin);
+
+    let lollys_lotto_pda = LollysLotto::address(test_state.test_admin);
+    test_state.execute_create_lollys_lotto_ix(
+        test_state.test_admin,
+        lollys_lotto_pda,
+        event_emitter_pda,
+    );
+
+    let token_2022_mint = Pubkey::new_unique();
+    test_state.set_token_2022_mint_with_transfer_fee(token_2022_mint, 6, 200, 1_000_000_000);
+
+    // 2. Start Round 1 in the Token-2022 mint
+    let round: u64 = 0;
+    let ticket_price: u64 = 1000000;
+    let (lotto_game_pda, _lotto_game_bump) =
+        get_lotto_game_pda_and_bump(&test_state.test_admin, round);
+    let (lotto_game_vault_signer, _lotto_game_vault_signer_bump) =
+        get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda);
+    let lotto_game_vault_pda =
+        get_lotto_game_vault_pda_for_mint(&lotto_game_pda, &token_2022_mint, &spl_token_2022::ID);
+    test_state.execute([start_lotto_game(
+        round,
+        ticket_price,
+        3600,
+        0,
+        86400,
+        "Round 1".to_string(),
+        LottoGameVersion::V1,
+        &test_state.test_admin,
+        &test_state.lollys_lotto,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &get_draw_record_pda(&lotto_game_pda),
+        &token_2022_mint,
+        &event_emitter_pda,
+        &spl_token_2022::ID,
+    )]);
+
+    // 3. User 1 stakes enough LOLLY for the highest discount the Stake Pool allows
+    let stake_pool_pda = get_stake_pool_pda(test_state.test_admin);
+    let stake_vault = get_stake_pool_stake_vault(test_state.test_admin);
+    let min_staked: u64 = 1_000_000_000;
+    test_state.execute_create_stake_pool_ix(
+        86400,
+        [
+            StakeDiscountTier {
+                min_staked,
+                discount_bps: StakePool::MAX_DISCOUNT_BPS,
+            },
+            StakeDiscountTier::default(),
+            StakeDiscountTier::default(),
+        ],
+        &test_state.test_admin,
+        &stake_pool_pda,
+        &LOLLY_MINT,
+        &stake_vault,
+        &USDC_DEVNET_MINT,
+        &get_stake_pool_reward_vault(test_state.test_admin),
+        &event_emitter_pda,
+    );
+    let stake_position_pda = get_stake_position_pda(stake_pool_pda, test_state.test_user1);
+    test_state.execute_create_stake_position_ix(
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_stake_lolly_ix(
+        min_staked,
+        &test_state.test_user1,
+        &stake_pool_pda,
+        &stake_position_pda,
+        &TestUserLolly1.address(),
+        &stake_vault,
+        &event_emitter_pda,
+    );
+
+    // 4. The discount and the transfer fee never exceed the DAO share of the ticket, so the
+    //    discount is cut by the fee withheld on the full ticket price
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &get_user_rewards_vault_address(test_state.test_user1),
+        &event_emitter_pda,
+    );
+    let user_token_account = get_associated_token_address_with_program_id(
+        &test_state.test_user1,
+        &token_2022_mint,
+        &spl_token_2022::ID,
+    );
+    test_state.execute_create_token_2022_account(
+        &test_state.test_user1,
+        &token_2022_mint,
+        ticket_price,
+    );
+    let numbers = LottoTicketNumbers {
+        number1: 1,
+        number2: 2,
+        number3: 3,
+        number4: 4,
+        number5: 5,
+        jackpot_number: 6,
+    };
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    test_state.execute([buy_lotto_ticket(
+        round,
+        numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_token_account,
+        &token_2022_mint,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, numbers),
+        &event_emitter_pda,
+        Some(&stake_pool_pda),
+        Some(&stake_position_pda),
+        &spl_token_2022::ID,
+    )]);
+
+    let dao_share = ticket_price * LottoGame::DAO_BPS as u64 / 10000;
+    let max_transfer_fee = test_state.get_token_2022_transfer_fee(token_2022_mint, ticket_price);
+    let discount = dao_share - max_transfer_fee;
+    assert!(discount < ticket_price * StakePool::MAX_DISCOUNT_BPS as u64 / 10000);
+    let transfer_fee =
+        test_state.get_token_2022_transfer_fee(token_2022_mint, ticket_price - discount);
+    assert_eq!(
+        test_state.get_token_2022_balance(user_token_account),
+        discount
+    );
+    assert_eq!(
+        test_state.get_lotto_ticket(lotto_ticket_pda).ticket_price,
+        ticket_price - discount
+    );
+    let mut expected_liabilities = LottoGameLiabilities::default();
+    expected_liabilities
+        .reserve_ticket_sale(LottoGameVersion::V1, ticket_price, 0)
+        .unwrap();
+    expected_liabilities
+        .release_dao(discount + transfer_fee)
+        .unwrap();
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities, expected_liabilities);
+    assert!(lotto_game.liabilities.dao <= dao_share);
+    assert!(lotto_game
+        .check_solvency(test_state.get_token_2022_balance(lotto_game_vault_pda))
+        .is_ok());
+}
+
+#[test]
+fn test_buy_lotto_ticket_with_lolly() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto, Round 1, Lolly Burn State and User Metadata
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+
+    let lolly_burn_state_pda = get_lolly_burn_state_pda(test_state.test_admin);
+    test_state.execute_create_lolly_burn_state_ix(
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &LOLLY_MINT,
+        &get_lolly_burn_state_lolly_vault(test_state.test_admin),
+        &USDC_DEVNET_MINT,
+        &get_lolly_burn_state_usdc_vault(test_state.test_admin),
+        &event_emitter_pda,
+    );
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &get_user_rewards_vault_address(test_state.test_user1),
+        &event_emitter_pda,
+    );
+
+    // 2. Create a LOLLY/USDC mock pool and allowlist it
+    test_state.execute_initialize_mock_swap_pool_ix(
+        &test_state.test_admin,
+        &LOLLY_MINT,
+        &USDC_DEVNET_MINT,
+    );
+    let pool = MockSwapPool::address(LOLLY_MINT, USDC_DEVNET_MINT);
+    let mock_swap_pool = test_state.get_mock_swap_pool(pool);
+    test_state.execute_transfer_spl_token(
+        2_000_000_000,
+        TestAdminLolly.address(),
+        mock_swap_pool.vault_a,
+        test_state.test_admin,
+    );
+    test_state.execute_transfer_spl_token(
+        1_000_000_000,
+        TestAdminUsdc.address(),
+        mock_swap_pool.vault_b,
+        test_state.test_admin,
+    );
+    test_state.execute_set_swap_programs_ix(
+        vec![mock_swap::ID],
+        &test_state.test_admin,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+
+    // 3. The LOLLY payment discount can't exceed the DAO share of the ticket
+    test_state.execute_expecting_err(
+        [set_lolly_payment_discount(
+            LollysLotto::MAX_LOLLY_PAYMENT_DISCOUNT_BPS + 1,
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        LollysLottoError::InvalidLollyPaymentDiscount,
+    );
+    let discount_bps: u16 = 200;
+    test_state.execute_set_lolly_payment_discount_ix(
+        discount_bps,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state
+            .get_lollys_lotto(lollys_lotto_pda)
+            .lolly_payment_discount_bps,
+        discount_bps
+    );
+
+    let numbers = LottoTicketNumbers {
+        number1: 1,
+        number2: 2,
+        number3: 3,
+        number4: 4,
+        number5: 5,
+        jackpot_number: 6,
+    };
+    let (lotto_ticket_pda, _lotto_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, numbers);
+    let lolly_in: u64 = 5_000_000;
+    let usdc_out = {
+        let reserve_lolly = test_state.get_ata_balance(mock_swap_pool.vault_a) as u128;
+        let reserve_usdc = test_state.get_ata_balance(mock_swap_pool.vault_b) as u128;
+        (reserve_usdc * lolly_in as u128 / (reserve_lolly + lolly_in as u128)) as u64
+    };
+    let route = test_state.mock_swap_route(
+        lolly_in,
+        0,
+        &test_state.test_user1,
+        &pool,
+        &TestUserLolly1.address(),
+        &test_state.test_user_usdc1,
+        &LOLLY_MINT,
+        &USDC_DEVNET_MINT,
+    );
+    let buy_with_swap_ix = |payment_asset: PaymentAsset, max_amount_in: u64| {
+        buy_lotto_ticket_with_swap(
+            round,
+            numbers,
+            payment_asset,
+            max_amount_in,
+            route.clone(),
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &TestUserLolly1.address(),
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, numbers),
+            &lollys_lotto_pda,
+            &lolly_burn_state_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )
+    };
+
+    // 4. The payment asset must match the payment token account, and USDC has its own path
+    test_state.execute_expecting_err(
+        [buy_with_swap_ix(PaymentAsset::Sol, lolly_in)],
+        0,
+        LollysLottoError::InvalidPaymentAsset,
+    );
+    test_state.execute_expecting_err(
+        [buy_with_swap_ix(PaymentAsset::Usdc, lolly_in)],
+        0,
+        LollysLottoError::InvalidPaymentAsset,
+    );
+
+    // 5. The swap can't spend more than max_amount_in
+    test_state.execute_expecting_err(
+        [buy_with_swap_ix(PaymentAsset::Lolly, lolly_in - 1)],
+        0,
+        LollysLottoError::SwapAmountExceeded,
+    );
+
+    // 6. Pay with LOLLY, the discount comes out of the DAO share
+    let prev_user_lolly = test_state.get_ata_balance(TestUserLolly1.address());
+    let prev_user_usdc = test_state.get_ata_balance(test_state.test_user_usdc1);
+    test_state.execute_buy_lotto_ticket_with_swap_ix(
+        round,
+        numbers,
+        PaymentAsset::Lolly,
+        lolly_in,
+        route.clone(),
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_user_usdc1,
+        &TestUserLolly1.address(),
+        &test_state.test_usdc,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pda,
+        &lollys_lotto_pda,
+        &lolly_burn_state_pda,
+        &event_emitter_pda,
+    );
+    let discount = ticket_price * discount_bps as u64 / 10000;
+    assert_eq!(
+        test_state.get_ata_balance(TestUserLolly1.address()),
+        prev_user_lolly - lolly_in
+    );
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc1),
+        prev_user_usdc + usdc_out - (ticket_price - discount)
+    );
+    let lotto_ticket = test_state.get_lotto_ticket(lotto_ticket_pda);
+    assert_eq!(lotto_ticket.payment_asset, PaymentAsset::Lolly);
+    assert_eq!(lotto_ticket.ticket_price, ticket_price - discount);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.tickets_sold, 1);
+    assert_eq!(
+        lotto_game.liabilities.dao,
+        ticket_price * LottoGame::DAO_BPS as u64 / 10000 - discount
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        lotto_game.liabilities.total().unwrap()
+    );
+}
+
+#[test]
+fn test_token_2022_lotto_game() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and a Token-2022 stablecoin with a 1% transfer fee
+    let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+    test_state.execute_create_event_emitter_ix(event_emitter_pda, test_state.test_admin);
+
+    let lollys_lotto_pda = LollysLotto::address(test_state.test_admin);
+    test_state.execute_create_lollys_lotto_ix(
+        test_state.test_admin,
+        lollys_lotto_pda,
+        event_emitter_pda,
+    );
+
+    let token_2022_mint = Pubkey::new_unique();
+    test_state.set_token_2022_mint_with_transfer_fee(token_2022_mint, 6, 100, 1_000_000_000);
+
+    // 2. Start Round 1 in the Token-2022 mint
+    let round: u64 = 0;
+    let ticket_price: u64 = 1000000;
+    let (lotto_game_pda, _lotto_game_bump) =
+        get_lotto_game_pda_and_bump(&test_state.test_admin, round);
+    let (lotto_game_vault_signer, _lotto_game_vault_signer_bump) =
+        get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda);
+    let lotto_game_vault_pda =
+        get_lotto_game_vault_pda_for_mint(&lotto_game_pda, &token_2022_mint, &spl_token_2022::ID);
+    let draw_record_pda = get_draw_record_pda(&lotto_game_pda);
+    test_state.execute([start_lotto_game(
+        round,
+        ticket_price,
+        3600,
+        0,
+        86400,
+        "Round 1".to_string(),
+        LottoGameVersion::V1,
+        &test_state.test_admin,
+        &test_state.lollys_lotto,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &draw_record_pda,
+        &token_2022_mint,
+        &event_emitter_pda,
+        &spl_token_2022::ID,
+    )]);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.lotto_game_mint, token_2022_mint);
+    assert_eq!(lotto_game.lotto_game_vault, lotto_game_vault_pda);
+
+    // 3. Buy a jackpot ticket, the vault is credited the price net of the transfer fee
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &get_user_rewards_vault_address(test_state.test_user1),
+        &event_emitter_pda,
+    );
+    let user_token_account = get_associated_token_address_with_program_id(
+        &test_state.test_user1,
+        &token_2022_mint,
+        &spl_token_2022::ID,
+    );
+    test_state.execute_create_token_2022_account(
+        &test_state.test_user1,
+        &token_2022_mint,
+        10 * ticket_price,
+    );
+    let user_rewards_vault = get_user_rewards_vault_address_for_mint(
+        test_state.test_user1,
+        &token_2022_mint,
+        &spl_token_2022::ID,
+    );
+    test_state.execute_create_token_2022_account(&user_metadata_pda, &token_2022_mint, 0);
+
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    test_state.execute([buy_lotto_ticket(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_token_account,
+        &token_2022_mint,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, jackpot_numbers),
+        &event_emitter_pda,
+        None,
+        None,
+        &spl_token_2022::ID,
+    )]);
+
+    let transfer_fee = test_state.get_token_2022_transfer_fee(token_2022_mint, ticket_price);
+    assert_eq!(transfer_fee, ticket_price / 100);
+    assert_eq!(
+        test_state.get_token_2022_balance(user_token_account),
+        9 * ticket_price
+    );
+    assert_eq!(
+        test_state.get_token_2022_balance(lotto_game_vault_pda),
+        ticket_price - transfer_fee
+    );
+    let mut expected_liabilities = LottoGameLiabilities::default();
+    expected_liabilities
+        .reserve_ticket_sale(LottoGameVersion::V1, ticket_price, 0)
+        .unwrap();
+    expected_liabilities.release_dao(transfer_fee).unwrap();
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities, expected_liabilities);
+    assert!(lotto_game
+        .check_solvency(test_state.get_token_2022_balance(lotto_game_vault_pda))
+        .is_ok());
+    test_state.execute_verify_lotto_game_solvency_ix(
+        round,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+
+    // 4. A subscription escrows the transfer fee on top and plays the Token-2022 round,
+    //    a USDC subscription can't
+    let cranker_fee: u64 = 10000;
+    let subscription_numbers = LottoTicketNumbers {
+        number1: 7,
+        number2: 8,
+        number3: 9,
+        number4: 10,
+        number5: 11,
+        jackpot_number: 1,
+    };
+    let (ticket_subscription_pda, _ticket_subscription_bump) =
+        get_ticket_subscription_pda_and_bump(user_metadata_pda, test_state.test_admin, 0);
+    let subscription_vault = get_ticket_subscription_vault_address_for_mint(
+        ticket_subscription_pda,
+        &token_2022_mint,
+        &spl_token_2022::ID,
+    );
+    let prev_user_balance = test_state.get_token_2022_balance(user_token_account);
+    test_state.execute([create_ticket_subscription(
+        0,
+        1,
+        Some(subscription_numbers),
+        ticket_price,
+        cranker_fee,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_admin,
+        &ticket_subscription_pda,
+        &token_2022_mint,
+        &user_token_account,
+        &subscription_vault,
+        &event_emitter_pda,
+        &spl_token_2022::ID,
+    )]);
+    assert_eq!(
+        test_state.get_token_2022_balance(subscription_vault),
+        ticket_price + cranker_fee
+    );
+    assert!(
+        test_state.get_token_2022_balance(user_token_account)
+            < prev_user_balance - (ticket_price + cranker_fee)
+    );
+
+    let cranker_token_account = get_associated_token_address_with_program_id(
+        &test_state.test_user2,
+        &token_2022_mint,
+        &spl_token_2022::ID,
+    );
+    test_state.execute_create_token_2022_account(&test_state.test_user2, &token_2022_mint, 0);
+    let (subscription_ticket_pda, _subscription_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, subscription_numbers);
+    let prev_lotto_game_vault_balance = test_state.get_token_2022_balance(lotto_game_vault_pda);
+    test_state.execute([crank_fulfil_subscription(
+        round,
+        subscription_numbers,
+        &test_state.test_user2,
+        &cranker_token_account,
+        &test_state.test_admin,
+        &user_metadata_pda,
+        &ticket_subscription_pda,
+        &subscription_vault,
+        &lotto_game_pda,
+        &token_2022_mint,
+        &lotto_game_vault_pda,
+        &subscription_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, subscription_numbers),
+        &event_emitter_pda,
+        None,
+        None,
+        &spl_token_2022::ID,
+    )]);
+    assert_eq!(test_state.get_token_2022_balance(subscription_vault), 0);
+    assert_eq!(
+        test_state.get_token_2022_balance(lotto_game_vault_pda),
+        prev_lotto_game_vault_balance + ticket_price - transfer_fee
+    );
+    assert_eq!(
+        test_state.get_token_2022_balance(cranker_token_account),
+        cranker_fee - test_state.get_token_2022_transfer_fee(token_2022_mint, cranker_fee)
+    );
+    expected_liabilities
+        .reserve_ticket_sale(LottoGameVersion::V1, ticket_price, 1)
+        .unwrap();
+    expected_liabilities.release_dao(transfer_fee).unwrap();
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities, expected_liabilities);
+    assert!(lotto_game
+        .check_solvency(test_state.get_token_2022_balance(lotto_game_vault_pda))
+        .is_ok());
+
+    let (usdc_subscription_pda, _usdc_subscription_bump) =
+        get_ticket_subscription_pda_and_bump(user_metadata_pda, test_state.test_admin, 1);
+    let usdc_subscription_vault = get_ticket_subscription_vault_address(usdc_subscription_pda);
+    let usdc_subscription_numbers = LottoTicketNumbers {
+        jackpot_number: 2,
+        ..subscription_numbers
+    };
+    test_state.execute_create_ticket_subscription_ix(
+        1,
+        1,
+        Some(usdc_subscription_numbers),
+        ticket_price,
+        cranker_fee,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_admin,
+        &usdc_subscription_pda,
+        &test_state.test_usdc,
+        &test_state.test_user_usdc1,
+        &usdc_subscription_vault,
+        &event_emitter_pda,
+    );
+    let (usdc_subscription_ticket_pda, _usdc_subscription_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, usdc_subscription_numbers);
+    test_state.execute_expecting_err(
+        [crank_fulfil_subscription(
+            round,
+            usdc_subscription_numbers,
+            &test_state.test_user2,
+            &cranker_token_account,
+            &test_state.test_admin,
+            &user_metadata_pda,
+            &usdc_subscription_pda,
+            &usdc_subscription_vault,
+            &lotto_game_pda,
+            &token_2022_mint,
+            &lotto_game_vault_pda,
+            &usdc_subscription_ticket_pda,
+            &LottoTicketCombination::address(lotto_game_pda, usdc_subscription_numbers),
+            &event_emitter_pda,
+            None,
+            None,
+            &spl_token_2022::ID,
+        )],
+        0,
+        LollysLottoError::SubscriptionMintMismatch,
+    );
+
+    // 5. Close the round and settle the jackpot, the user is credited the prize net of the fee
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    let prev_lotto_game_vault_balance = test_state.get_token_2022_balance(lotto_game_vault_pda);
+    test_state.execute([crank_settle_winning_ticket(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &token_2022_mint,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &LottoTicketCombination::address(lotto_game_pda, jackpot_numbers),
+        &test_state.test_user1,
+        &event_emitter_pda,
+        &spl_token_2022::ID,
+    )]);
+
+    let prize_received = jackpot_winning_amount
+        - test_state.get_token_2022_transfer_fee(token_2022_mint, jackpot_winning_amount);
+    let jackpot_ticket = test_state.get_lotto_ticket(jackpot_ticket_pda);
+    assert_eq!(jackpot_ticket.prize, jackpot_winning_amount);
+    assert_eq!(
+        test_state.get_token_2022_balance(lotto_game_vault_pda),
+        prev_lotto_game_vault_balance - jackpot_winning_amount
+    );
+    assert_eq!(
+        test_state.get_token_2022_balance(user_rewards_vault),
+        prize_received
+    );
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.total_amount_won, prize_received);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert!(lotto_game
+        .check_solvency(test_state.get_token_2022_balance(lotto_game_vault_pda))
+        .is_ok());
+
+    // 6. Rewards are claimable per mint, the empty USDC rewards vault has nothing to claim
+    test_state.execute_expecting_err(
+        [claim_user_rewards(
+            prize_received,
+            &test_state.test_user1,
+            &test_state.test_user_usdc1,
+            &user_metadata_pda,
+            &test_state.test_usdc,
+            &get_user_rewards_vault_address(test_state.test_user1),
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::NoRewardsToClaimFromVault,
+    );
+
+    // 7. Claim the prize from the Token-2022 rewards vault
+    let prev_user_balance = test_state.get_token_2022_balance(user_token_account);
+    test_state.execute([claim_user_rewards(
+        prize_received,
+        &test_state.test_user1,
+        &user_token_account,
+        &user_metadata_pda,
+        &token_2022_mint,
+        &user_rewards_vault,
+        &event_emitter_pda,
+        &spl_token_2022::ID,
+    )]);
+    assert_eq!(test_state.get_token_2022_balance(user_rewards_vault), 0);
+    assert_eq!(
+        test_state.get_token_2022_balance(user_token_account),
+        prev_user_balance + prize_received
+            - test_state.get_token_2022_transfer_fee(token_2022_mint, prize_received)
+    );
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(user_metadata.total_amount_claimed, prize_received);
+}
+
+#[test]
+fn test_add_to_prize_pool() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto, Round 1 and User Metadata
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.sponsored_prize_pool, [0; 4]);
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    // 2. Buy a jackpot ticket
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    test_state.execute_buy_lotto_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_user_usdc1,
+        &test_state.test_usdc,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+
+    // 3. A sponsor tops up the jackpot
+    let jackpot_top_up: u64 = 10_000_000;
+    let prev_sponsor_balance = test_state.get_ata_balance(test_state.test_user_usdc2);
+    test_state.execute_add_to_prize_pool_ix(
+        round,
+        0,
+        jackpot_top_up,
+        &test_state.test_user2,
+        &test_state.test_user_usdc2,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+
+    assert_eq!(
+        test_state.get_ata_balance(test_state.test_user_usdc2),
+        prev_sponsor_balance - jackpot_top_up
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        ticket_price + jackpot_top_up
+    );
+    let mut expected_liabilities = LottoGameLiabilities::default();
+    expected_liabilities
+        .reserve_ticket_sale(LottoGameVersion::V1, ticket_price, 0)
+        .unwrap();
+    expected_liabilities
+        .reserve_prize(0, jackpot_top_up)
+        .unwrap();
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.sponsored_prize_pool, [jackpot_top_up, 0, 0, 0]);
+    assert_eq!(lotto_game.liabilities, expected_liabilities);
+    assert_eq!(
+        lotto_game.current_jackpot_winning_amount(),
+        ticket_price / 2 + jackpot_top_up
+    );
+    assert!(lotto_game
+        .check_solvency(test_state.get_ata_balance(lotto_game_vault_pda))
+        .is_ok());
+
+    // 4. Empty top-ups and top-ups to any other tier are rejected
+    test_state.execute_expecting_err(
+        [add_to_prize_pool(
+            round,
+            0,
+            0,
+            &test_state.test_user2,
+            &test_state.test_user_usdc2,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidSponsorAmount,
+    );
+    for tier in 1..=4 {
+        test_state.execute_expecting_err(
+            [add_to_prize_pool(
+                round,
+                tier,
+                jackpot_top_up,
+                &test_state.test_user2,
+                &test_state.test_user_usdc2,
+                &test_state.test_usdc,
+                &lotto_game_pda,
+                &lotto_game_vault_pda,
+                &event_emitter_pda,
+                &spl_token::ID,
+            )],
+            0,
+            LollysLottoError::SponsoredTierNotJackpot,
+        );
+    }
+
+    // 5. Close the round and settle the jackpot, the prize includes the sponsored amount
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_expecting_err(
+        [add_to_prize_pool(
+            round,
+            0,
+            jackpot_top_up,
+            &test_state.test_user2,
+            &test_state.test_user_usdc2,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::LottoGameNotOpen,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    assert_eq!(jackpot_winning_amount, ticket_price / 2 + jackpot_top_up);
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+
+    let jackpot_ticket = test_state.get_lotto_ticket(jackpot_ticket_pda);
+    assert_eq!(jackpot_ticket.prize, jackpot_winning_amount);
+    assert_eq!(
+        test_state.get_ata_balance(user_rewards_vault),
+        jackpot_winning_amount
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        ticket_price - ticket_price / 2
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert!(lotto_game
+        .check_solvency(test_state.get_ata_balance(lotto_game_vault_pda))
+        .is_ok());
+}
+
+#[test]
+fn test_min_jackpot() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto, Round 1 and User Metadata
+    let treasury_token_account = TestAdminUsdc.address();
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::V1);
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    // 2. The treasury funds a minimum jackpot, only once and only from the treasury
+    let min_jackpot: u64 = 10_000_000;
+    test_state.execute_expecting_err(
+        [fund_min_jackpot(
+            round,
+            min_jackpot,
+            &test_state.test_user2,
+            &test_state.lollys_lotto,
+            &test_state.test_user_usdc2,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidTreasury,
+    );
+    test_state.execute_expecting_err(
+        [fund_min_jackpot(
+            round,
+            0,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &treasury_token_account,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidMinJackpot,
+    );
+
+    let prev_treasury_balance = test_state.get_ata_balance(treasury_token_account);
+    test_state.execute_fund_min_jackpot_ix(
+        round,
+        min_jackpot,
+        &test_state.test_admin,
+        &treasury_token_account,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(treasury_token_account),
+        prev_treasury_balance - min_jackpot
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        min_jackpot
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.min_jackpot, min_jackpot);
+    assert_eq!(lotto_game.liabilities.jackpot, min_jackpot);
+    assert_eq!(
+        estimate_winning_amounts(&lotto_game),
+        [min_jackpot, 0, 0, 0]
+    );
+
+    test_state.execute_expecting_err(
+        [fund_min_jackpot(
+            round,
+            min_jackpot,
+            &test_state.test_admin,
+            &test_state.lollys_lotto,
+            &treasury_token_account,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidMinJackpot,
+    );
+
+    // 3. Buy a jackpot ticket, the revenue share is still below the minimum jackpot
+    let seed = [7u8; 32];
+    let [jackpot_numbers] = drawn_winning_numbers(&seed);
+    let (jackpot_ticket_pda, _jackpot_ticket_bump) =
+        get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, jackpot_numbers);
+    test_state.execute_buy_lotto_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_user_usdc1,
+        &test_state.test_usdc,
+        &lotto_game_pda,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.current_jackpot_winning_amount(), min_jackpot);
+    assert!(lotto_game
+        .check_solvency(test_state.get_ata_balance(lotto_game_vault_pda))
+        .is_ok());
+
+    // 4. Close the round and settle the jackpot, the winner is paid the minimum jackpot
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_test_emit_winning_numbers_ix(
+        seed.to_vec(),
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        jackpot_numbers,
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &jackpot_ticket_pda,
+        &event_emitter_pda,
+    );
+    let jackpot_ticket = test_state.get_lotto_ticket(jackpot_ticket_pda);
+    assert_eq!(jackpot_ticket.prize, min_jackpot);
+    assert_eq!(test_state.get_ata_balance(user_rewards_vault), min_jackpot);
+
+    // 5. Finish the round, the part of the seed covered by ticket sales goes back to the treasury
+    let jackpot_revenue_share = ticket_price / 2;
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.unused_min_jackpot(), jackpot_revenue_share);
+    let prev_treasury_balance = test_state.get_ata_balance(treasury_token_account);
+    let prev_lotto_game_vault_balance = test_state.get_ata_balance(lotto_game_vault_pda);
+    test_state.set_timestamp(lotto_game.claim_deadline);
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        Some(&treasury_token_account),
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state.get_ata_balance(treasury_token_account),
+        prev_treasury_balance + jackpot_revenue_share
+    );
+    assert_eq!(
+        test_state.get_ata_balance(lotto_game_vault_pda),
+        prev_lotto_game_vault_balance - jackpot_revenue_share
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Finished);
+    assert_eq!(lotto_game.liabilities.jackpot, 0);
+    assert!(lotto_game
+        .check_solvency(test_state.get_ata_balance(lotto_game_vault_pda))
+        .is_ok());
+}
+
+#[test]
+fn test_raffle_lotto_game() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto, a raffle Round 1 and User Metadata
+    let ticket_price: u64 = 1000000;
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        draw_record_pda,
+        ..
+    } = test_state.start_round(ticket_price, 86400, LottoGameVersion::Raffle);
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.version, LottoGameVersion::Raffle);
+
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+
+    // 2. Buy three tickets, numbered 0, 1 and 2 in order of purchase
+    let ticket_numbers: Vec<LottoTicketNumbers> = (1..=3)
+        .map(|i| LottoTicketNumbers {
+            number1: i,
+            number2: i + 1,
+            number3: i + 2,
+            number4: i + 3,
+            number5: i + 4,
+            jackpot_number: i,
+        })
+        .collect();
+    let mut lotto_ticket_pdas = vec![];
+    for numbers in ticket_numbers.iter() {
+        let (lotto_ticket_pda, _lotto_ticket_bump) =
+            get_lotto_ticket_pda_and_bump(lotto_game_pda, user_metadata_pda, *numbers);
+        test_state.execute_buy_lotto_ticket_ix(
+            round,
+            *numbers,
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &test_state.test_user_usdc1,
+            &test_state.test_usdc,
+            &lotto_game_pda,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pda,
+            &event_emitter_pda,
+        );
+        lotto_ticket_pdas.push(lotto_ticket_pda);
+    }
+    for (i, lotto_ticket_pda) in lotto_ticket_pdas.iter().enumerate() {
+        let lotto_ticket = test_state.get_lotto_ticket(*lotto_ticket_pda);
+        assert_eq!(lotto_ticket.ticket_number, i as u64);
+    }
+
+    // A raffle has no tiers, their shares of the ticket sales are reserved for its jackpot
+    let raffle_jackpot = 3 * ticket_price * LottoGame::RAFFLE_JACKPOT_WINNING_BPS as u64 / 10000;
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities.jackpot, raffle_jackpot);
+    assert_eq!(lotto_game.liabilities.tier_1, 0);
+    assert_eq!(lotto_game.liabilities.tier_2, 0);
+    assert_eq!(lotto_game.liabilities.tier_3, 0);
+
+    // 3. Close the round and draw it with stubbed Switchboard On-Demand randomness
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.tickets_sold, 3);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_set_randomness_provider_ix(
+        RandomnessProvider::SwitchboardOnDemand,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+
+    let randomness_account = Pubkey::new_unique();
+    let commit_slot = 100;
+    test_state.set_slot(commit_slot);
+    let mut randomness_data: RandomnessAccountData = bytemuck::Zeroable::zeroed();
+    randomness_data.seed_slot = commit_slot - 1;
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_commit_winning_numbers_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+
+    let reveal_slot = commit_slot + 2;
+    test_state.set_slot(reveal_slot);
+    randomness_data.reveal_slot = reveal_slot;
+    randomness_data.value = [42u8; 32];
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_reveal_winning_numbers_ix(
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+
+    // 4. The reveal draws the winning ticket number in one go
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    let winning_ticket_number = derive_raffle_winning_ticket_number(&[42u8; 32], 3);
+    assert!(winning_ticket_number < 3);
+    assert_eq!(
+        lotto_game.raffle_winning_ticket_number,
+        winning_ticket_number
+    );
+    let draw_record = test_state.get_draw_record(draw_record_pda);
+    assert!(verify_winning_numbers(&draw_record, &lotto_game).unwrap());
+
+    let mut tampered_lotto_game = lotto_game;
+    tampered_lotto_game.raffle_winning_ticket_number = (winning_ticket_number + 1) % 3;
+    assert!(!verify_winning_numbers(&draw_record, &tampered_lotto_game).unwrap());
+
+    // 5. A ticket with any other ticket number can't be settled
+    let winning_index = winning_ticket_number as usize;
+    let losing_index = (winning_index + 1) % 3;
+    test_state.execute_expecting_err(
+        [crank_settle_winning_ticket(
+            round,
+            ticket_numbers[losing_index],
+            &test_state.test_admin,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &user_rewards_vault,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &test_state.test_usdc,
+            &lotto_game_vault_pda,
+            &lotto_ticket_pdas[losing_index],
+            &LottoTicketCombination::address(lotto_game_pda, ticket_numbers[losing_index]),
+            &test_state.test_user1,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )],
+        0,
+        LollysLottoError::InvalidWinningTicket,
+    );
+
+    // 6. The winning ticket is paid the jackpot
+    let jackpot_winning_amount = lotto_game.final_jackpot_winning_amount().unwrap();
+    assert_eq!(jackpot_winning_amount, raffle_jackpot);
+    test_state.execute_crank_settle_winning_ticket_ix(
+        round,
+        ticket_numbers[winning_index],
+        &test_state.test_admin,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &user_rewards_vault,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        &lotto_ticket_pdas[winning_index],
+        &event_emitter_pda,
+    );
+    let winning_ticket = test_state.get_lotto_ticket(lotto_ticket_pdas[winning_index]);
+    assert_eq!(winning_ticket.prize, jackpot_winning_amount);
+    assert_eq!(
+        test_state.get_ata_balance(user_rewards_vault),
+        jackpot_winning_amount
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.liabilities.jackpot, 0);
+    assert!(lotto_game
+        .check_solvency(test_state.get_ata_balance(lotto_game_vault_pda))
+        .is_ok());
+
+    // 7. Its only winner is paid, so the raffle can be finished before the claim deadline
+    assert!(!lotto_game.check_claim_deadline_passed(test_state.clock().unix_timestamp));
+    assert!(lotto_game.check_all_winning_amounts_disbursed());
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        None,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Finished);
+}
+
+#[test]
+fn test_raffle_lotto_game_without_tickets() {
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and a raffle Round 1
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        round,
+        lotto_game_pda,
+        lotto_game_vault_signer,
+        lotto_game_vault_pda,
+        draw_record_pda,
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::Raffle);
+
+    // 2. Close the round without selling a ticket and draw it with stubbed randomness
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    test_state.set_timestamp(lotto_game.end_date + 1);
+    test_state.execute_crank_lotto_game_closed_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_set_randomness_provider_ix(
+        RandomnessProvider::SwitchboardOnDemand,
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+
+    let randomness_account = Pubkey::new_unique();
+    let commit_slot = 100;
+    test_state.set_slot(commit_slot);
+    let mut randomness_data: RandomnessAccountData = bytemuck::Zeroable::zeroed();
+    randomness_data.seed_slot = commit_slot - 1;
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_commit_winning_numbers_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+
+    let reveal_slot = commit_slot + 2;
+    test_state.set_slot(reveal_slot);
+    randomness_data.reveal_slot = reveal_slot;
+    randomness_data.value = [42u8; 32];
+    test_state.set_randomness_account(randomness_account, &randomness_data);
+    test_state.execute_reveal_winning_numbers_ix(
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &draw_record_pda,
+        &randomness_account,
+        &event_emitter_pda,
+    );
+
+    // 3. Nothing is owed to anyone
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Drawn);
+    assert_eq!(lotto_game.tickets_sold, 0);
+    assert_eq!(lotto_game.raffle_winning_ticket_number, 0);
+    assert_eq!(lotto_game.final_jackpot_winning_amount().unwrap(), 0);
+    assert_eq!(lotto_game.liabilities.total().unwrap(), 0);
+    assert_eq!(test_state.get_ata_balance(lotto_game_vault_pda), 0);
+
+    // 4. There is no winner to wait for, so the raffle can be finished before the claim deadline
+    assert!(!lotto_game.check_claim_deadline_passed(test_state.clock().unix_timestamp));
+    assert!(lotto_game.check_all_winning_amounts_disbursed());
+    test_state.execute_crank_lotto_game_finished_ix(
+        round,
+        &test_state.test_admin,
+        &lotto_game_pda,
+        &lotto_game_vault_signer,
+        &lotto_game_vault_pda,
+        None,
+        &event_emitter_pda,
+    );
+    let lotto_game = test_state.get_lotto_game(lotto_game_pda);
+    assert_eq!(lotto_game.state, LottoGameState::Finished);
+}
+
+#[test]
+fn test_migrate_accounts() {
+    // Fields and size of LollysLotto and UserMetadata before the claim deadline, treasury,
+    // sweep destination, claim authority and intent nonce were appended to them
+    const PREVIOUS_LOLLYS_LOTTO_FIELDS_LEN: usize = 1 + 8 + 32;
+    const PREVIOUS_LOLLYS_LOTTO_SPACE: usize = 48;
+    const PREVIOUS_USER_METADATA_FIELDS_LEN: usize = 1 + 32 + 8 + 1 + 6 * 8;
+    const PREVIOUS_USER_METADATA_SPACE: usize = 104;
+
+    let test_state = TestState::new();
+
+    // 1. Create Event Emitter, Lollys Lotto and start Round 1
+    let TestRound {
+        event_emitter_pda,
+        lollys_lotto_pda,
+        ..
+    } = test_state.start_round(1000000, 86400, LottoGameVersion::V1);
+    let (user_metadata_pda, _user_metadata_bump) =
+        get_user_metadata_pda_and_bump(test_state.test_user1);
+    let user_rewards_vault = get_user_rewards_vault_address(test_state.test_user1);
+    test_state.execute_create_user_metadata_ix(
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &test_state.test_usdc,
+        &user_rewards_vault,
+        &event_emitter_pda,
+    );
+    let lollys_lotto = test_state.get_lollys_lotto(lollys_lotto_pda);
+    let user_metadata = test_state.get_user_metadata(user_metadata_pda);
+
+    // 2. Accounts created by an earlier version of the program can't be read until migrated
+    test_state.set_previous_account_layout(
+        lollys_lotto_pda,
+        PREVIOUS_LOLLYS_LOTTO_FIELDS_LEN,
+        PREVIOUS_LOLLYS_LOTTO_SPACE,
+    );
+    test_state.set_previous_account_layout(
+        user_metadata_pda,
+        PREVIOUS_USER_METADATA_FIELDS_LEN,
+        PREVIOUS_USER_METADATA_SPACE,
+    );
+    let treasury = Pubkey::new_unique();
+    let set_treasury = || {
+        set_sweep_destination(
+            SweepDestination::Treasury,
+            &treasury,
+            &test_state.test_admin,
+            &lollys_lotto_pda,
+            &event_emitter_pda,
+        )
+    };
+    let set_claimant = || {
+        set_claim_authority(
+            test_state.test_user2,
+            &test_state.test_user1,
+            &user_metadata_pda,
+            &event_emitter_pda,
+        )
+    };
+    test_state.execute_expecting_err(
+        [set_treasury()],
+        0,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize,
+    );
+    test_state.execute_expecting_err(
+        [set_claimant()],
+        0,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize,
+    );
+
+    // 3. Only the authority can migrate its LollysLotto
+    test_state.execute_expecting_err(
+        [migrate_lollys_lotto(
+            &test_state.test_user1,
+            &lollys_lotto_pda,
+            &event_emitter_pda,
+        )],
+        0,
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+
+    // 4. Migrating keeps the existing fields and zeroes the appended ones,
+    // anyone can pay for the migration of a UserMetadata
+    test_state.execute_migrate_lollys_lotto_ix(
+        &test_state.test_admin,
+        &lollys_lotto_pda,
+        &event_emitter_pda,
+    );
+    test_state.execute_migrate_user_metadata_ix(
+        &test_state.test_user2,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &event_emitter_pda,
+    );
+    for (pubkey, space) in [
+        (lollys_lotto_pda, std::mem::size_of::<LollysLotto>()),
+        (user_metadata_pda, std::mem::size_of::<UserMetadata>()),
+    ] {
+        let account = test_state.get_account(&pubkey).unwrap();
+        assert_eq!(account.data().len(), 8 + space);
+        assert_eq!(
+            account.lamports(),
+            Rent::default().minimum_balance(8 + space)
+        );
+    }
+    let migrated_lollys_lotto = test_state.get_lollys_lotto(lollys_lotto_pda);
+    assert_eq!(migrated_lollys_lotto.bump, lollys_lotto.bump);
+    assert_eq!(
+        migrated_lollys_lotto.lotto_game_count,
+        lollys_lotto.lotto_game_count
+    );
+    assert_eq!(migrated_lollys_lotto.authority, lollys_lotto.authority);
+    assert_eq!(migrated_lollys_lotto.treasury, Pubkey::default());
+    assert_eq!(migrated_lollys_lotto.lolly_payment_discount_bps, 0);
+    let migrated_user_metadata = test_state.get_user_metadata(user_metadata_pda);
+    assert_eq!(migrated_user_metadata.user, user_metadata.user);
+    assert_eq!(
+        migrated_user_metadata.created_timestamp,
+        user_metadata.created_timestamp
+    );
+    assert_eq!(migrated_user_metadata.claim_deadline, 0);
+    assert_eq!(migrated_user_metadata.total_amount_expired, 0);
+    assert_eq!(migrated_user_metadata.claim_authority, Pubkey::default());
+    assert_eq!(migrated_user_metadata.intent_nonce, 0);
+
+    // 5. Migrated accounts can be used again, and migrating them again changes nothing
+    test_state.execute([set_treasury()]);
+    test_state.execute([set_claimant()]);
+    assert_eq!(
+        test_state.get_lollys_lotto(lollys_lotto_pda).treasury,
+        treasury
+    );
+    assert_eq!(
+        test_state
+            .get_user_metadata(user_metadata_pda)
+            .claim_authority,
+        test_state.test_user2
+    );
+    test_state.execute_migrate_user_metadata_ix(
+        &test_state.test_user2,
+        &test_state.test_user1,
+        &user_metadata_pda,
+        &event_emitter_pda,
+    );
+    assert_eq!(
+        test_state
+            .get_user_metadata(user_metadata_pda)
+            .claim_authority,
+        test_state.test_user2
+    );
 }
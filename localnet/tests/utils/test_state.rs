@@ -1,17 +1,43 @@
+use anchor_lang::{
+    solana_program::instruction::Instruction, Discriminator, InstructionData, ToAccountMetas,
+};
 use lazy_static::lazy_static;
-use lollys_lotto::state::{
-    EventEmitter, LollyBurnState, LollysLotto, LottoGame, LottoTicket, LottoTicketNumbers,
-    UserMetadata,
+use lollys_lotto::{
+    constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+    state::{
+        derive_winning_numbers, DrawRecord, EventEmitter, LollyBurnState, LollysLotto, LottoGame,
+        LottoGameState, LottoGameVersion, LottoTicket, LottoTicketCombination, LottoTicketNumbers,
+        PaymentAsset, RandomnessProvider, StakeDiscountTier, StakePool, StakePosition,
+        SweepDestination, TicketPurchaseIntent, TicketSubscription, UserMetadata,
+    },
 };
 use lollys_lotto_rust_sdk::instructions::{
-    burn_lolly, buy_lotto_ticket, claim_user_rewards, crank_lotto_game_closed, crank_lotto_game_winner, crank_transfer_to_buy_and_burn_vault, crank_transfer_winning_amount_to_user_rewards_vault, create_event_emitter, create_lolly_burn_state, create_lollys_lotto, create_user_metadata, start_lotto_game, test_emit_winning_numbers
+    add_to_prize_pool, burn_lolly, buy_lotto_ticket, buy_lotto_ticket_with_intent, buy_lotto_ticket_with_swap, cancel_ticket_subscription, claim_all_user_rewards, claim_stake_rewards, claim_user_rewards, close_lotto_game, close_lotto_ticket, close_lotto_tickets, commit_winning_numbers, crank_close_lotto_ticket_combination, crank_distribute_protocol_fees, crank_expand_winning_numbers, crank_fulfil_subscription, crank_lotto_game_closed, crank_lotto_game_finished, crank_rerequest_winning_numbers, crank_settle_winning_ticket, crank_transfer_to_buy_and_burn_vault, create_event_emitter, create_lolly_burn_state, create_lollys_lotto, create_stake_pool, create_stake_position, create_ticket_subscription, create_user_metadata, expire_user_rewards, fund_min_jackpot, migrate_lollys_lotto, migrate_user_metadata, process_winning_numbers, request_winning_numbers, reveal_winning_numbers, set_burn_cap, set_claim_authority, set_lolly_payment_discount, set_randomness_provider, set_stake_pool_config, set_swap_programs, set_sweep_destination, stake_lolly, start_lotto_game, swap_and_burn, swap_usdc_lolly, sweep_lotto_game_vault, test_emit_winning_numbers, unstake_lolly, verify_lotto_game_solvency, verify_ticket_purchase_intent
+};
+use lollys_lotto_rust_sdk::pda::{
+    get_draw_record_pda, get_lotto_game_pda_and_bump, get_lotto_game_vault_pda,
+    get_lotto_game_vault_signer_pda_and_bump,
 };
 use solana_devtools_localnet::{
     localnet_account::TokenAccount, GeneratedAccount, LocalnetConfiguration, ProcessedMessage,
     TransactionSimulator,
 };
-use solana_program::pubkey::Pubkey;
+use mock_swap::Pool;
+use solana_program::{
+    hash::hash, program_option::COption, program_pack::Pack, pubkey::Pubkey, rent::Rent,
+};
+use solana_randomness_service::{SimpleRandomnessV1Account, State as RandomnessServiceState};
+use solana_sdk::account::{AccountSharedData, ReadableAccount};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::extension::{
+    transfer_fee::{TransferFee, TransferFeeConfig},
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+};
 use std::{ops::Deref, sync::Mutex};
+use switchboard_on_demand::RandomnessAccountData;
 
 use lolly_lotto_localnet::{
     mints::TestUsdc,
@@ -33,6 +59,41 @@ fn test_config() -> LocalnetConfiguration {
         .unwrap()
         .program_binary_file(lollys_lotto::ID, "../target/deploy/lollys_lotto.so")
         .unwrap()
+        .program_binary_file(mock_swap::ID, "../target/deploy/mock_swap.so")
+        .unwrap()
+        .program_binary_file(
+            mock_randomness_service::ID,
+            "../target/deploy/mock_randomness_service.so",
+        )
+        .unwrap()
+}
+
+/// The first `N` winning combinations drawn from `seed`, jackpot first, so a test can buy
+/// winning tickets before `test_emit_winning_numbers` draws them.
+pub fn drawn_winning_numbers<const N: usize>(seed: &[u8; 32]) -> [LottoTicketNumbers; N] {
+    let mut winning_numbers = [LottoTicketNumbers::default(); N];
+    let (mut drawn, mut nonce) = (0, 0);
+    while drawn < N {
+        let [number1, number2, number3, number4, number5, jackpot_number] = derive_winning_numbers(
+            seed,
+            nonce,
+            LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.to_array(),
+        );
+        let numbers = LottoTicketNumbers {
+            number1,
+            number2,
+            number3,
+            number4,
+            number5,
+            jackpot_number,
+        };
+        if !winning_numbers[..drawn].contains(&numbers) {
+            winning_numbers[drawn] = numbers;
+            drawn += 1;
+        }
+        nonce += 1;
+    }
+    winning_numbers
 }
 
 /// All the addresses needed to run tests,
@@ -50,6 +111,17 @@ pub struct TestState {
     pub lollys_lotto: Pubkey,
 }
 
+/// The accounts of a round started by [TestState::start_round].
+pub struct TestRound {
+    pub event_emitter_pda: Pubkey,
+    pub lollys_lotto_pda: Pubkey,
+    pub round: u64,
+    pub lotto_game_pda: Pubkey,
+    pub lotto_game_vault_signer: Pubkey,
+    pub lotto_game_vault_pda: Pubkey,
+    pub draw_record_pda: Pubkey,
+}
+
 impl HasMockRuntime for TestState {
     fn runtime(&self) -> &TransactionSimulator {
         &self.runtime
@@ -108,6 +180,24 @@ impl TestState {
         ).unwrap()])
     }
 
+    pub fn execute_approve_spl_token(
+        &self,
+        amount: u64,
+        source: Pubkey,
+        delegate: Pubkey,
+        owner: Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([spl_token::instruction::approve(
+            &spl_token::ID,
+            &source,
+            &delegate,
+            &owner,
+            &[],
+            amount,
+        )
+        .unwrap()])
+    }
+
     pub fn get_ata_balance(&self, associated_token_address: Pubkey) -> u64 {
         let state: TokenAccount = self
             .get_account_as(&associated_token_address)
@@ -115,6 +205,111 @@ impl TestState {
         state.amount
     }
 
+    /// Writes a Token-2022 mint with a transfer fee of `transfer_fee_basis_points`,
+    /// capped at `maximum_fee`. The test admin is the mint and fee authority.
+    pub fn set_token_2022_mint_with_transfer_fee(
+        &self,
+        mint: Pubkey,
+        decimals: u8,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) {
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        let mut data = vec![0; space];
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data)
+                .unwrap();
+        let transfer_fee = TransferFee {
+            epoch: 0u64.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        };
+        let transfer_fee_config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        transfer_fee_config.transfer_fee_config_authority =
+            Some(self.test_admin).try_into().unwrap();
+        transfer_fee_config.withdraw_withheld_authority = Some(self.test_admin).try_into().unwrap();
+        transfer_fee_config.withheld_amount = 0u64.into();
+        transfer_fee_config.older_transfer_fee = transfer_fee;
+        transfer_fee_config.newer_transfer_fee = transfer_fee;
+        state.base = spl_token_2022::state::Mint {
+            mint_authority: COption::Some(self.test_admin),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        let mut account = AccountSharedData::new(1_000_000_000, space, &spl_token_2022::ID);
+        account.set_data_from_slice(&data);
+        self.runtime().update_account(&mint, &account);
+    }
+
+    /// Creates the Token-2022 associated token account of `wallet` and mints `amount` to it.
+    pub fn execute_create_token_2022_account(
+        &self,
+        wallet: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> ProcessedMessage {
+        let token_account =
+            get_associated_token_address_with_program_id(wallet, mint, &spl_token_2022::ID);
+        let mut ixs = vec![create_associated_token_account_idempotent(
+            &self.payer(),
+            wallet,
+            mint,
+            &spl_token_2022::ID,
+        )];
+        if amount > 0 {
+            ixs.push(
+                spl_token_2022::instruction::mint_to(
+                    &spl_token_2022::ID,
+                    mint,
+                    &token_account,
+                    &self.test_admin,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+        self.execute(ixs)
+    }
+
+    pub fn get_token_2022_balance(&self, token_account: Pubkey) -> u64 {
+        let account = self
+            .get_account(&token_account)
+            .expect("Could not find Token-2022 account");
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(account.data())
+            .expect("Could not unpack Token-2022 account")
+            .base
+            .amount
+    }
+
+    /// Transfer fee the mint withholds on a transfer of `amount`.
+    pub fn get_token_2022_transfer_fee(&self, mint: Pubkey, amount: u64) -> u64 {
+        let account = self
+            .get_account(&mint)
+            .expect("Could not find Mint account");
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(account.data())
+            .expect("Could not unpack Token-2022 mint")
+            .get_extension::<TransferFeeConfig>()
+            .expect("Mint has no transfer fee")
+            .calculate_epoch_fee(0, amount)
+            .unwrap()
+    }
+
+    pub fn get_mint_supply(&self, mint: Pubkey) -> u64 {
+        let account = self.get_account(&mint).expect("Could not find Mint account");
+        spl_token::state::Mint::unpack(account.data())
+            .expect("Could not unpack Mint account")
+            .supply
+    }
+
     pub fn get_event_emitter(&self, event_emitter_pubkey: Pubkey) -> EventEmitter {
         self.get_account_as::<EventEmitter>(&event_emitter_pubkey)
             .expect("couldn't find Event Emitter account")
@@ -125,26 +320,148 @@ impl TestState {
             .expect("couldn't find Lollys Lotto account")
     }
 
+    /// Rewrites `pubkey` the way an earlier version of the program created it, serializing
+    /// only the first `fields_len` bytes of the current layout into `space` bytes of data.
+    pub fn set_previous_account_layout(&self, pubkey: Pubkey, fields_len: usize, space: usize) {
+        let account = self.get_account(&pubkey).expect("couldn't find account");
+        let mut data = account.data()[..8 + fields_len].to_vec();
+        data.resize(8 + space, 0);
+        let mut previous_account = AccountSharedData::new(
+            Rent::default().minimum_balance(data.len()),
+            data.len(),
+            account.owner(),
+        );
+        previous_account.set_data_from_slice(&data);
+        self.runtime().update_account(&pubkey, &previous_account);
+    }
+
     pub fn get_lotto_game(&self, lotto_game_pubkey: Pubkey) -> LottoGame {
         self.get_account_as::<LottoGame>(&lotto_game_pubkey)
             .expect("couldn't find Lotto Game account")
     }
 
+    /// Overwrites a zero-copy [LottoGame] account, e.g. to stub the randomness callback.
+    pub fn set_lotto_game(&self, lotto_game_pubkey: Pubkey, lotto_game: &LottoGame) {
+        let mut account = self
+            .get_account(&lotto_game_pubkey)
+            .expect("couldn't find Lotto Game account");
+        let mut data = LottoGame::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(lotto_game));
+        account.set_data_from_slice(&data);
+        self.runtime().update_account(&lotto_game_pubkey, &account);
+    }
+
+    /// Writes a stubbed Switchboard On-Demand randomness account.
+    pub fn set_randomness_account(
+        &self,
+        randomness_account: Pubkey,
+        randomness_data: &RandomnessAccountData,
+    ) {
+        let mut data = hash(b"account:RandomnessAccountData").to_bytes()[..8].to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(randomness_data));
+        let mut account =
+            AccountSharedData::new(1_000_000_000, data.len(), &SWITCHBOARD_ON_DEMAND_PROGRAM_ID);
+        account.set_data_from_slice(&data);
+        self.runtime().update_account(&randomness_account, &account);
+    }
+
+    /// Writes the Solana Randomness Service's `State` and the native mint requests are paid in.
+    pub fn set_randomness_service_state(&self) -> Pubkey {
+        let (randomness_state, bump) =
+            Pubkey::find_program_address(&[b"STATE"], &solana_randomness_service::ID);
+        self.set_new_account(
+            &randomness_state,
+            &RandomnessServiceState {
+                bump,
+                authority: self.test_admin,
+                mint: spl_token::native_mint::ID,
+                switchboard_service: Pubkey::new_unique(),
+                wallet: Pubkey::new_unique(),
+                cost_per_byte: 10_000,
+                last_updated: 0,
+                _ebuf: [0; 512],
+            },
+            &solana_randomness_service::ID,
+        )
+        .unwrap();
+
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: spl_token::native_mint::DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        let mut account = AccountSharedData::new(1_000_000_000, data.len(), &spl_token::ID);
+        account.set_data_from_slice(&data);
+        self.runtime()
+            .update_account(&spl_token::native_mint::ID, &account);
+        randomness_state
+    }
+
+    /// Writes the request account the Solana Randomness Service creates for `randomness_request`.
+    pub fn set_randomness_service_request(&self, randomness_request: Pubkey, lotto_game: Pubkey) {
+        self.set_new_account(
+            &randomness_request,
+            &SimpleRandomnessV1Account {
+                num_bytes: LottoGame::WINNING_NUMBERS_SEED_LEN as u8,
+                user: lotto_game,
+                escrow: Pubkey::new_unique(),
+                ..SimpleRandomnessV1Account::default()
+            },
+            &solana_randomness_service::ID,
+        )
+        .unwrap();
+    }
+
+    pub fn get_draw_record(&self, draw_record_pubkey: Pubkey) -> DrawRecord {
+        self.get_account_as::<DrawRecord>(&draw_record_pubkey)
+            .expect("couldn't find Draw Record account")
+    }
+
     pub fn get_user_metadata(&self, user_metadata_pubkey: Pubkey) -> UserMetadata {
         self.get_account_as::<UserMetadata>(&user_metadata_pubkey)
             .expect("couldn't find User Metadata account")
     }
 
+    pub fn get_ticket_subscription(
+        &self,
+        ticket_subscription_pubkey: Pubkey,
+    ) -> TicketSubscription {
+        self.get_account_as::<TicketSubscription>(&ticket_subscription_pubkey)
+            .expect("couldn't find Ticket Subscription account")
+    }
+
     pub fn get_lotto_ticket(&self, lotto_ticket_pubkey: Pubkey) -> LottoTicket {
         self.get_account_as::<LottoTicket>(&lotto_ticket_pubkey)
             .expect("couldn't find Lotto Ticket account")
     }
 
+    pub fn get_lotto_ticket_combination(
+        &self,
+        lotto_ticket_combination_pubkey: Pubkey,
+    ) -> LottoTicketCombination {
+        self.get_account_as::<LottoTicketCombination>(&lotto_ticket_combination_pubkey)
+            .expect("couldn't find Lotto Ticket Combination account")
+    }
+
     pub fn get_lolly_burn_state(&self, lolly_burn_state_pubkey: Pubkey) -> LollyBurnState {
         self.get_account_as::<LollyBurnState>(&lolly_burn_state_pubkey)
             .expect("couldn't find Lolly Burn State account")
     }
 
+    pub fn get_stake_pool(&self, stake_pool_pubkey: Pubkey) -> StakePool {
+        self.get_account_as::<StakePool>(&stake_pool_pubkey)
+            .expect("couldn't find Stake Pool account")
+    }
+
+    pub fn get_stake_position(&self, stake_position_pubkey: Pubkey) -> StakePosition {
+        self.get_account_as::<StakePosition>(&stake_position_pubkey)
+            .expect("couldn't find Stake Position account")
+    }
+
     pub fn execute_create_event_emitter_ix(
         &self,
         event_emitter_pda: Pubkey,
@@ -171,12 +488,15 @@ impl TestState {
         round: u64,
         ticket_price: u64,
         game_duration: u64,
+        sales_close_buffer: u64,
+        claim_period: u64,
         round_name: String,
         authority: &Pubkey,
         lollys_lotto: &Pubkey,
         lotto_game: &Pubkey,
         lotto_game_vault_signer: &Pubkey,
         lotto_game_vault: &Pubkey,
+        draw_record: &Pubkey,
         lotto_game_mint: &Pubkey,
         event_emitter_pda: &Pubkey,
     ) -> ProcessedMessage {
@@ -184,14 +504,85 @@ impl TestState {
             round,
             ticket_price,
             game_duration,
+            sales_close_buffer,
+            claim_period,
             round_name,
+            LottoGameVersion::V1,
             authority,
             lollys_lotto,
             lotto_game,
             lotto_game_vault_signer,
             lotto_game_vault,
+            draw_record,
             lotto_game_mint,
             event_emitter_pda,
+            &spl_token::ID,
+        )])
+    }
+
+    /// Creates the Event Emitter and Lollys Lotto, then starts Round 1,
+    /// an hour long game without a sales close buffer.
+    pub fn start_round(
+        &self,
+        ticket_price: u64,
+        claim_period: u64,
+        lotto_game_version: LottoGameVersion,
+    ) -> TestRound {
+        let (event_emitter_pda, _event_emitter_bump) = EventEmitter::address_with_bump();
+        self.execute_create_event_emitter_ix(event_emitter_pda, self.test_admin);
+
+        let lollys_lotto_pda = LollysLotto::address(self.test_admin);
+        self.execute_create_lollys_lotto_ix(self.test_admin, lollys_lotto_pda, event_emitter_pda);
+
+        let round: u64 = 0;
+        let (lotto_game_pda, _lotto_game_bump) =
+            get_lotto_game_pda_and_bump(&self.test_admin, round);
+        let (lotto_game_vault_signer, _lotto_game_vault_signer_bump) =
+            get_lotto_game_vault_signer_pda_and_bump(&lotto_game_pda);
+        let lotto_game_vault_pda = get_lotto_game_vault_pda(&lotto_game_pda);
+        let draw_record_pda = get_draw_record_pda(&lotto_game_pda);
+        self.execute([start_lotto_game(
+            round,
+            ticket_price,
+            3600,
+            0,
+            claim_period,
+            "Round 1".to_string(),
+            lotto_game_version,
+            &self.test_admin,
+            &lollys_lotto_pda,
+            &lotto_game_pda,
+            &lotto_game_vault_signer,
+            &lotto_game_vault_pda,
+            &draw_record_pda,
+            &self.test_usdc,
+            &event_emitter_pda,
+            &spl_token::ID,
+        )]);
+
+        TestRound {
+            event_emitter_pda,
+            lollys_lotto_pda,
+            round,
+            lotto_game_pda,
+            lotto_game_vault_signer,
+            lotto_game_vault_pda,
+            draw_record_pda,
+        }
+    }
+
+    pub fn execute_migrate_user_metadata_ix(
+        &self,
+        payer: &Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([migrate_user_metadata(
+            payer,
+            user,
+            user_metadata_pda,
+            event_emitter,
         )])
     }
 
@@ -237,179 +628,1108 @@ impl TestState {
             lotto_game,
             lotto_game_vault,
             lotto_ticket_pda,
+            &LottoTicketCombination::address(*lotto_game, numbers),
             event_emitter_pda,
+            None,
+            None,
+            &spl_token::ID,
         )])
     }
 
-    pub fn execute_crank_lotto_game_closed_ix(
+    pub fn execute_add_to_prize_pool_ix(
         &self,
         round: u64,
-        authority: &Pubkey,
+        tier: u8,
+        amount: u64,
+        sponsor: &Pubkey,
+        sponsor_token_account: &Pubkey,
         lotto_game: &Pubkey,
+        lotto_game_vault: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([crank_lotto_game_closed(
+        self.execute([add_to_prize_pool(
             round,
-            authority,
+            tier,
+            amount,
+            sponsor,
+            sponsor_token_account,
+            &self.test_usdc,
             lotto_game,
+            lotto_game_vault,
             event_emitter,
+            &spl_token::ID,
         )])
     }
 
-    pub fn execute_test_emit_winning_numbers_ix(
+    pub fn execute_crank_lotto_game_closed_ix(
         &self,
-        result: Vec<u8>,
+        round: u64,
         authority: &Pubkey,
         lotto_game: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([test_emit_winning_numbers(
-            result,
+        self.execute([crank_lotto_game_closed(
+            round,
             authority,
             lotto_game,
             event_emitter,
         )])
     }
 
-    pub fn execute_crank_lotto_game_winner_ix(
+    pub fn execute_crank_lotto_game_finished_ix(
         &self,
         round: u64,
-        winning_numbers: LottoTicketNumbers,
-        winning_numbers_index: [i64; 4],
         authority: &Pubkey,
-        user: &Pubkey,
-        user_metadata_pda: &Pubkey,
         lotto_game: &Pubkey,
         lotto_game_vault_signer: &Pubkey,
         lotto_game_vault: &Pubkey,
-        lotto_ticket: &Pubkey,
+        treasury_token_account: Option<&Pubkey>,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([crank_lotto_game_winner(
+        self.execute([crank_lotto_game_finished(
             round,
-            winning_numbers,
-            winning_numbers_index,
             authority,
-            user,
-            user_metadata_pda,
+            &self.lollys_lotto,
             lotto_game,
             lotto_game_vault_signer,
+            &self.test_usdc,
             lotto_game_vault,
-            lotto_ticket,
+            treasury_token_account,
             event_emitter,
+            &spl_token::ID,
         )])
     }
 
-    pub fn execute_crank_transfer_winning_amount_to_user_rewards_vault_ix(
+    pub fn execute_fund_min_jackpot_ix(
         &self,
         round: u64,
-        winning_numbers: LottoTicketNumbers,
-        number_of_tickets_with_duplicate_numbers: u32,
-        authority: &Pubkey,
-        user: &Pubkey,
-        user_metadata_pda: &Pubkey,
-        user_rewards_vault: &Pubkey,
+        amount: u64,
+        treasury: &Pubkey,
+        treasury_token_account: &Pubkey,
         lotto_game: &Pubkey,
-        lotto_game_vault_signer: &Pubkey,
         lotto_game_vault: &Pubkey,
-        lotto_ticket: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([crank_transfer_winning_amount_to_user_rewards_vault(
+        self.execute([fund_min_jackpot(
             round,
-            winning_numbers,
-            number_of_tickets_with_duplicate_numbers,
+            amount,
+            treasury,
+            &self.lollys_lotto,
+            treasury_token_account,
+            &self.test_usdc,
+            lotto_game,
+            lotto_game_vault,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_set_randomness_provider_ix(
+        &self,
+        randomness_provider: RandomnessProvider,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_randomness_provider(
+            randomness_provider,
+            authority,
+            lollys_lotto,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_migrate_lollys_lotto_ix(
+        &self,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([migrate_lollys_lotto(authority, lollys_lotto, event_emitter)])
+    }
+
+    pub fn execute_set_sweep_destination_ix(
+        &self,
+        sweep_destination: SweepDestination,
+        treasury: &Pubkey,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_sweep_destination(
+            sweep_destination,
+            treasury,
+            authority,
+            lollys_lotto,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_expire_user_rewards_ix(
+        &self,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        user: &Pubkey,
+        user_metadata: &Pubkey,
+        user_rewards_vault: &Pubkey,
+        destination_vault: &Pubkey,
+        next_lotto_game: Option<&Pubkey>,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([expire_user_rewards(
             authority,
+            lollys_lotto,
             user,
-            user_metadata_pda,
+            user_metadata,
+            &self.test_usdc,
             user_rewards_vault,
-            lotto_game,
-            lotto_game_vault_signer,
-            lotto_game_vault,
-            lotto_ticket,
+            destination_vault,
+            next_lotto_game,
             event_emitter,
+            &spl_token::ID,
         )])
     }
 
-    pub fn execute_create_lolly_burn_state_ix(
+    pub fn execute_sweep_lotto_game_vault_ix(
         &self,
         authority: &Pubkey,
-        lolly_burn_state_pda: &Pubkey,
-        lolly_mint: &Pubkey,
-        lolly_burn_state_lolly_vault: &Pubkey,
-        usdc_mint: &Pubkey,
-        lolly_burn_state_usdc_vault: &Pubkey,
+        lollys_lotto: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault_signer: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        destination_vault: &Pubkey,
+        next_lotto_game: Option<&Pubkey>,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([create_lolly_burn_state(
+        self.execute([sweep_lotto_game_vault(
             authority,
-            lolly_burn_state_pda,
-            lolly_mint,
-            lolly_burn_state_lolly_vault,
-            usdc_mint,
-            lolly_burn_state_usdc_vault,
+            lollys_lotto,
+            lotto_game,
+            lotto_game_vault_signer,
+            &self.test_usdc,
+            lotto_game_vault,
+            destination_vault,
+            next_lotto_game,
             event_emitter,
+            &spl_token::ID,
         )])
     }
 
-    pub fn execute_crank_transfer_to_buy_and_burn_vault_ix(
+    pub fn execute_close_lotto_game_ix(
         &self,
-        round: u64,
         authority: &Pubkey,
         lotto_game: &Pubkey,
         lotto_game_vault_signer: &Pubkey,
         lotto_game_vault: &Pubkey,
-        lolly_burn_state: &Pubkey,
-        lolly_burn_state_usdc_vault: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([crank_transfer_to_buy_and_burn_vault(
-            round,
+        self.execute([close_lotto_game(
             authority,
             lotto_game,
             lotto_game_vault_signer,
             lotto_game_vault,
-            lolly_burn_state,
-            lolly_burn_state_usdc_vault,
             event_emitter,
         )])
     }
 
-    pub fn execute_claim_user_rewards_ix(
+    pub fn execute_commit_winning_numbers_ix(
         &self,
-        amount_to_be_claimed: u64,
-        user: &Pubkey,
-        user_usdc_token_account: &Pubkey,
-        user_metadata_pda: &Pubkey,
-        usdc_mint: &Pubkey,
-        user_rewards_vault: &Pubkey,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
+        randomness_account_data: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([claim_user_rewards(
-            amount_to_be_claimed,
-            user,
-            user_usdc_token_account,
-            user_metadata_pda,
-            usdc_mint,
-            user_rewards_vault,
+        self.execute([commit_winning_numbers(
+            authority,
+            lollys_lotto,
+            lotto_game,
+            draw_record,
+            randomness_account_data,
             event_emitter,
         )])
     }
 
-    pub fn execute_burn_lolly_ix(
+    pub fn execute_request_winning_numbers_ix(
         &self,
-        lolly_mint: &Pubkey,
+        randomness_request: &Pubkey,
+        randomness_state: &Pubkey,
         authority: &Pubkey,
-        lolly_burn_state: &Pubkey,
-        lolly_burn_state_lolly_vault: &Pubkey,
+        lollys_lotto: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
         event_emitter: &Pubkey,
     ) -> ProcessedMessage {
-        self.execute([burn_lolly(
-            lolly_mint,
+        self.execute([request_winning_numbers(
+            &solana_randomness_service::ID,
+            randomness_request,
+            &Pubkey::new_unique(),
+            randomness_state,
+            &self.test_admin,
             authority,
-            lolly_burn_state,
-            lolly_burn_state_lolly_vault,
+            lollys_lotto,
+            lotto_game,
+            draw_record,
             event_emitter,
         )])
     }
+
+    pub fn execute_crank_rerequest_winning_numbers_ix(
+        &self,
+        round: u64,
+        randomness_request: &Pubkey,
+        randomness_state: &Pubkey,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_rerequest_winning_numbers(
+            round,
+            &solana_randomness_service::ID,
+            randomness_request,
+            &Pubkey::new_unique(),
+            randomness_state,
+            &self.test_admin,
+            authority,
+            lollys_lotto,
+            lotto_game,
+            draw_record,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_process_winning_numbers_ix(
+        &self,
+        randomness_state: &Pubkey,
+        request: &Pubkey,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
+        event_emitter: &Pubkey,
+        result: Vec<u8>,
+    ) -> ProcessedMessage {
+        self.execute([process_winning_numbers(
+            randomness_state,
+            request,
+            authority,
+            lotto_game,
+            draw_record,
+            event_emitter,
+            result,
+        )])
+    }
+
+    pub fn execute_reveal_winning_numbers_ix(
+        &self,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
+        randomness_account_data: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([reveal_winning_numbers(
+            authority,
+            lotto_game,
+            draw_record,
+            randomness_account_data,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_crank_expand_winning_numbers_ix(
+        &self,
+        round: u64,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        draw_record: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_expand_winning_numbers(
+            round,
+            authority,
+            lotto_game,
+            draw_record,
+            event_emitter,
+        )])
+    }
+
+    /// Draws the winning numbers from the seed `result`, expanding them until the round is drawn.
+    pub fn execute_test_emit_winning_numbers_ix(
+        &self,
+        result: Vec<u8>,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        let processed = self.execute([test_emit_winning_numbers(
+            result,
+            authority,
+            lotto_game,
+            event_emitter,
+        )]);
+        let round = self.get_lotto_game(*lotto_game).round;
+        let draw_record = DrawRecord::address(*lotto_game);
+        while self.get_lotto_game(*lotto_game).state == LottoGameState::Drawing {
+            self.execute_crank_expand_winning_numbers_ix(
+                round,
+                authority,
+                lotto_game,
+                &draw_record,
+                event_emitter,
+            );
+        }
+        processed
+    }
+
+    pub fn execute_crank_settle_winning_ticket_ix(
+        &self,
+        round: u64,
+        winning_numbers: LottoTicketNumbers,
+        authority: &Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        user_rewards_vault: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault_signer: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lotto_ticket: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        let lotto_ticket_combination =
+            LottoTicketCombination::address(*lotto_game, winning_numbers);
+        self.execute([crank_settle_winning_ticket(
+            round,
+            winning_numbers,
+            authority,
+            user,
+            user_metadata_pda,
+            user_rewards_vault,
+            lotto_game,
+            lotto_game_vault_signer,
+            &self.test_usdc,
+            lotto_game_vault,
+            lotto_ticket,
+            &lotto_ticket_combination,
+            &self
+                .get_lotto_ticket_combination(lotto_ticket_combination)
+                .payer,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_crank_close_lotto_ticket_combination_ix(
+        &self,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        payer: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_close_lotto_ticket_combination(
+            round,
+            numbers,
+            authority,
+            lotto_game,
+            &LottoTicketCombination::address(*lotto_game, numbers),
+            payer,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_create_lolly_burn_state_ix(
+        &self,
+        authority: &Pubkey,
+        lolly_burn_state_pda: &Pubkey,
+        lolly_mint: &Pubkey,
+        lolly_burn_state_lolly_vault: &Pubkey,
+        usdc_mint: &Pubkey,
+        lolly_burn_state_usdc_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([create_lolly_burn_state(
+            authority,
+            lolly_burn_state_pda,
+            lolly_mint,
+            lolly_burn_state_lolly_vault,
+            usdc_mint,
+            lolly_burn_state_usdc_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_crank_transfer_to_buy_and_burn_vault_ix(
+        &self,
+        round: u64,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault_signer: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        lolly_burn_state_usdc_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_transfer_to_buy_and_burn_vault(
+            round,
+            authority,
+            &self.lollys_lotto,
+            lotto_game,
+            lotto_game_vault_signer,
+            &self.test_usdc,
+            lotto_game_vault,
+            lolly_burn_state,
+            Some(lolly_burn_state_usdc_vault),
+            None,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_verify_lotto_game_solvency_ix(
+        &self,
+        round: u64,
+        lotto_game: &Pubkey,
+        lotto_game_vault_signer: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([verify_lotto_game_solvency(
+            round,
+            lotto_game,
+            lotto_game_vault_signer,
+            lotto_game_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_close_lotto_ticket_ix(
+        &self,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        authority: &Pubkey,
+        user: &Pubkey,
+        user_metadata: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_ticket: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([close_lotto_ticket(
+            round,
+            numbers,
+            authority,
+            user,
+            user_metadata,
+            lotto_game,
+            lotto_ticket,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_close_lotto_tickets_ix(
+        &self,
+        user: &Pubkey,
+        user_metadata: &Pubkey,
+        event_emitter: &Pubkey,
+        tickets: &[(Pubkey, Pubkey)],
+    ) -> ProcessedMessage {
+        self.execute([close_lotto_tickets(
+            user,
+            user_metadata,
+            event_emitter,
+            tickets,
+        )])
+    }
+
+    pub fn execute_claim_user_rewards_ix(
+        &self,
+        amount_to_be_claimed: u64,
+        user: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        usdc_mint: &Pubkey,
+        user_rewards_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([claim_user_rewards(
+            amount_to_be_claimed,
+            user,
+            user_usdc_token_account,
+            user_metadata_pda,
+            usdc_mint,
+            user_rewards_vault,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_buy_lotto_ticket_with_intent_ix(
+        &self,
+        intent: TicketPurchaseIntent,
+        signature: &[u8; 64],
+        relayer: &Pubkey,
+        relayer_usdc_token_account: &Pubkey,
+        authority: &Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        lotto_game_mint: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lotto_ticket_pda: &Pubkey,
+        event_emitter_pda: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([
+            verify_ticket_purchase_intent(user, signature, &intent),
+            buy_lotto_ticket_with_intent(
+                intent,
+                relayer,
+                relayer_usdc_token_account,
+                authority,
+                user,
+                user_metadata_pda,
+                user_usdc_token_account,
+                lotto_game_mint,
+                lotto_game,
+                lotto_game_vault,
+                lotto_ticket_pda,
+                &LottoTicketCombination::address(*lotto_game, intent.numbers),
+                event_emitter_pda,
+                None,
+                None,
+                &spl_token::ID,
+            ),
+        ])
+    }
+
+    pub fn execute_create_ticket_subscription_ix(
+        &self,
+        subscription_id: u64,
+        rounds: u64,
+        numbers: Option<LottoTicketNumbers>,
+        max_ticket_price: u64,
+        cranker_fee: u64,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        authority: &Pubkey,
+        ticket_subscription_pda: &Pubkey,
+        subscription_mint: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        subscription_vault: &Pubkey,
+        event_emitter_pda: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([create_ticket_subscription(
+            subscription_id,
+            rounds,
+            numbers,
+            max_ticket_price,
+            cranker_fee,
+            user,
+            user_metadata_pda,
+            authority,
+            ticket_subscription_pda,
+            subscription_mint,
+            user_usdc_token_account,
+            subscription_vault,
+            event_emitter_pda,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_cancel_ticket_subscription_ix(
+        &self,
+        user: &Pubkey,
+        ticket_subscription_pda: &Pubkey,
+        subscription_mint: &Pubkey,
+        subscription_vault: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        event_emitter_pda: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([cancel_ticket_subscription(
+            user,
+            ticket_subscription_pda,
+            subscription_mint,
+            subscription_vault,
+            user_usdc_token_account,
+            event_emitter_pda,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_crank_fulfil_subscription_ix(
+        &self,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        cranker: &Pubkey,
+        cranker_usdc_token_account: &Pubkey,
+        authority: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        ticket_subscription_pda: &Pubkey,
+        subscription_vault: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lotto_ticket_pda: &Pubkey,
+        event_emitter_pda: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_fulfil_subscription(
+            round,
+            numbers,
+            cranker,
+            cranker_usdc_token_account,
+            authority,
+            user_metadata_pda,
+            ticket_subscription_pda,
+            subscription_vault,
+            lotto_game,
+            &self.test_usdc,
+            lotto_game_vault,
+            lotto_ticket_pda,
+            &LottoTicketCombination::address(*lotto_game, numbers),
+            event_emitter_pda,
+            None,
+            None,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_claim_all_user_rewards_ix(
+        &self,
+        claimant: &Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        rewards_mint: &Pubkey,
+        user_rewards_vault: &Pubkey,
+        destination: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([claim_all_user_rewards(
+            claimant,
+            user,
+            user_metadata_pda,
+            rewards_mint,
+            user_rewards_vault,
+            destination,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_set_claim_authority_ix(
+        &self,
+        claim_authority: Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_claim_authority(
+            claim_authority,
+            user,
+            user_metadata_pda,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_burn_lolly_ix(
+        &self,
+        lolly_mint: &Pubkey,
+        authority: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        lolly_burn_state_lolly_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([burn_lolly(
+            lolly_mint,
+            authority,
+            lolly_burn_state,
+            lolly_burn_state_lolly_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn get_mock_swap_pool(&self, pool_pubkey: Pubkey) -> Pool {
+        self.get_account_as::<Pool>(&pool_pubkey)
+            .expect("couldn't find mock swap Pool account")
+    }
+
+    pub fn execute_initialize_mock_swap_pool_ix(
+        &self,
+        payer: &Pubkey,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+    ) -> ProcessedMessage {
+        let pool = Pool::address(*mint_a, *mint_b);
+        self.execute([Instruction {
+            program_id: mock_swap::ID,
+            accounts: mock_swap::accounts::InitializePool {
+                payer: *payer,
+                pool,
+                mint_a: *mint_a,
+                mint_b: *mint_b,
+                vault_a: spl_associated_token_account::get_associated_token_address(&pool, mint_a),
+                vault_b: spl_associated_token_account::get_associated_token_address(&pool, mint_b),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: mock_swap::instruction::InitializePool.data(),
+        }])
+    }
+
+    /// Mock swap route from `source` to `destination`, both owned by `user_authority`.
+    pub fn mock_swap_route(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        user_authority: &Pubkey,
+        pool: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        source_mint: &Pubkey,
+        destination_mint: &Pubkey,
+    ) -> Instruction {
+        Instruction {
+            program_id: mock_swap::ID,
+            accounts: mock_swap::accounts::Swap {
+                user_authority: *user_authority,
+                pool: *pool,
+                source: *source,
+                destination: *destination,
+                pool_source_vault: spl_associated_token_account::get_associated_token_address(
+                    pool,
+                    source_mint,
+                ),
+                pool_destination_vault: spl_associated_token_account::get_associated_token_address(
+                    pool,
+                    destination_mint,
+                ),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: mock_swap::instruction::Swap {
+                amount_in,
+                min_amount_out,
+            }
+            .data(),
+        }
+    }
+
+    pub fn execute_set_swap_programs_ix(
+        &self,
+        swap_programs: Vec<Pubkey>,
+        authority: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_swap_programs(
+            swap_programs,
+            authority,
+            lolly_burn_state,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_swap_usdc_lolly_ix(
+        &self,
+        min_lolly_out: u64,
+        route: Instruction,
+        authority: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        lolly_burn_state_usdc_vault: &Pubkey,
+        lolly_burn_state_lolly_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([swap_usdc_lolly(
+            min_lolly_out,
+            route,
+            authority,
+            lolly_burn_state,
+            lolly_burn_state_usdc_vault,
+            lolly_burn_state_lolly_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_set_burn_cap_ix(
+        &self,
+        burn_cap_per_epoch: u64,
+        authority: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_burn_cap(
+            burn_cap_per_epoch,
+            authority,
+            lolly_burn_state,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_swap_and_burn_ix(
+        &self,
+        round: u64,
+        max_usdc_in: u64,
+        min_lolly_out: u64,
+        route: Instruction,
+        authority: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        lotto_game: &Pubkey,
+        lolly_burn_state_usdc_vault: &Pubkey,
+        lolly_burn_state_lolly_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([swap_and_burn(
+            round,
+            max_usdc_in,
+            min_lolly_out,
+            route,
+            authority,
+            lolly_burn_state,
+            lotto_game,
+            lolly_burn_state_usdc_vault,
+            lolly_burn_state_lolly_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_buy_lotto_ticket_with_stake_ix(
+        &self,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        authority: &Pubkey,
+        user: &Pubkey,
+        user_metadata_pda: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        lotto_game_mint: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lotto_ticket_pda: &Pubkey,
+        event_emitter_pda: &Pubkey,
+        stake_pool: &Pubkey,
+        stake_position: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([buy_lotto_ticket(
+            round,
+            numbers,
+            authority,
+            user,
+            user_metadata_pda,
+            user_usdc_token_account,
+            lotto_game_mint,
+            lotto_game,
+            lotto_game_vault,
+            lotto_ticket_pda,
+            &LottoTicketCombination::address(*lotto_game, numbers),
+            event_emitter_pda,
+            Some(stake_pool),
+            Some(stake_position),
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_create_stake_pool_ix(
+        &self,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+        authority: &Pubkey,
+        stake_pool: &Pubkey,
+        lolly_mint: &Pubkey,
+        stake_vault: &Pubkey,
+        usdc_mint: &Pubkey,
+        reward_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([create_stake_pool(
+            unstake_cooldown,
+            discount_tiers,
+            authority,
+            stake_pool,
+            lolly_mint,
+            stake_vault,
+            usdc_mint,
+            reward_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_set_stake_pool_config_ix(
+        &self,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+        authority: &Pubkey,
+        stake_pool: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_stake_pool_config(
+            unstake_cooldown,
+            discount_tiers,
+            authority,
+            stake_pool,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_create_stake_position_ix(
+        &self,
+        user: &Pubkey,
+        stake_pool: &Pubkey,
+        stake_position: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([create_stake_position(
+            user,
+            stake_pool,
+            stake_position,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_stake_lolly_ix(
+        &self,
+        amount: u64,
+        user: &Pubkey,
+        stake_pool: &Pubkey,
+        stake_position: &Pubkey,
+        user_lolly_token_account: &Pubkey,
+        stake_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([stake_lolly(
+            amount,
+            user,
+            stake_pool,
+            stake_position,
+            user_lolly_token_account,
+            stake_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_unstake_lolly_ix(
+        &self,
+        amount: u64,
+        user: &Pubkey,
+        stake_pool: &Pubkey,
+        stake_position: &Pubkey,
+        user_lolly_token_account: &Pubkey,
+        stake_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([unstake_lolly(
+            amount,
+            user,
+            stake_pool,
+            stake_position,
+            user_lolly_token_account,
+            stake_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_claim_stake_rewards_ix(
+        &self,
+        user: &Pubkey,
+        stake_pool: &Pubkey,
+        stake_position: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        reward_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([claim_stake_rewards(
+            user,
+            stake_pool,
+            stake_position,
+            user_usdc_token_account,
+            reward_vault,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_crank_distribute_protocol_fees_ix(
+        &self,
+        round: u64,
+        authority: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault_signer: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        stake_pool: &Pubkey,
+        reward_vault: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([crank_distribute_protocol_fees(
+            round,
+            authority,
+            &self.lollys_lotto,
+            lotto_game,
+            lotto_game_vault_signer,
+            &self.test_usdc,
+            lotto_game_vault,
+            stake_pool,
+            Some(reward_vault),
+            None,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
+
+    pub fn execute_set_lolly_payment_discount_ix(
+        &self,
+        lolly_payment_discount_bps: u16,
+        authority: &Pubkey,
+        lollys_lotto: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([set_lolly_payment_discount(
+            lolly_payment_discount_bps,
+            authority,
+            lollys_lotto,
+            event_emitter,
+        )])
+    }
+
+    pub fn execute_buy_lotto_ticket_with_swap_ix(
+        &self,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        payment_asset: PaymentAsset,
+        max_amount_in: u64,
+        route: Instruction,
+        authority: &Pubkey,
+        user: &Pubkey,
+        user_metadata: &Pubkey,
+        user_usdc_token_account: &Pubkey,
+        user_payment_token_account: &Pubkey,
+        lotto_game_mint: &Pubkey,
+        lotto_game: &Pubkey,
+        lotto_game_vault: &Pubkey,
+        lotto_ticket: &Pubkey,
+        lollys_lotto: &Pubkey,
+        lolly_burn_state: &Pubkey,
+        event_emitter: &Pubkey,
+    ) -> ProcessedMessage {
+        self.execute([buy_lotto_ticket_with_swap(
+            round,
+            numbers,
+            payment_asset,
+            max_amount_in,
+            route,
+            authority,
+            user,
+            user_metadata,
+            user_usdc_token_account,
+            user_payment_token_account,
+            lotto_game_mint,
+            lotto_game,
+            lotto_game_vault,
+            lotto_ticket,
+            &LottoTicketCombination::address(*lotto_game, numbers),
+            lollys_lotto,
+            lolly_burn_state,
+            event_emitter,
+            &spl_token::ID,
+        )])
+    }
 }
@@ -8,6 +8,12 @@ pub enum LollyLottoSDKError {
 
     #[error("Could not deserialize {0} as type: {1}")]
     DeserializeFailure(Pubkey, String),
+
+    #[error("DrawRecord has no fulfilled randomness request: {0}")]
+    DrawNotFulfilled(Pubkey),
+
+    #[error("Could not recompute the winning numbers of {0}: {1}")]
+    RecomputeFailure(Pubkey, String),
 }
 
 pub type Result<T> = std::result::Result<T, LollyLottoSDKError>;
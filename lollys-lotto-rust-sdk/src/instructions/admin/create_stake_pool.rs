@@ -0,0 +1,40 @@
+use lollys_lotto::state::{StakeDiscountTier, StakePool};
+
+use crate::instructions::*;
+
+pub fn create_stake_pool(
+    unstake_cooldown: i64,
+    discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    authority: &Pubkey,
+    stake_pool: &Pubkey,
+    lolly_mint: &Pubkey,
+    stake_vault: &Pubkey,
+    usdc_mint: &Pubkey,
+    reward_vault: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CreateStakePool {
+        unstake_cooldown,
+        discount_tiers,
+    }
+    .data();
+
+    let accounts = lollys_lotto::accounts::CreateStakePool {
+        authority: *authority,
+        stake_pool: *stake_pool,
+        lolly_mint: *lolly_mint,
+        stake_vault: *stake_vault,
+        usdc_mint: *usdc_mint,
+        reward_vault: *reward_vault,
+        event_emitter: *event_emitter,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
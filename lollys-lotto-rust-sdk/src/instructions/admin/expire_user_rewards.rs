@@ -0,0 +1,35 @@
+use crate::instructions::*;
+
+/// `next_lotto_game` is only required when sweeping to the next round.
+pub fn expire_user_rewards(
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    rewards_mint: &Pubkey,
+    user_rewards_vault: &Pubkey,
+    destination_vault: &Pubkey,
+    next_lotto_game: Option<&Pubkey>,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::ExpireUserRewards.data();
+    let accounts = lollys_lotto::accounts::ExpireUserRewards {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        user: *user,
+        user_metadata: *user_metadata,
+        rewards_mint: *rewards_mint,
+        user_rewards_vault: *user_rewards_vault,
+        destination_vault: *destination_vault,
+        next_lotto_game: next_lotto_game.copied(),
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,32 @@
+use crate::instructions::*;
+
+pub fn fund_min_jackpot(
+    round: u64,
+    amount: u64,
+    treasury: &Pubkey,
+    lollys_lotto: &Pubkey,
+    treasury_token_account: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::FundMinJackpot { round, amount }.data();
+    let accounts = lollys_lotto::accounts::FundMinJackpot {
+        treasury: *treasury,
+        lollys_lotto: *lollys_lotto,
+        treasury_token_account: *treasury_token_account,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game: *lotto_game,
+        lotto_game_vault: *lotto_game_vault,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
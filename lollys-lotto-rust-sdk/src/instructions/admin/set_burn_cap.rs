@@ -0,0 +1,21 @@
+use crate::instructions::*;
+
+pub fn set_burn_cap(
+    burn_cap_per_epoch: u64,
+    authority: &Pubkey,
+    lolly_burn_state: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetBurnCap { burn_cap_per_epoch }.data();
+    let accounts = lollys_lotto::accounts::SetBurnCap {
+        authority: *authority,
+        lolly_burn_state: *lolly_burn_state,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,24 @@
+use crate::instructions::*;
+
+pub fn set_lolly_payment_discount(
+    lolly_payment_discount_bps: u16,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetLollyPaymentDiscount {
+        lolly_payment_discount_bps,
+    }
+    .data();
+    let accounts = lollys_lotto::accounts::SetLollyPaymentDiscount {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
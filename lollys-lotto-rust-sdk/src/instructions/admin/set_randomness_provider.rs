@@ -0,0 +1,26 @@
+use lollys_lotto::state::RandomnessProvider;
+
+use crate::instructions::*;
+
+pub fn set_randomness_provider(
+    randomness_provider: RandomnessProvider,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetRandomnessProvider {
+        randomness_provider,
+    }
+    .data();
+    let accounts = lollys_lotto::accounts::SetRandomnessProvider {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
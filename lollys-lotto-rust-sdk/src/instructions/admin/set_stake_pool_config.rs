@@ -0,0 +1,28 @@
+use lollys_lotto::state::{StakeDiscountTier, StakePool};
+
+use crate::instructions::*;
+
+pub fn set_stake_pool_config(
+    unstake_cooldown: i64,
+    discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    authority: &Pubkey,
+    stake_pool: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetStakePoolConfig {
+        unstake_cooldown,
+        discount_tiers,
+    }
+    .data();
+    let accounts = lollys_lotto::accounts::SetStakePoolConfig {
+        authority: *authority,
+        stake_pool: *stake_pool,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,21 @@
+use crate::instructions::*;
+
+pub fn set_swap_programs(
+    swap_programs: Vec<Pubkey>,
+    authority: &Pubkey,
+    lolly_burn_state: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetSwapPrograms { swap_programs }.data();
+    let accounts = lollys_lotto::accounts::SetSwapPrograms {
+        authority: *authority,
+        lolly_burn_state: *lolly_burn_state,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
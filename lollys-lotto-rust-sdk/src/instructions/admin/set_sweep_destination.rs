@@ -0,0 +1,28 @@
+use lollys_lotto::state::SweepDestination;
+
+use crate::instructions::*;
+
+pub fn set_sweep_destination(
+    sweep_destination: SweepDestination,
+    treasury: &Pubkey,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetSweepDestination {
+        sweep_destination,
+        treasury: *treasury,
+    }
+    .data();
+    let accounts = lollys_lotto::accounts::SetSweepDestination {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
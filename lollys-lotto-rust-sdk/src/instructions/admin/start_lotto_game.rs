@@ -1,23 +1,33 @@
+use lollys_lotto::state::LottoGameVersion;
+
 use crate::instructions::*;
 
 pub fn start_lotto_game(
     round: u64,
     ticket_price: u64,
     game_duration: u64,
+    sales_close_buffer: u64,
+    claim_period: u64,
     round_name: String,
+    lotto_game_version: LottoGameVersion,
     authority: &Pubkey,
     lollys_lotto: &Pubkey,
     lotto_game: &Pubkey,
     lotto_game_vault_signer: &Pubkey,
     lotto_game_vault: &Pubkey,
+    draw_record: &Pubkey,
     lotto_game_mint: &Pubkey,
     event_emitter: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
     let data = lollys_lotto::instruction::StartLottoGame {
         round,
         ticket_price,
         game_duration,
+        sales_close_buffer,
+        claim_period,
         round_name,
+        lotto_game_version,
     }
     .data();
 
@@ -27,9 +37,10 @@ pub fn start_lotto_game(
         lotto_game: *lotto_game,
         lotto_game_vault_signer: *lotto_game_vault_signer,
         lotto_game_vault: *lotto_game_vault,
+        draw_record: *draw_record,
         lotto_game_mint: *lotto_game_mint,
         event_emitter: *event_emitter,
-        token_program: token::ID,
+        token_program: *token_program,
         associated_token_program: associated_token::ID,
         system_program: system_program::ID,
     }
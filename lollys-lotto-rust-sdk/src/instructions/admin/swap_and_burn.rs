@@ -0,0 +1,48 @@
+use anchor_client::anchor_lang::solana_program::instruction::AccountMeta;
+
+use crate::instructions::*;
+
+/// `route` is the swap instruction of an allowlisted swap program, built off-chain with
+/// `lolly_burn_state` as the owner of the source and destination token accounts.
+pub fn swap_and_burn(
+    round: u64,
+    max_usdc_in: u64,
+    min_lolly_out: u64,
+    route: Instruction,
+    authority: &Pubkey,
+    lolly_burn_state: &Pubkey,
+    lotto_game: &Pubkey,
+    lolly_burn_state_usdc_vault: &Pubkey,
+    lolly_burn_state_lolly_vault: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SwapAndBurn {
+        round,
+        max_usdc_in,
+        min_lolly_out,
+        data: route.data,
+    }
+    .data();
+    let mut accounts = lollys_lotto::accounts::SwapAndBurn {
+        authority: *authority,
+        lolly_burn_state: *lolly_burn_state,
+        lotto_game: *lotto_game,
+        lolly_mint: crate::constants::LOLLY_MINT,
+        lolly_burn_state_usdc_vault: *lolly_burn_state_usdc_vault,
+        lolly_burn_state_lolly_vault: *lolly_burn_state_lolly_vault,
+        event_emitter: *event_emitter,
+        swap_program: route.program_id,
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+    // lolly_burn_state only signs inside the program, through invoke_signed
+    accounts.extend(route.accounts.into_iter().map(|account| AccountMeta {
+        is_signer: account.is_signer && account.pubkey != *lolly_burn_state,
+        ..account
+    }));
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -1,26 +1,39 @@
+use anchor_client::anchor_lang::solana_program::instruction::AccountMeta;
+
 use crate::instructions::*;
 
+/// `route` is the swap instruction of an allowlisted swap program, built off-chain with
+/// `lolly_burn_state` as the owner of the source and destination token accounts.
 pub fn swap_usdc_lolly(
-    data: Vec<u8>,
+    min_lolly_out: u64,
+    route: Instruction,
     authority: &Pubkey,
     lollys_burn_state: &Pubkey,
     lolly_burn_state_usdc_vault: &Pubkey,
     lolly_burn_state_lolly_vault: &Pubkey,
     event_emitter: &Pubkey,
-    jupiter_program_id: &Pubkey,
 ) -> Instruction {
-    let data = lollys_lotto::instruction::SwapUsdcLolly { data }.data();
-    let accounts = lollys_lotto::accounts::SwapUsdcLolly {
+    let data = lollys_lotto::instruction::SwapUsdcLolly {
+        min_lolly_out,
+        data: route.data,
+    }
+    .data();
+    let mut accounts = lollys_lotto::accounts::SwapUsdcLolly {
         authority: *authority,
         lolly_burn_state: *lollys_burn_state,
         lolly_burn_state_usdc_vault: *lolly_burn_state_usdc_vault,
         lolly_burn_state_lolly_vault: *lolly_burn_state_lolly_vault,
         event_emitter: *event_emitter,
-        jupiter_program: *jupiter_program_id,
+        swap_program: route.program_id,
         token_program: token::ID,
         system_program: system_program::ID,
     }
     .to_account_metas(None);
+    // lolly_burn_state only signs inside the program, through invoke_signed
+    accounts.extend(route.accounts.into_iter().map(|account| AccountMeta {
+        is_signer: account.is_signer && account.pubkey != *lollys_burn_state,
+        ..account
+    }));
     Instruction {
         program_id: lollys_lotto::ID,
         accounts,
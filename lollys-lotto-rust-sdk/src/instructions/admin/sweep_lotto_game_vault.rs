@@ -0,0 +1,36 @@
+use crate::instructions::*;
+
+/// `next_lotto_game` is only required when sweeping to the next round.
+pub fn sweep_lotto_game_vault(
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault_signer: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    destination_vault: &Pubkey,
+    next_lotto_game: Option<&Pubkey>,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SweepLottoGameVault.data();
+    let accounts = lollys_lotto::accounts::SweepLottoGameVault {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        lotto_game: *lotto_game,
+        lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game_vault: *lotto_game_vault,
+        destination_vault: *destination_vault,
+        next_lotto_game: next_lotto_game.copied(),
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
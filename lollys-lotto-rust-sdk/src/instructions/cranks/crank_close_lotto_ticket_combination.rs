@@ -0,0 +1,27 @@
+use crate::instructions::*;
+use lollys_lotto::{instruction::CrankCloseLottoTicketCombination, state::LottoTicketNumbers};
+
+pub fn crank_close_lotto_ticket_combination(
+    round: u64,
+    numbers: LottoTicketNumbers,
+    authority: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
+    payer: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = CrankCloseLottoTicketCombination { round, numbers }.data();
+    let accounts = lollys_lotto::accounts::CrankCloseLottoTicketCombination {
+        authority: *authority,
+        lotto_game: *lotto_game,
+        lotto_ticket_combination: *lotto_ticket_combination,
+        payer: *payer,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
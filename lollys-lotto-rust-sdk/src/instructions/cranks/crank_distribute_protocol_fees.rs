@@ -0,0 +1,39 @@
+use crate::instructions::*;
+use lollys_lotto::instruction::CrankDistributeProtocolFees;
+
+/// `reward_vault` is only required for USDC rounds, `treasury_token_account` for rounds in any other mint.
+pub fn crank_distribute_protocol_fees(
+    round: u64,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault_signer: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    stake_pool: &Pubkey,
+    reward_vault: Option<&Pubkey>,
+    treasury_token_account: Option<&Pubkey>,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = CrankDistributeProtocolFees { round }.data();
+    let accounts = lollys_lotto::accounts::CrankDistributeProtocolFees {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        lotto_game: *lotto_game,
+        lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game_vault: *lotto_game_vault,
+        stake_pool: *stake_pool,
+        reward_vault: reward_vault.copied(),
+        treasury_token_account: treasury_token_account.copied(),
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
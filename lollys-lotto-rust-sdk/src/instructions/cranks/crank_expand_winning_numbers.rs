@@ -0,0 +1,25 @@
+use lollys_lotto::instruction::CrankExpandWinningNumbers;
+
+use crate::instructions::*;
+
+pub fn crank_expand_winning_numbers(
+    round: u64,
+    authority: &Pubkey,
+    lotto_game: &Pubkey,
+    draw_record: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = CrankExpandWinningNumbers { round }.data();
+    let accounts = lollys_lotto::accounts::CrankExpandWinningNumbers {
+        authority: *authority,
+        lotto_game: *lotto_game,
+        draw_record: *draw_record,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,51 @@
+use lollys_lotto::state::LottoTicketNumbers;
+
+use crate::instructions::*;
+
+pub fn crank_fulfil_subscription(
+    round: u64,
+    numbers: LottoTicketNumbers,
+    cranker: &Pubkey,
+    cranker_usdc_token_account: &Pubkey,
+    authority: &Pubkey,
+    user_metadata: &Pubkey,
+    ticket_subscription: &Pubkey,
+    subscription_vault: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    lotto_ticket: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
+    event_emitter: &Pubkey,
+    stake_pool: Option<&Pubkey>,
+    stake_position: Option<&Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CrankFulfilSubscription { round, numbers }.data();
+
+    let accounts = lollys_lotto::accounts::CrankFulfilSubscription {
+        cranker: *cranker,
+        cranker_usdc_token_account: *cranker_usdc_token_account,
+        authority: *authority,
+        user_metadata: *user_metadata,
+        ticket_subscription: *ticket_subscription,
+        subscription_vault: *subscription_vault,
+        lotto_game: *lotto_game,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game_vault: *lotto_game_vault,
+        lotto_ticket: *lotto_ticket,
+        lotto_ticket_combination: *lotto_ticket_combination,
+        event_emitter: *event_emitter,
+        stake_pool: stake_pool.copied(),
+        stake_position: stake_position.copied(),
+        token_program: *token_program,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,35 @@
+use lollys_lotto::instruction::CrankLottoGameFinished;
+
+use crate::instructions::*;
+
+pub fn crank_lotto_game_finished(
+    round: u64,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault_signer: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    treasury_token_account: Option<&Pubkey>,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = CrankLottoGameFinished { round }.data();
+    let accounts = lollys_lotto::accounts::CrankLottoGameFinished {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        lotto_game: *lotto_game,
+        lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game_vault: *lotto_game_vault,
+        treasury_token_account: treasury_token_account.copied(),
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -1,39 +1,42 @@
 use crate::instructions::*;
-use lollys_lotto::{
-    instruction::CrankTransferWinningAmountToUserRewardsVault, state::LottoTicketNumbers,
-};
+use lollys_lotto::{instruction::CrankSettleWinningTicket, state::LottoTicketNumbers};
 
-pub fn crank_transfer_winning_amount_to_user_rewards_vault(
+pub fn crank_settle_winning_ticket(
     round: u64,
     winning_numbers: LottoTicketNumbers,
-    number_of_tickets_with_duplicate_numbers: u32,
     authority: &Pubkey,
     user: &Pubkey,
     user_metadata: &Pubkey,
     user_rewards_vault: &Pubkey,
     lotto_game: &Pubkey,
     lotto_game_vault_signer: &Pubkey,
+    lotto_game_mint: &Pubkey,
     lotto_game_vault: &Pubkey,
     lotto_ticket: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
+    lotto_ticket_combination_payer: &Pubkey,
     event_emitter: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
-    let data = CrankTransferWinningAmountToUserRewardsVault {
+    let data = CrankSettleWinningTicket {
         round,
         winning_numbers,
-        number_of_tickets_with_duplicate_numbers,
     }
     .data();
-    let accounts = lollys_lotto::accounts::CrankTransferWinningAmountToUserRewardsVault {
+    let accounts = lollys_lotto::accounts::CrankSettleWinningTicket {
         authority: *authority,
         user: *user,
         user_metadata: *user_metadata,
         user_rewards_vault: *user_rewards_vault,
         lotto_game: *lotto_game,
         lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_mint: *lotto_game_mint,
         lotto_game_vault: *lotto_game_vault,
         lotto_ticket: *lotto_ticket,
+        lotto_ticket_combination: *lotto_ticket_combination,
+        lotto_ticket_combination_payer: *lotto_ticket_combination_payer,
         event_emitter: *event_emitter,
-        token_program: token::ID,
+        token_program: *token_program,
     }
     .to_account_metas(None);
     Instruction {
@@ -1,26 +1,35 @@
 use crate::instructions::*;
 use lollys_lotto::instruction::CrankTransferToBuyAndBurnVault;
 
+/// `lolly_burn_state_usdc_vault` is only required for USDC rounds, `treasury_token_account`
+/// for rounds in any other mint.
 pub fn crank_transfer_to_buy_and_burn_vault(
     round: u64,
     authority: &Pubkey,
+    lollys_lotto: &Pubkey,
     lotto_game: &Pubkey,
     lotto_game_vault_signer: &Pubkey,
+    lotto_game_mint: &Pubkey,
     lotto_game_vault: &Pubkey,
     lolly_burn_state: &Pubkey,
-    lolly_burn_state_usdc_vault: &Pubkey,
+    lolly_burn_state_usdc_vault: Option<&Pubkey>,
+    treasury_token_account: Option<&Pubkey>,
     event_emitter: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
     let data = CrankTransferToBuyAndBurnVault { round }.data();
     let accounts = lollys_lotto::accounts::CrankTransferToBuyAndBurnVault {
         authority: *authority,
+        lollys_lotto: *lollys_lotto,
         lotto_game: *lotto_game,
         lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_mint: *lotto_game_mint,
         lotto_game_vault: *lotto_game_vault,
         lolly_burn_state: *lolly_burn_state,
-        lolly_burn_state_usdc_vault: *lolly_burn_state_usdc_vault,
+        lolly_burn_state_usdc_vault: lolly_burn_state_usdc_vault.copied(),
+        treasury_token_account: treasury_token_account.copied(),
         event_emitter: *event_emitter,
-        token_program: token::ID,
+        token_program: *token_program,
     }
     .to_account_metas(None);
     Instruction {
@@ -0,0 +1,24 @@
+use crate::instructions::*;
+use lollys_lotto::instruction::VerifyLottoGameSolvency;
+
+pub fn verify_lotto_game_solvency(
+    round: u64,
+    lotto_game: &Pubkey,
+    lotto_game_vault_signer: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = VerifyLottoGameSolvency { round }.data();
+    let accounts = lollys_lotto::accounts::VerifyLottoGameSolvency {
+        lotto_game: *lotto_game,
+        lotto_game_vault_signer: *lotto_game_vault_signer,
+        lotto_game_vault: *lotto_game_vault,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
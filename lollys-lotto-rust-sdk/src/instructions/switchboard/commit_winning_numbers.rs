@@ -0,0 +1,26 @@
+use crate::instructions::*;
+
+pub fn commit_winning_numbers(
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lotto_game: &Pubkey,
+    draw_record: &Pubkey,
+    randomness_account_data: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CommitWinningNumbers.data();
+    let accounts = lollys_lotto::accounts::CommitWinningNumbers {
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        lotto_game: *lotto_game,
+        draw_record: *draw_record,
+        randomness_account_data: *randomness_account_data,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -1 +1,27 @@
 use crate::instructions::*;
+
+pub fn process_winning_numbers(
+    randomness_state: &Pubkey,
+    request: &Pubkey,
+    authority: &Pubkey,
+    lotto_game: &Pubkey,
+    draw_record: &Pubkey,
+    event_emitter: &Pubkey,
+    result: Vec<u8>,
+) -> Instruction {
+    let data = lollys_lotto::instruction::ProcessWinningNumbers { result }.data();
+    let accounts = lollys_lotto::accounts::ProcessWinningNumbers {
+        randomness_state: *randomness_state,
+        request: *request,
+        authority: *authority,
+        lotto_game: *lotto_game,
+        draw_record: *draw_record,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -1 +1,38 @@
 use crate::instructions::*;
+
+pub fn request_winning_numbers(
+    randomness_service: &Pubkey,
+    randomness_request: &Pubkey,
+    randomness_escrow: &Pubkey,
+    randomness_state: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lotto_game: &Pubkey,
+    draw_record: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::RequestWinningNumbers.data();
+    let accounts = lollys_lotto::accounts::RequestWinningNumbers {
+        randomness_service: *randomness_service,
+        randomness_request: *randomness_request,
+        randomness_escrow: *randomness_escrow,
+        randomness_state: *randomness_state,
+        randomness_mint: token::spl_token::native_mint::ID,
+        payer: *payer,
+        authority: *authority,
+        lollys_lotto: *lollys_lotto,
+        lotto_game: *lotto_game,
+        draw_record: *draw_record,
+        event_emitter: *event_emitter,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+    }
+    .to_account_metas(None);
+    Instruction {
+        program_id: lollys_lotto::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,38 @@
+use crate::instructions::*;
+
+pub fn add_to_prize_pool(
+    round: u64,
+    tier: u8,
+    amount: u64,
+    sponsor: &Pubkey,
+    sponsor_token_account: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::AddToPrizePool {
+        round,
+        tier,
+        amount,
+    }
+    .data();
+
+    let accounts = lollys_lotto::accounts::AddToPrizePool {
+        sponsor: *sponsor,
+        sponsor_token_account: *sponsor_token_account,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game: *lotto_game,
+        lotto_game_vault: *lotto_game_vault,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
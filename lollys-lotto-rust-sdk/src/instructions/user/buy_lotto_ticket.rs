@@ -13,7 +13,11 @@ pub fn buy_lotto_ticket(
     lotto_game: &Pubkey,
     lotto_game_vault: &Pubkey,
     lotto_ticket: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
     event_emitter: &Pubkey,
+    stake_pool: Option<&Pubkey>,
+    stake_position: Option<&Pubkey>,
+    token_program: &Pubkey,
 ) -> Instruction {
     let data = lollys_lotto::instruction::BuyLottoTicket { round, numbers }.data();
 
@@ -26,8 +30,11 @@ pub fn buy_lotto_ticket(
         lotto_game: *lotto_game,
         lotto_game_vault: *lotto_game_vault,
         lotto_ticket: *lotto_ticket,
+        lotto_ticket_combination: *lotto_ticket_combination,
         event_emitter: *event_emitter,
-        token_program: token::ID,
+        stake_pool: stake_pool.copied(),
+        stake_position: stake_position.copied(),
+        token_program: *token_program,
         associated_token_program: associated_token::ID,
         system_program: system_program::ID,
     }
@@ -0,0 +1,87 @@
+use anchor_client::anchor_lang::solana_program::ed25519_program;
+use lollys_lotto::state::TicketPurchaseIntent;
+
+use crate::instructions::*;
+
+pub fn buy_lotto_ticket_with_intent(
+    intent: TicketPurchaseIntent,
+    relayer: &Pubkey,
+    relayer_usdc_token_account: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    user_usdc_token_account: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    lotto_ticket: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
+    event_emitter: &Pubkey,
+    stake_pool: Option<&Pubkey>,
+    stake_position: Option<&Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::BuyLottoTicketWithIntent { intent }.data();
+
+    let accounts = lollys_lotto::accounts::BuyLottoTicketWithIntent {
+        relayer: *relayer,
+        relayer_usdc_token_account: *relayer_usdc_token_account,
+        authority: *authority,
+        user: *user,
+        user_metadata: *user_metadata,
+        user_usdc_token_account: *user_usdc_token_account,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game: *lotto_game,
+        lotto_game_vault: *lotto_game_vault,
+        lotto_ticket: *lotto_ticket,
+        lotto_ticket_combination: *lotto_ticket_combination,
+        stake_pool: stake_pool.copied(),
+        stake_position: stake_position.copied(),
+        instructions: sysvar::instructions::ID,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
+
+/// Ed25519 precompile instruction verifying `signature` of `user` over the intent message.
+/// Must be placed right before `buy_lotto_ticket_with_intent` in the same transaction.
+pub fn verify_ticket_purchase_intent(
+    user: &Pubkey,
+    signature: &[u8; 64],
+    intent: &TicketPurchaseIntent,
+) -> Instruction {
+    let message = intent.message();
+    let public_key_offset: u16 = 2 + 14;
+    let signature_offset = public_key_offset + 32;
+    let message_data_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_data_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(user.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(&message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,65 @@
+use lollys_lotto::state::{LottoTicketNumbers, PaymentAsset};
+
+use crate::instructions::*;
+
+/// `route` is the swap instruction of an allowlisted swap program, built off-chain with
+/// `user` as the owner of `user_payment_token_account` and `user_usdc_token_account`.
+pub fn buy_lotto_ticket_with_swap(
+    round: u64,
+    numbers: LottoTicketNumbers,
+    payment_asset: PaymentAsset,
+    max_amount_in: u64,
+    route: Instruction,
+    authority: &Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    user_usdc_token_account: &Pubkey,
+    user_payment_token_account: &Pubkey,
+    lotto_game_mint: &Pubkey,
+    lotto_game: &Pubkey,
+    lotto_game_vault: &Pubkey,
+    lotto_ticket: &Pubkey,
+    lotto_ticket_combination: &Pubkey,
+    lollys_lotto: &Pubkey,
+    lolly_burn_state: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::BuyLottoTicketWithSwap {
+        round,
+        numbers,
+        payment_asset,
+        max_amount_in,
+        data: route.data,
+    }
+    .data();
+
+    let mut accounts = lollys_lotto::accounts::BuyLottoTicketWithSwap {
+        authority: *authority,
+        user: *user,
+        user_metadata: *user_metadata,
+        user_usdc_token_account: *user_usdc_token_account,
+        user_payment_token_account: *user_payment_token_account,
+        lotto_game_mint: *lotto_game_mint,
+        lotto_game: *lotto_game,
+        lotto_game_vault: *lotto_game_vault,
+        lotto_ticket: *lotto_ticket,
+        lotto_ticket_combination: *lotto_ticket_combination,
+        lollys_lotto: *lollys_lotto,
+        lolly_burn_state: *lolly_burn_state,
+        event_emitter: *event_emitter,
+        swap_program: route.program_id,
+        token_program: *token_program,
+        payment_token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(route.accounts);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
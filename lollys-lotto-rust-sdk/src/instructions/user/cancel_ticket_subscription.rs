@@ -0,0 +1,30 @@
+use crate::instructions::*;
+
+pub fn cancel_ticket_subscription(
+    user: &Pubkey,
+    ticket_subscription: &Pubkey,
+    subscription_mint: &Pubkey,
+    subscription_vault: &Pubkey,
+    user_usdc_token_account: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CancelTicketSubscription.data();
+
+    let accounts = lollys_lotto::accounts::CancelTicketSubscription {
+        user: *user,
+        ticket_subscription: *ticket_subscription,
+        subscription_mint: *subscription_mint,
+        subscription_vault: *subscription_vault,
+        user_usdc_token_account: *user_usdc_token_account,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
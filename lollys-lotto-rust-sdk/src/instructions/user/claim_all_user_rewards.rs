@@ -0,0 +1,32 @@
+use crate::instructions::*;
+
+pub fn claim_all_user_rewards(
+    claimant: &Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    rewards_mint: &Pubkey,
+    user_rewards_vault: &Pubkey,
+    destination: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::ClaimAllUserRewards.data();
+
+    let accounts = lollys_lotto::accounts::ClaimAllUserRewards {
+        claimant: *claimant,
+        user: *user,
+        user_metadata: *user_metadata,
+        rewards_mint: *rewards_mint,
+        user_rewards_vault: *user_rewards_vault,
+        destination: *destination,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
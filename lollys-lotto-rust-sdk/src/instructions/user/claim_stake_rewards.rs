@@ -0,0 +1,29 @@
+use crate::instructions::*;
+
+pub fn claim_stake_rewards(
+    user: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_position: &Pubkey,
+    user_usdc_token_account: &Pubkey,
+    reward_vault: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::ClaimStakeRewards.data();
+
+    let accounts = lollys_lotto::accounts::ClaimStakeRewards {
+        user: *user,
+        stake_pool: *stake_pool,
+        stake_position: *stake_position,
+        user_usdc_token_account: *user_usdc_token_account,
+        reward_vault: *reward_vault,
+        event_emitter: *event_emitter,
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
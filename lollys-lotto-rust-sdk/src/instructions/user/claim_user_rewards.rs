@@ -5,9 +5,10 @@ pub fn claim_user_rewards(
     user: &Pubkey,
     user_usdc_token_account: &Pubkey,
     user_metadata: &Pubkey,
-    usdc_mint: &Pubkey,
+    rewards_mint: &Pubkey,
     user_rewards_vault: &Pubkey,
     event_emitter: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
     let data = lollys_lotto::instruction::ClaimUserRewards {
         amount_to_be_claimed,
@@ -18,10 +19,10 @@ pub fn claim_user_rewards(
         user: *user,
         user_usdc_token_account: *user_usdc_token_account,
         user_metadata: *user_metadata,
-        usdc_mint: *usdc_mint,
+        rewards_mint: *rewards_mint,
         user_rewards_vault: *user_rewards_vault,
         event_emitter: *event_emitter,
-        token_program: token::ID,
+        token_program: *token_program,
     }
     .to_account_metas(None);
 
@@ -0,0 +1,31 @@
+use anchor_client::anchor_lang::solana_program::instruction::AccountMeta;
+
+use crate::instructions::*;
+
+/// `tickets` holds a `(lotto_game, lotto_ticket)` pair per ticket to be closed.
+pub fn close_lotto_tickets(
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    event_emitter: &Pubkey,
+    tickets: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let data = lollys_lotto::instruction::CloseLottoTickets.data();
+
+    let mut accounts = lollys_lotto::accounts::CloseLottoTickets {
+        user: *user,
+        user_metadata: *user_metadata,
+        event_emitter: *event_emitter,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for (lotto_game, lotto_ticket) in tickets {
+        accounts.push(AccountMeta::new_readonly(*lotto_game, false));
+        accounts.push(AccountMeta::new(*lotto_ticket, false));
+    }
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
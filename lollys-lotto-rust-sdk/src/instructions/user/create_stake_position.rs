@@ -0,0 +1,25 @@
+use crate::instructions::*;
+
+pub fn create_stake_position(
+    user: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_position: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CreateStakePosition.data();
+
+    let accounts = lollys_lotto::accounts::CreateStakePosition {
+        user: *user,
+        stake_pool: *stake_pool,
+        stake_position: *stake_position,
+        event_emitter: *event_emitter,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,50 @@
+use lollys_lotto::state::LottoTicketNumbers;
+
+use crate::instructions::*;
+
+pub fn create_ticket_subscription(
+    subscription_id: u64,
+    rounds: u64,
+    numbers: Option<LottoTicketNumbers>,
+    max_ticket_price: u64,
+    cranker_fee: u64,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    authority: &Pubkey,
+    ticket_subscription: &Pubkey,
+    subscription_mint: &Pubkey,
+    user_usdc_token_account: &Pubkey,
+    subscription_vault: &Pubkey,
+    event_emitter: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::CreateTicketSubscription {
+        subscription_id,
+        rounds,
+        numbers,
+        max_ticket_price,
+        cranker_fee,
+    }
+    .data();
+
+    let accounts = lollys_lotto::accounts::CreateTicketSubscription {
+        user: *user,
+        user_metadata: *user_metadata,
+        authority: *authority,
+        ticket_subscription: *ticket_subscription,
+        subscription_mint: *subscription_mint,
+        user_usdc_token_account: *user_usdc_token_account,
+        subscription_vault: *subscription_vault,
+        event_emitter: *event_emitter,
+        token_program: *token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
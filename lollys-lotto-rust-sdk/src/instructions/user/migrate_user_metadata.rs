@@ -0,0 +1,25 @@
+use crate::instructions::*;
+
+pub fn migrate_user_metadata(
+    payer: &Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::MigrateUserMetadata.data();
+
+    let accounts = lollys_lotto::accounts::MigrateUserMetadata {
+        payer: *payer,
+        user: *user,
+        user_metadata: *user_metadata,
+        event_emitter: *event_emitter,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,23 @@
+use crate::instructions::*;
+
+pub fn set_claim_authority(
+    claim_authority: Pubkey,
+    user: &Pubkey,
+    user_metadata: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::SetClaimAuthority { claim_authority }.data();
+
+    let accounts = lollys_lotto::accounts::SetClaimAuthority {
+        user: *user,
+        user_metadata: *user_metadata,
+        event_emitter: *event_emitter,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,30 @@
+use crate::instructions::*;
+
+pub fn unstake_lolly(
+    amount: u64,
+    user: &Pubkey,
+    stake_pool: &Pubkey,
+    stake_position: &Pubkey,
+    user_lolly_token_account: &Pubkey,
+    stake_vault: &Pubkey,
+    event_emitter: &Pubkey,
+) -> Instruction {
+    let data = lollys_lotto::instruction::UnstakeLolly { amount }.data();
+
+    let accounts = lollys_lotto::accounts::UnstakeLolly {
+        user: *user,
+        stake_pool: *stake_pool,
+        stake_position: *stake_position,
+        user_lolly_token_account: *user_lolly_token_account,
+        stake_vault: *stake_vault,
+        event_emitter: *event_emitter,
+        token_program: token::ID,
+    }
+    .to_account_metas(None);
+
+    Instruction {
+        program_id: lollys_lotto::id(),
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,10 @@
+use lollys_lotto::state::DrawRecord;
+use solana_program::pubkey::Pubkey;
+
+pub fn get_draw_record_pda(lotto_game: &Pubkey) -> Pubkey {
+    DrawRecord::address(*lotto_game)
+}
+
+pub fn get_draw_record_pda_and_bump(lotto_game: &Pubkey) -> (Pubkey, u8) {
+    DrawRecord::address_with_bump(*lotto_game)
+}
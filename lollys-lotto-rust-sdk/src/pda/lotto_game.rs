@@ -13,6 +13,14 @@ pub fn get_lotto_game_vault_pda(lotto_game: &Pubkey) -> Pubkey {
     LottoGameVault::vault_address(*lotto_game)
 }
 
+pub fn get_lotto_game_vault_pda_for_mint(
+    lotto_game: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    LottoGameVault::vault_address_for_mint(*lotto_game, *mint, *token_program)
+}
+
 pub fn get_lotto_game_vault_signer_pda(lotto_game: &Pubkey) -> Pubkey {
     LottoGameVault::signer_address(*lotto_game)
     // LottoGameVault::address(*lotto_game)
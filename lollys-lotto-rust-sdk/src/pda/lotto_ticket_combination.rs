@@ -0,0 +1,16 @@
+use lollys_lotto::state::{LottoTicketCombination, LottoTicketNumbers};
+use solana_program::pubkey::Pubkey;
+
+pub fn get_lotto_ticket_combination_pda(
+    lotto_game_pda: Pubkey,
+    numbers: LottoTicketNumbers,
+) -> Pubkey {
+    LottoTicketCombination::address(lotto_game_pda, numbers)
+}
+
+pub fn get_lotto_ticket_combination_pda_and_bump(
+    lotto_game_pda: Pubkey,
+    numbers: LottoTicketNumbers,
+) -> (Pubkey, u8) {
+    LottoTicketCombination::address_with_bump(lotto_game_pda, numbers)
+}
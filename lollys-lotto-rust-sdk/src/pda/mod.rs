@@ -1,13 +1,21 @@
+pub mod draw_record;
 pub mod events;
 pub mod lolly_burn_state;
 pub mod lollys_lotto;
 pub mod lotto_game;
 pub mod lotto_ticket;
+pub mod lotto_ticket_combination;
+pub mod stake_pool;
+pub mod ticket_subscription;
 pub mod user_metadata;
 
+pub use draw_record::*;
 pub use events::*;
 pub use lolly_burn_state::*;
 pub use lollys_lotto::*;
 pub use lotto_game::*;
 pub use lotto_ticket::*;
+pub use lotto_ticket_combination::*;
+pub use stake_pool::*;
+pub use ticket_subscription::*;
 pub use user_metadata::*;
@@ -0,0 +1,26 @@
+use lollys_lotto::state::{StakePool, StakePosition};
+use solana_program::pubkey::Pubkey;
+
+pub fn get_stake_pool_pda(authority: Pubkey) -> Pubkey {
+    StakePool::address(authority)
+}
+
+pub fn get_stake_pool_pda_and_bump(authority: Pubkey) -> (Pubkey, u8) {
+    StakePool::address_with_bump(authority)
+}
+
+pub fn get_stake_pool_stake_vault(authority: Pubkey) -> Pubkey {
+    StakePool::stake_vault(authority)
+}
+
+pub fn get_stake_pool_reward_vault(authority: Pubkey) -> Pubkey {
+    StakePool::reward_vault(authority)
+}
+
+pub fn get_stake_position_pda(stake_pool: Pubkey, user: Pubkey) -> Pubkey {
+    StakePosition::address(stake_pool, user)
+}
+
+pub fn get_stake_position_pda_and_bump(stake_pool: Pubkey, user: Pubkey) -> (Pubkey, u8) {
+    StakePosition::address_with_bump(stake_pool, user)
+}
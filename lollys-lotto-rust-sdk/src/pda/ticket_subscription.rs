@@ -0,0 +1,30 @@
+use lollys_lotto::state::TicketSubscription;
+use solana_program::pubkey::Pubkey;
+
+pub fn get_ticket_subscription_pda(
+    user_metadata_pda: Pubkey,
+    authority: Pubkey,
+    subscription_id: u64,
+) -> Pubkey {
+    TicketSubscription::address(user_metadata_pda, authority, subscription_id)
+}
+
+pub fn get_ticket_subscription_pda_and_bump(
+    user_metadata_pda: Pubkey,
+    authority: Pubkey,
+    subscription_id: u64,
+) -> (Pubkey, u8) {
+    TicketSubscription::address_with_bump(user_metadata_pda, authority, subscription_id)
+}
+
+pub fn get_ticket_subscription_vault_address(ticket_subscription_pda: Pubkey) -> Pubkey {
+    TicketSubscription::vault_address(ticket_subscription_pda)
+}
+
+pub fn get_ticket_subscription_vault_address_for_mint(
+    ticket_subscription_pda: Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    TicketSubscription::vault_address_for_mint(ticket_subscription_pda, *mint, *token_program)
+}
@@ -12,3 +12,11 @@ pub fn get_user_metadata_pda_and_bump(user: Pubkey) -> (Pubkey, u8) {
 pub fn get_user_rewards_vault_address(user: Pubkey) -> Pubkey {
     UserMetadata::user_rewards_vault_address(user)
 }
+
+pub fn get_user_rewards_vault_address_for_mint(
+    user: Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    UserMetadata::user_rewards_vault_address_for_mint(user, *mint, *token_program)
+}
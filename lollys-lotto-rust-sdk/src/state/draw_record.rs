@@ -0,0 +1,128 @@
+use anchor_client::anchor_lang::solana_program::hash::hashv;
+pub use anchor_client::{
+    anchor_lang::solana_program::pubkey::Pubkey,
+    solana_client::{nonblocking::rpc_client::RpcClient as NonBlockingRpcClient, rpc_client},
+};
+use lollys_lotto::state::{DrawRecord, LottoGame, LottoGameVersion};
+use std::collections::HashSet;
+
+use crate::{
+    error::{LollyLottoSDKError, Result},
+    utils::{get_state, get_state_blocking},
+};
+
+pub async fn get_draw_record(
+    address: &Pubkey,
+    client: &NonBlockingRpcClient,
+) -> Result<DrawRecord> {
+    get_state(address, client, "DrawRecord").await
+}
+
+pub async fn get_draw_record_blocking(
+    address: &Pubkey,
+    client: &rpc_client::RpcClient,
+) -> Result<DrawRecord> {
+    get_state_blocking(address, client, "DrawRecord")
+}
+
+/// Winning numbers of a round recomputed off-chain from the randomness kept in its [DrawRecord].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecomputedWinningNumbers {
+    /// The randomness seed the winning numbers were derived from.
+    pub seed: [u8; 32],
+    /// The number of combinations derived from the seed, duplicates included.
+    pub nonce: u64,
+    /// The winning combinations in the order they were drawn, jackpot first.
+    pub winning_numbers: Vec<[u8; 6]>,
+    /// The winning ticket number of a raffle, 0 otherwise.
+    pub raffle_winning_ticket_number: u64,
+}
+
+/// Recomputes the winning numbers of `lotto_game` from the randomness kept in its [DrawRecord],
+/// following the documented derivation rather than the on-chain code:
+/// - the combination for nonce `n` takes number `i` from bytes `2i..2i+2` (little endian) of
+///   `sha256(seed || n as u64 little endian)`, modulo `max_numbers_in_ticket[i] + 1`,
+/// - combinations already drawn are skipped, until [LottoGame::MAX_WINNING_NUMBERS_V1] are drawn,
+/// - a raffle takes the winning ticket number from the first 16 bytes (little endian) of
+///   `sha256(seed || "raffle")`, modulo the number of tickets sold.
+pub fn recompute_winning_numbers(
+    draw_record: &DrawRecord,
+    lotto_game: &LottoGame,
+) -> Result<RecomputedWinningNumbers> {
+    let request = draw_record
+        .fulfilled_request()
+        .ok_or(LollyLottoSDKError::DrawNotFulfilled(draw_record.lotto_game))?;
+    let seed = request.randomness;
+
+    if lotto_game.version == LottoGameVersion::Raffle {
+        let raffle_winning_ticket_number = if lotto_game.tickets_sold == 0 {
+            0
+        } else {
+            let hash = hashv(&[seed.as_ref(), b"raffle"]).to_bytes();
+            let value = u128::from_le_bytes(hash[..16].try_into().unwrap());
+            (value % lotto_game.tickets_sold as u128) as u64
+        };
+        return Ok(RecomputedWinningNumbers {
+            seed,
+            nonce: 1,
+            winning_numbers: Vec::new(),
+            raffle_winning_ticket_number,
+        });
+    }
+
+    let max_numbers_in_ticket = lotto_game.max_numbers_in_ticket;
+    let mut winning_numbers = Vec::with_capacity(LottoGame::MAX_WINNING_NUMBERS_V1);
+    let mut drawn = HashSet::with_capacity(LottoGame::MAX_WINNING_NUMBERS_V1);
+    let mut nonce: u64 = 0;
+    while winning_numbers.len() < LottoGame::MAX_WINNING_NUMBERS_V1 {
+        let hash = hashv(&[seed.as_ref(), nonce.to_le_bytes().as_ref()]).to_bytes();
+        nonce = nonce.checked_add(1).ok_or_else(|| {
+            LollyLottoSDKError::RecomputeFailure(draw_record.lotto_game, "nonce overflow".into())
+        })?;
+        let mut numbers = [0u8; 6];
+        for (i, number) in numbers.iter_mut().enumerate() {
+            let value = u16::from_le_bytes([hash[2 * i], hash[2 * i + 1]]);
+            *number = (value % (max_numbers_in_ticket[i] as u16 + 1)) as u8;
+        }
+        if drawn.insert(numbers) {
+            winning_numbers.push(numbers);
+        }
+    }
+    Ok(RecomputedWinningNumbers {
+        seed,
+        nonce,
+        winning_numbers,
+        raffle_winning_ticket_number: 0,
+    })
+}
+
+/// Packs a combination the way LottoGame stores it: number1..number5 take 5 bits each,
+/// from the least significant bit up, and the jackpot number the 6 bits above them.
+fn pack_numbers(numbers: &[u8; 6]) -> u32 {
+    numbers
+        .iter()
+        .enumerate()
+        .fold(0, |key, (i, &number)| key | ((number as u32) << (5 * i)))
+}
+
+/// Checks that the winning numbers stored in `lotto_game` are the ones derived
+/// from the randomness kept in its [DrawRecord].
+pub fn verify_winning_numbers(draw_record: &DrawRecord, lotto_game: &LottoGame) -> Result<bool> {
+    let recomputed = recompute_winning_numbers(draw_record, lotto_game)?;
+    let filled = lotto_game.winning_numbers_filled as usize;
+    if recomputed.seed != lotto_game.winning_numbers_seed
+        || recomputed.nonce != draw_record.winning_numbers_nonce
+        || recomputed.raffle_winning_ticket_number != lotto_game.raffle_winning_ticket_number
+    {
+        return Ok(false);
+    }
+    if lotto_game.version == LottoGameVersion::Raffle {
+        return Ok(filled == 1);
+    }
+    Ok(filled == recomputed.winning_numbers.len()
+        && recomputed
+            .winning_numbers
+            .iter()
+            .zip(lotto_game.winning_numbers[..filled].iter())
+            .all(|(numbers, &key)| pack_numbers(numbers) == key))
+}
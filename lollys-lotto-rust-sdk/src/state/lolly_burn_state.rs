@@ -2,7 +2,7 @@ pub use anchor_client::{
     anchor_lang::solana_program::pubkey::Pubkey,
     solana_client::{nonblocking::rpc_client::RpcClient as NonBlockingRpcClient, rpc_client},
 };
-use lollys_lotto::state::LollyBurnState;
+use lollys_lotto::state::{LollyBurnState, RoundBurn};
 
 use crate::{
     error::Result,
@@ -22,3 +22,33 @@ pub async fn get_lolly_burn_state_blocking(
 ) -> Result<LollyBurnState> {
     get_state_blocking(address, client, "LollyBurnState")
 }
+
+/// Buy and burn flow of the rounds still tracked by `lolly_burn_state`, oldest round first.
+pub fn round_burns(lolly_burn_state: &LollyBurnState) -> Vec<RoundBurn> {
+    let mut round_burns: Vec<RoundBurn> = lolly_burn_state
+        .round_burns
+        .iter()
+        .filter(|round_burn| **round_burn != RoundBurn::default())
+        .copied()
+        .collect();
+    round_burns.sort_by_key(|round_burn| round_burn.round);
+    round_burns
+}
+
+pub async fn get_round_burn(
+    lolly_burn_state: &Pubkey,
+    round: u64,
+    client: &NonBlockingRpcClient,
+) -> Result<Option<RoundBurn>> {
+    let lolly_burn_state = get_lolly_burn_state(lolly_burn_state, client).await?;
+    Ok(lolly_burn_state.round_burn(round).copied())
+}
+
+pub async fn get_round_burn_blocking(
+    lolly_burn_state: &Pubkey,
+    round: u64,
+    client: &rpc_client::RpcClient,
+) -> Result<Option<RoundBurn>> {
+    let lolly_burn_state = get_lolly_burn_state_blocking(lolly_burn_state, client).await?;
+    Ok(lolly_burn_state.round_burn(round).copied())
+}
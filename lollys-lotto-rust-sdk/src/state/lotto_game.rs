@@ -19,3 +19,14 @@ pub async fn get_lotto_game_blocking(
 ) -> Result<LottoGame> {
     get_state_blocking(address, client, "LottoGame")
 }
+
+/// Estimated prize of a single jackpot, tier 1, tier 2 and tier 3 winner with the tickets sold so far,
+/// including the minimum jackpot, rollovers and sponsor top-ups.
+pub fn estimate_winning_amounts(lotto_game: &LottoGame) -> [u64; 4] {
+    [
+        lotto_game.current_jackpot_winning_amount(),
+        lotto_game.current_tier_1_winning_amount(),
+        lotto_game.current_tier_2_winning_amount(),
+        lotto_game.current_tier_3_winning_amount(),
+    ]
+}
@@ -1,13 +1,17 @@
+pub mod draw_record;
 pub mod events;
 pub mod lolly_burn_state;
 pub mod lollys_lotto;
 pub mod lotto_game;
 pub mod lotto_ticket;
+pub mod stake_pool;
 pub mod user_metadata;
 
+pub use draw_record::*;
 pub use events::*;
 pub use lolly_burn_state::*;
 pub use lollys_lotto::*;
 pub use lotto_game::*;
 pub use lotto_ticket::*;
+pub use stake_pool::*;
 pub use user_metadata::*;
@@ -0,0 +1,35 @@
+pub use anchor_client::{
+    anchor_lang::solana_program::pubkey::Pubkey,
+    solana_client::{nonblocking::rpc_client::RpcClient as NonBlockingRpcClient, rpc_client},
+};
+use lollys_lotto::state::{StakePool, StakePosition};
+
+use crate::{
+    error::Result,
+    utils::{get_state, get_state_blocking},
+};
+
+pub async fn get_stake_pool(address: &Pubkey, client: &NonBlockingRpcClient) -> Result<StakePool> {
+    get_state(address, client, "StakePool").await
+}
+
+pub async fn get_stake_pool_blocking(
+    address: &Pubkey,
+    client: &rpc_client::RpcClient,
+) -> Result<StakePool> {
+    get_state_blocking(address, client, "StakePool")
+}
+
+pub async fn get_stake_position(
+    address: &Pubkey,
+    client: &NonBlockingRpcClient,
+) -> Result<StakePosition> {
+    get_state(address, client, "StakePosition").await
+}
+
+pub async fn get_stake_position_blocking(
+    address: &Pubkey,
+    client: &rpc_client::RpcClient,
+) -> Result<StakePosition> {
+    get_state_blocking(address, client, "StakePosition")
+}
@@ -11,3 +11,14 @@ pub const LOLLY_MINT: Pubkey = pubkey!("1o1ohFR7M25XktNXAsbnDbvserNoFkrFLdA9916E
 pub const LOLLY_MINT: Pubkey = pubkey!("1o1ohFR7M25XktNXAsbnDbvserNoFkrFLdA9916EGWw");
 
 pub const WRAPPED_SOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Jupiter v6 aggregator, allowlisted as swap program of every new LollyBurnState
+pub const JUPITER_V6_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Switchboard On-Demand program, owner of the randomness accounts
+#[cfg(not(feature = "devnet"))]
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+#[cfg(feature = "devnet")]
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
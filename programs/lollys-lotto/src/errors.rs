@@ -128,9 +128,168 @@ pub enum LollysLottoError {
 
     #[msg("[L139] No Duplicate tickets found")] //0x17FB (6139)
     NoDuplicateTicketsFound,
+
+    #[msg("[L140] Invalid randomness length")] //0x17FC (6140)
+    InvalidRandomnessLength,
+
+    #[msg("[L141] Winning numbers seed already set")] //0x17FD (6141)
+    WinningNumbersSeedAlreadySet,
+
+    #[msg("[L142] Winning numbers seed not set")] //0x17FE (6142)
+    WinningNumbersSeedNotSet,
+
+    #[msg("[L143] All winning numbers already set")] //0x17FF (6143)
+    AllWinningNumbersSet,
+
+    #[msg("[L144] Randomness provider mismatch")] //0x1800 (6144)
+    RandomnessProviderMismatch,
+
+    #[msg("[L145] Winning numbers already requested")] //0x1801 (6145)
+    WinningNumbersAlreadyRequested,
+
+    #[msg("[L146] Invalid randomness account")] //0x1802 (6146)
+    InvalidRandomnessAccount,
+
+    #[msg("[L147] Randomness already revealed")] //0x1803 (6147)
+    RandomnessAlreadyRevealed,
+
+    #[msg("[L148] Randomness not resolved")] //0x1804 (6148)
+    RandomnessNotResolved,
+
+    #[msg("[L149] Invalid randomness request")] //0x1805 (6149)
+    InvalidRandomnessRequest,
+
+    #[msg("[L150] Randomness request not expired")] //0x1806 (6150)
+    RandomnessRequestNotExpired,
+
+    #[msg("[L151] Invalid LottoGame state transition")] //0x1807 (6151)
+    InvalidStateTransition,
+
+    #[msg("[L152] Invalid LottoGame state")] //0x1808 (6152)
+    InvalidLottoGameState,
+
+    #[msg("[L153] Sales close buffer must be shorter than the game duration")] //0x1809 (6153)
+    InvalidSalesCloseBuffer,
+
+    #[msg("[L154] LottoGame ticket sales are closed")] //0x180A (6154)
+    LottoGameSalesClosed,
+
+    #[msg("[L155] DrawRecord has no room for more randomness requests")] //0x180B (6155)
+    DrawRecordFull,
+
+    #[msg("[L156] Ticket has not been declared a winner")] //0x180C (6156)
+    WinnerNotDeclared,
+
+    #[msg("[L157] Lotto Game vault holds less than its outstanding liabilities")] //0x180D (6157)
+    LottoGameVaultInsolvent,
+
+    #[msg("[L158] Disbursement exceeds the reserved liability")] //0x180E (6158)
+    LiabilityExceeded,
+
+    #[msg("[L159] Claim period of the Lotto Game is not over yet")] //0x180F (6159)
+    ClaimPeriodNotOver,
+
+    #[msg("[L160] Destination does not match the configured sweep destination")] //0x1810 (6160)
+    InvalidSweepDestination,
+
+    #[msg("[L161] Claim period must be greater than zero")] //0x1811 (6161)
+    InvalidClaimPeriod,
+
+    #[msg("[L162] Claim deadline of the Lotto Game has passed")] //0x1812 (6162)
+    ClaimDeadlinePassed,
+
+    #[msg("[L163] Lotto Ticket can still win and has not been settled")] //0x1813 (6163)
+    LottoTicketNotSettled,
+
+    #[msg("[L164] Invalid remaining accounts")] //0x1814 (6164)
+    InvalidRemainingAccounts,
+
+    #[msg("[L165] Signer is neither the user nor their claim authority")] //0x1815 (6165)
+    InvalidClaimAuthority,
+
+    #[msg("[L166] Ticket purchase intent is not signed by the user")] //0x1816 (6166)
+    InvalidIntentSignature,
+
+    #[msg("[L167] Ticket purchase intent has expired")] //0x1817 (6167)
+    IntentExpired,
+
+    #[msg("[L168] Ticket purchase intent nonce does not match")] //0x1818 (6168)
+    InvalidIntentNonce,
+
+    #[msg("[L169] Ticket price is above the maximum price of the intent")] //0x1819 (6169)
+    TicketPriceAboveMax,
+
+    #[msg("[L170] User USDC token account is not delegated to the user metadata")] //0x181A (6170)
+    InvalidUsdcDelegate,
+
+    #[msg("[L171] Ticket subscription must cover at least one round")] //0x181B (6171)
+    InvalidSubscriptionRounds,
+
+    #[msg("[L172] Ticket subscription has no rounds remaining")] //0x181C (6172)
+    SubscriptionExhausted,
+
+    #[msg("[L173] Ticket subscription was already fulfilled for this round")] //0x181D (6173)
+    SubscriptionAlreadyFulfilled,
+
+    #[msg("[L174] Swap returned less LOLLY than the minimum requested")] //0x181E (6174)
+    SwapSlippageExceeded,
+
+    #[msg("[L175] Swap program is not on the LollyBurnState allowlist")] //0x181F (6175)
+    SwapProgramNotAllowed,
+
+    #[msg("[L176] Invalid swap program allowlist")] //0x1820 (6176)
+    InvalidSwapProgramAllowlist,
+
+    #[msg("[L177] Swap did not turn vault USDC into LOLLY")] //0x1821 (6177)
+    InvalidSwapBalanceChange,
+
+    #[msg("[L178] Burn would exceed the LollyBurnState burn cap of this epoch")] //0x1822 (6178)
+    BurnCapExceeded,
+
+    #[msg("[L179] Swap spent more USDC than the maximum requested")] //0x1823 (6179)
+    SwapAmountExceeded,
+
+    #[msg("[L180] Invalid stake pool cooldown or discount tiers")] //0x1824 (6180)
+    InvalidStakePoolConfig,
+
+    #[msg("[L181] Stake position is still in its unstake cooldown")] //0x1825 (6181)
+    UnstakeCooldownNotOver,
+
+    #[msg("[L182] Unstake amount exceeds the staked amount")] //0x1826 (6182)
+    InsufficientStake,
+
+    #[msg("[L183] Stake position does not belong to this user and stake pool")] //0x1827 (6183)
+    InvalidStakePosition,
+
+    #[msg("[L184] Payment asset does not match this purchase path or token account")] //0x1828 (6184)
+    InvalidPaymentAsset,
+
+    #[msg("[L185] LOLLY payment discount exceeds the DAO share of the ticket")] //0x1829 (6185)
+    InvalidLollyPaymentDiscount,
+
+    #[msg("[L186] Prize pool top-up must be greater than zero")] //0x182A (6186)
+    InvalidSponsorAmount,
+
+    #[msg("[L187] Signer or token account is not the LollysLotto treasury")] //0x182B (6187)
+    InvalidTreasury,
+
+    #[msg("[L188] Minimum jackpot must be non-zero and funded once")] //0x182C (6188)
+    InvalidMinJackpot,
+
+    #[msg("[L189] LottoGame is not played in the mint of the ticket subscription")] //0x182D (6189)
+    SubscriptionMintMismatch,
+
+    #[msg("[L190] Committed randomness was revealed and can't be replaced")] //0x182E (6190)
+    RandomnessRevealWithheld,
+
+    #[msg("[L191] Only the jackpot can be topped up")] //0x182F (6191)
+    SponsoredTierNotJackpot,
+
+    #[msg("[L192] Account is not the payer of the LottoTicketCombination")] //0x1830 (6192)
+    InvalidCombinationPayer,
 }
 
-pub const LOLLY_NUM_ERR_VARIANTS: u32 = 39;
+pub const LOLLY_NUM_ERR_VARIANTS: u32 = 92;
 
 impl LollysLottoError {
     /// For use during checked math operations,
@@ -73,7 +73,7 @@ pub fn burn_lolly(ctx: Context<BurnLolly>) -> Result<()> {
         lolly_vault_balance,
     )?;
 
-    lolly_burn_state.total_lolly_burnt += lolly_vault_balance;
+    lolly_burn_state.record_burn(lolly_vault_balance, Clock::get()?.epoch)?;
 
     msg!(
         "Total Burnt $LOLLY: {}",
@@ -7,7 +7,7 @@ use crate::{
     },
 };
 pub use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount;
 
 #[derive(Accounts)]
 pub struct CloseLottoGame<'info> {
@@ -18,7 +18,8 @@ pub struct CloseLottoGame<'info> {
     #[account(
         mut,
         has_one = authority,
-        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::GameNotClosed,
+        has_one = lotto_game_vault,
+        constraint = lotto_game.load()?.state == LottoGameState::Finished @LollysLottoError::GameNotClosed,
         close = authority,
         seeds = [
             LottoGame::IDENT,
@@ -39,12 +40,9 @@ pub struct CloseLottoGame<'info> {
     )]
     pub lotto_game_vault_signer: UncheckedAccount<'info>,
 
-    #[account(
-        constraint = lotto_game_vault.amount == 0 @LollysLottoError::LottoGameVaultNotEmpty,
-        associated_token::mint = lotto_game.load()?.lotto_game_mint,
-        associated_token::authority = lotto_game_vault_signer,
-    )]
-    pub lotto_game_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Held by the SPL Token or the Token-2022 program, depending on the LottoGame mint.
+    /// Already closed once the round is swept, otherwise checked to be empty.
+    pub lotto_game_vault: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
@@ -53,6 +51,19 @@ pub struct CloseLottoGame<'info> {
 }
 
 pub fn close_lotto_game(ctx: Context<CloseLottoGame>) -> Result<()> {
+    // CHECK: A vault the sweep hasn't closed must not hold anything
+    let lotto_game_vault = &ctx.accounts.lotto_game_vault;
+    if !lotto_game_vault.data_is_empty() {
+        let lotto_game_vault =
+            TokenAccount::try_deserialize(&mut &lotto_game_vault.try_borrow_data()?[..])?;
+        if lotto_game_vault.owner != ctx.accounts.lotto_game_vault_signer.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintTokenOwner.into());
+        }
+        if lotto_game_vault.amount != 0 {
+            return Err(LollysLottoError::LottoGameVaultNotEmpty.into());
+        }
+    }
+
     let block_time = Clock::get()?.unix_timestamp;
     ctx.accounts.event_emitter.emit_new_event(
         Some(block_time),
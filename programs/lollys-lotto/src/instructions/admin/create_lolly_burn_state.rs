@@ -7,7 +7,10 @@ use anchor_spl::{
 use crate::{
     constants::{LOLLY_MINT, USDC_MINT_DEVNET},
     pda_identifier::PDAIdentifier,
-    state::{CreateLollyBurnStateEvent, EventEmitter, LollyBurnState, LollysLottoProgramEventData},
+    state::{
+        CreateLollyBurnStateEvent, EventEmitter, LollyBurnState, LollysLottoProgramEventData,
+        RoundBurn,
+    },
 };
 
 #[derive(Accounts)]
@@ -31,7 +34,7 @@ pub struct CreateLollyBurnState<'info> {
     /// Mint address of the LOLLY token
     #[account(address = LOLLY_MINT)]
     lolly_mint: Box<Account<'info, Mint>>,
-    /// LOLLY token account to store LOLLY swapped from USDC of lolly_burn_state_usdc_vault using jupiter owned by LollyBurnState PDA
+    /// LOLLY token account to store LOLLY swapped from USDC of lolly_burn_state_usdc_vault through an allowlisted swap program, owned by LollyBurnState PDA
     #[account(
         init,
         payer = authority,
@@ -62,6 +65,13 @@ impl<'info> CreateLollyBurnState<'info> {
         let lolly_burn_state = &mut self.lolly_burn_state;
         lolly_burn_state.bump = bump;
         lolly_burn_state.authority = self.authority.key();
+        lolly_burn_state.total_usdc_swapped = 0;
+        lolly_burn_state.total_lolly_acquired = 0;
+        lolly_burn_state.swap_programs = LollyBurnState::default_swap_programs();
+        lolly_burn_state.burn_cap_per_epoch = 0;
+        lolly_burn_state.burn_epoch = 0;
+        lolly_burn_state.lolly_burnt_in_epoch = 0;
+        lolly_burn_state.round_burns = [RoundBurn::default(); LollyBurnState::ROUND_BURNS_LEN];
 
         let block_time = Clock::get()?.unix_timestamp;
         self.event_emitter.emit_new_event(
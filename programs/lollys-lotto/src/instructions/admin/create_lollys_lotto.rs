@@ -2,7 +2,10 @@ pub use anchor_lang::prelude::*;
 
 use crate::{
     pda_identifier::PDAIdentifier,
-    state::{CreateLollysLottoEvent, EventEmitter, LollysLotto, LollysLottoProgramEventData},
+    state::{
+        CreateLollysLottoEvent, EventEmitter, LollysLotto, LollysLottoProgramEventData,
+        RandomnessProvider, SweepDestination,
+    },
 };
 
 #[derive(Accounts)]
@@ -31,6 +34,10 @@ impl<'info> CreateLollysLotto<'info> {
         lollys_lotto.authority = *self.authority.key;
         lollys_lotto.lotto_game_count = 0;
         lollys_lotto.bump = bump;
+        lollys_lotto.randomness_provider = RandomnessProvider::default();
+        lollys_lotto.treasury = *self.authority.key;
+        lollys_lotto.sweep_destination = SweepDestination::default();
+        lollys_lotto.lolly_payment_discount_bps = 0;
 
         let block_time = Clock::get()?.unix_timestamp;
 
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    constants::{LOLLY_MINT, USDC_MINT_DEVNET},
+    pda_identifier::PDAIdentifier,
+    state::{
+        CreateStakePoolEvent, EventEmitter, LollysLottoProgramEventData, StakeDiscountTier,
+        StakePool,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CreateStakePool<'info> {
+    /// Authority of the LottoGame instances whose protocol fees are shared with stakers
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<StakePool>(),
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(address = LOLLY_MINT)]
+    pub lolly_mint: Box<Account<'info, Mint>>,
+
+    /// LOLLY token account holding the stake, owned by StakePool PDA
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = lolly_mint,
+        associated_token::authority = stake_pool,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = USDC_MINT_DEVNET)]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    /// USDC token account holding the rewards of the stakers, owned by StakePool PDA
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = stake_pool,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStakePool<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    ) -> Result<()> {
+        let stake_pool = &mut self.stake_pool;
+        stake_pool.bump = bump;
+        stake_pool.authority = self.authority.key();
+        stake_pool.total_staked = 0;
+        stake_pool.reward_per_share = 0;
+        stake_pool.undistributed_rewards = 0;
+        stake_pool.total_rewards_distributed = 0;
+        stake_pool.set_config(unstake_cooldown, discount_tiers)?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::CreateStakePool(CreateStakePoolEvent {
+                authority: self.authority.key(),
+                stake_pool: self.stake_pool.key(),
+                stake_vault: self.stake_vault.key(),
+                reward_vault: self.reward_vault.key(),
+                unstake_cooldown,
+                discount_tiers,
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, ExpireUserRewardsEvent, LollysLotto, LollysLottoProgramEventData, LottoGame,
+        UserMetadata,
+    },
+    utils::transfer_quote_tokens,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ExpireUserRewards<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    /// CHECK: User account
+    #[account()]
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    /// Mint of the expired rewards, USDC or the Token-2022 mint of the LottoGames they were won in
+    pub rewards_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = user_metadata,
+        associated_token::token_program = token_program,
+    )]
+    pub user_rewards_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account or the vault of `next_lotto_game`, depending on `lollys_lotto.sweep_destination`.
+    #[account(
+        mut,
+        constraint = destination_vault.mint == rewards_mint.key() @LollysLottoError::InvalidSweepDestination,
+    )]
+    pub destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The open round receiving the funds when sweeping to the next round.
+    #[account(mut)]
+    pub next_lotto_game: Option<AccountLoader<'info, LottoGame>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExpireUserRewards<'info> {
+    /// Moves the rewards of `rewards_mint` left unclaimed past the user's claim deadline to the
+    /// configured sweep destination.
+    pub fn process(&mut self) -> Result<()> {
+        let user_metadata = &mut self.user_metadata;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        if block_time < user_metadata.claim_deadline {
+            return Err(LollysLottoError::ClaimPeriodNotOver.into());
+        }
+
+        let expired_amount = self.user_rewards_vault.amount;
+        if expired_amount == 0 {
+            return Err(LollysLottoError::NoRewardsToClaimFromVault.into());
+        }
+
+        self.lollys_lotto.credit_sweep_destination(
+            &self.destination_vault,
+            self.next_lotto_game.as_ref(),
+            expired_amount,
+        )?;
+
+        transfer_quote_tokens(
+            &self.token_program,
+            &self.rewards_mint,
+            &self.user_rewards_vault,
+            &mut self.destination_vault,
+            user_metadata.to_account_info(),
+            &[&[
+                UserMetadata::IDENT,
+                self.user.key().as_ref(),
+                &[user_metadata.bump],
+            ]],
+            expired_amount,
+        )?;
+
+        user_metadata.total_amount_expired += expired_amount;
+
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::ExpireUserRewards(ExpireUserRewardsEvent {
+                user: user_metadata.user,
+                user_metadata: user_metadata.key(),
+                user_rewards_vault: self.user_rewards_vault.key(),
+                sweep_destination: self.lollys_lotto.sweep_destination,
+                destination_vault: self.destination_vault.key(),
+                claim_deadline: user_metadata.claim_deadline,
+                expired_amount,
+                total_amount_expired: user_metadata.total_amount_expired,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
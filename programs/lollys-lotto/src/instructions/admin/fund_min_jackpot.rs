@@ -0,0 +1,100 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, FundMinJackpotEvent, LollysLotto, LollysLottoProgramEventData, LottoGame,
+        LottoGameState,
+    },
+    utils::transfer_quote_tokens,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct FundMinJackpot<'info> {
+    #[account(
+        mut,
+        constraint = treasury.key() == lollys_lotto.treasury @LollysLottoError::InvalidTreasury,
+    )]
+    pub treasury: Signer<'info>,
+
+    #[account(
+        seeds = [
+            LollysLotto::IDENT,
+            lotto_game.load()?.authority.as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        token::mint = lotto_game_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        seeds = [
+            LottoGame::IDENT,
+            lotto_game.load()?.authority.as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(mut)]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundMinJackpot<'info> {
+    pub fn process(&mut self, round: u64, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(LollysLottoError::InvalidMinJackpot.into());
+        }
+
+        // Escrow the minimum jackpot in LottoGameVault until the round is finished
+        let amount_received = transfer_quote_tokens(
+            &self.token_program,
+            &self.lotto_game_mint,
+            &self.treasury_token_account,
+            &mut self.lotto_game_vault,
+            self.treasury.to_account_info(),
+            &[],
+            amount,
+        )?;
+
+        let lotto_game = &mut *self.lotto_game.load_mut()?;
+        lotto_game.credit_min_jackpot(amount_received)?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::FundMinJackpot(FundMinJackpotEvent {
+                treasury: self.treasury.key(),
+                lotto_game: self.lotto_game.key(),
+                round,
+                amount,
+                min_jackpot: lotto_game.min_jackpot,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
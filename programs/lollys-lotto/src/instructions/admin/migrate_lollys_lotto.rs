@@ -0,0 +1,57 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollysLotto, LollysLottoProgramEventData, MigrateAccountEvent},
+    utils::migrate_account,
+};
+
+#[derive(Accounts)]
+pub struct MigrateLollysLotto<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: LollysLotto created by an earlier version of the program, which can't be
+    /// deserialized before it is migrated. Checked in `migrate_account`.
+    #[account(
+        mut,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lollys_lotto: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateLollysLotto<'info> {
+    /// Grows the LollysLotto of `authority` to the current layout. The randomness provider,
+    /// treasury, sweep destination and LOLLY payment discount start zeroed and are set
+    /// with their admin instructions.
+    pub fn process(&mut self) -> Result<()> {
+        let space = 8 + std::mem::size_of::<LollysLotto>();
+        let previous_space = migrate_account::<LollysLotto>(
+            &self.lollys_lotto,
+            space,
+            &self.authority,
+            &self.system_program,
+        )?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::MigrateAccount(MigrateAccountEvent {
+                account: self.lollys_lotto.key(),
+                payer: self.authority.key(),
+                previous_space: previous_space as u64,
+                space: space as u64,
+            }),
+        )?;
+        Ok(())
+    }
+}
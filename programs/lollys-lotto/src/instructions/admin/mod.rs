@@ -6,8 +6,20 @@ pub mod close_lotto_game;
 pub mod create_event_emitter;
 pub mod create_lolly_burn_state;
 pub mod create_lollys_lotto;
+pub mod create_stake_pool;
+pub mod expire_user_rewards;
+pub mod fund_min_jackpot;
+pub mod migrate_lollys_lotto;
+pub mod set_burn_cap;
+pub mod set_lolly_payment_discount;
+pub mod set_randomness_provider;
+pub mod set_stake_pool_config;
+pub mod set_swap_programs;
+pub mod set_sweep_destination;
 pub mod start_lotto_game;
+pub mod swap_and_burn;
 pub mod swap_usdc_lolly;
+pub mod sweep_lotto_game_vault;
 
 pub use burn_lolly::*;
 pub use close_event_emitter::*;
@@ -17,5 +29,17 @@ pub use close_lotto_game::*;
 pub use create_event_emitter::*;
 pub use create_lolly_burn_state::*;
 pub use create_lollys_lotto::*;
+pub use create_stake_pool::*;
+pub use expire_user_rewards::*;
+pub use fund_min_jackpot::*;
+pub use migrate_lollys_lotto::*;
+pub use set_burn_cap::*;
+pub use set_lolly_payment_discount::*;
+pub use set_randomness_provider::*;
+pub use set_stake_pool_config::*;
+pub use set_swap_programs::*;
+pub use set_sweep_destination::*;
 pub use start_lotto_game::*;
+pub use swap_and_burn::*;
 pub use swap_usdc_lolly::*;
+pub use sweep_lotto_game_vault::*;
@@ -0,0 +1,44 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollyBurnState, LollysLottoProgramEventData, SetBurnCapEvent},
+};
+
+#[derive(Accounts)]
+pub struct SetBurnCap<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollyBurnState::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lolly_burn_state.bump,
+    )]
+    pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetBurnCap<'info> {
+    /// Sets the maximum LOLLY burnt per Solana epoch, 0 removes the cap.
+    /// LOLLY already burnt in the current epoch still counts against the new cap.
+    pub fn process(&mut self, burn_cap_per_epoch: u64) -> Result<()> {
+        self.lolly_burn_state.burn_cap_per_epoch = burn_cap_per_epoch;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetBurnCap(SetBurnCapEvent {
+                authority: self.authority.key(),
+                lolly_burn_state: self.lolly_burn_state.key(),
+                burn_cap_per_epoch,
+            }),
+        )?;
+        Ok(())
+    }
+}
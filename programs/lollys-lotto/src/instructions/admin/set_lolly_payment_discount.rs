@@ -0,0 +1,47 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollysLotto, LollysLottoProgramEventData, SetLollyPaymentDiscountEvent},
+};
+
+#[derive(Accounts)]
+pub struct SetLollyPaymentDiscount<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetLollyPaymentDiscount<'info> {
+    /// Sets the discount for tickets paid with LOLLY, 0 disables it.
+    pub fn process(&mut self, lolly_payment_discount_bps: u16) -> Result<()> {
+        if lolly_payment_discount_bps > LollysLotto::MAX_LOLLY_PAYMENT_DISCOUNT_BPS {
+            return Err(LollysLottoError::InvalidLollyPaymentDiscount.into());
+        }
+        self.lollys_lotto.lolly_payment_discount_bps = lolly_payment_discount_bps;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetLollyPaymentDiscount(SetLollyPaymentDiscountEvent {
+                authority: self.authority.key(),
+                lollys_lotto: self.lollys_lotto.key(),
+                lolly_payment_discount_bps,
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLotto, LollysLottoProgramEventData, RandomnessProvider,
+        SetRandomnessProviderEvent,
+    },
+};
+
+#[derive(Accounts)]
+pub struct SetRandomnessProvider<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetRandomnessProvider<'info> {
+    pub fn process(&mut self, randomness_provider: RandomnessProvider) -> Result<()> {
+        let previous_randomness_provider = self.lollys_lotto.randomness_provider;
+        self.lollys_lotto.randomness_provider = randomness_provider;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetRandomnessProvider(SetRandomnessProviderEvent {
+                lollys_lotto: self.lollys_lotto.key(),
+                previous_randomness_provider,
+                randomness_provider,
+            }),
+        )?;
+        Ok(())
+    }
+}
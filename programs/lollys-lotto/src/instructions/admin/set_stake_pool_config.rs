@@ -0,0 +1,52 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLottoProgramEventData, SetStakePoolConfigEvent, StakeDiscountTier,
+        StakePool,
+    },
+};
+
+#[derive(Accounts)]
+pub struct SetStakePoolConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetStakePoolConfig<'info> {
+    /// Replaces the unstake cooldown and the ticket discount tiers of the StakePool.
+    pub fn process(
+        &mut self,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    ) -> Result<()> {
+        self.stake_pool
+            .set_config(unstake_cooldown, discount_tiers)?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetStakePoolConfig(SetStakePoolConfigEvent {
+                authority: self.authority.key(),
+                stake_pool: self.stake_pool.key(),
+                unstake_cooldown,
+                discount_tiers,
+            }),
+        )?;
+        Ok(())
+    }
+}
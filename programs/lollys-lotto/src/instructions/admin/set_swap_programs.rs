@@ -0,0 +1,43 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollyBurnState, LollysLottoProgramEventData, SetSwapProgramsEvent},
+};
+
+#[derive(Accounts)]
+pub struct SetSwapPrograms<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollyBurnState::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lolly_burn_state.bump,
+    )]
+    pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetSwapPrograms<'info> {
+    /// Replaces the allowlist of programs `swap_usdc_lolly` may route through.
+    pub fn process(&mut self, swap_programs: Vec<Pubkey>) -> Result<()> {
+        self.lolly_burn_state.set_swap_programs(&swap_programs)?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetSwapPrograms(SetSwapProgramsEvent {
+                authority: self.authority.key(),
+                lolly_burn_state: self.lolly_burn_state.key(),
+                swap_programs,
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLotto, LollysLottoProgramEventData, SetSweepDestinationEvent,
+        SweepDestination,
+    },
+};
+
+#[derive(Accounts)]
+pub struct SetSweepDestination<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetSweepDestination<'info> {
+    pub fn process(&mut self, sweep_destination: SweepDestination, treasury: Pubkey) -> Result<()> {
+        self.lollys_lotto.sweep_destination = sweep_destination;
+        self.lollys_lotto.treasury = treasury;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetSweepDestination(SetSweepDestinationEvent {
+                lollys_lotto: self.lollys_lotto.key(),
+                sweep_destination,
+                treasury,
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -1,14 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::constants::USDC_MINT_DEVNET;
 use crate::errors::LollysLottoError;
 use crate::pda_identifier::PDAIdentifier;
 use crate::state::{
-    EventEmitter, LollysLotto, LollysLottoProgramEventData, LottoGame, LottoGameState,
-    LottoGameVault, LottoGameVersion, LottoGameWinningNumbers, LottoTicketNumbers,
-    StartLottoGameEvent,
+    DrawRecord, DrawRecordRequest, EventEmitter, LollysLotto, LollysLottoProgramEventData,
+    LottoGame, LottoGameLiabilities, LottoGameState, LottoGameVault, LottoGameVersion,
+    LottoTicketNumbers, StartLottoGameEvent,
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -16,7 +15,10 @@ pub struct StartLottoGameParams {
     pub round: u64,
     pub ticket_price: u64,
     pub game_duration: u64,
+    pub sales_close_buffer: u64,
+    pub claim_period: u64,
     pub round_name: String,
+    pub lotto_game_version: LottoGameVersion,
 }
 
 #[derive(Accounts)]
@@ -58,25 +60,40 @@ pub struct StartLottoGame<'info> {
     pub lotto_game_vault_signer: UncheckedAccount<'info>,
 
     /// This instruction initializes the token account
-    /// required for storing this LottoGame's ticket amount collections in USDC.
+    /// required for storing this LottoGame's ticket amount collections in `lotto_game_mint`.
     #[account(
         init,
         payer = authority,
         associated_token::mint = lotto_game_mint,
         associated_token::authority = lotto_game_vault_signer,
+        associated_token::token_program = token_program,
     )]
-    pub lotto_game_vault: Box<Account<'info, TokenAccount>>,
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Keeps the randomness this LottoGame's winning numbers are drawn from.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DrawRecord::SIZE,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
+    /// Quote mint of the LottoGame, USDC or a Token-2022 stablecoin.
     /// Needed for account initialization
     #[account(
-        address = USDC_MINT_DEVNET,
+        mint::token_program = token_program,
     )]
-    pub lotto_game_mint: Box<Account<'info, Mint>>,
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -86,7 +103,10 @@ pub fn start_lotto_game(
     round: u64,
     ticket_price: u64,
     game_duration: u64,
+    sales_close_buffer: u64,
+    claim_period: u64,
     round_name: String,
+    lotto_game_version: LottoGameVersion,
 ) -> Result<()> {
     let lotto_game = &mut *ctx.accounts.lotto_game.load_init()?;
     let lollys_lotto = &mut ctx.accounts.lollys_lotto;
@@ -95,10 +115,18 @@ pub fn start_lotto_game(
         return Err(LollysLottoError::RoundNumbersAreSequential.into());
     }
 
+    if sales_close_buffer >= game_duration {
+        return Err(LollysLottoError::InvalidSalesCloseBuffer.into());
+    }
+
+    if claim_period == 0 {
+        return Err(LollysLottoError::InvalidClaimPeriod.into());
+    }
+
     lotto_game.bump = ctx.bumps.lotto_game;
     lotto_game.lotto_game_vault_bump = ctx.bumps.lotto_game_vault_signer;
-    lotto_game.version = LottoGameVersion::V1;
-    lotto_game.state = LottoGameState::Open;
+    lotto_game.version = lotto_game_version;
+    lotto_game.state = LottoGameState::NotStarted;
     lotto_game.authority = *ctx.accounts.authority.key;
     lotto_game.round = lollys_lotto.lotto_game_count;
     lotto_game.start_date = Clock::get()?.unix_timestamp;
@@ -117,17 +145,43 @@ pub fn start_lotto_game(
     lotto_game.max_numbers_in_ticket[4] = LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number5;
     lotto_game.max_numbers_in_ticket[5] =
         LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.jackpot_number;
-    lotto_game.jackpot_winning_numbers = LottoGameWinningNumbers::default();
-    lotto_game.tier_1_winning_numbers =
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_1_WINNERS_V1];
-    lotto_game.tier_2_winning_numbers =
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_2_WINNERS_V1];
-    lotto_game.tier_3_winning_numbers =
-        [LottoGameWinningNumbers::default(); LottoGame::MAX_TIER_3_WINNERS_V1];
+    lotto_game.winning_numbers.fill(0);
+    lotto_game.winning_amount_disbursed.fill(0);
+    lotto_game.winning_numbers_index.fill(0);
+    lotto_game.winning_numbers_seed = [0; LottoGame::WINNING_NUMBERS_SEED_LEN];
+    lotto_game.winning_numbers_nonce = 0;
+    lotto_game.winning_numbers_filled = 0;
+    lotto_game.winning_numbers_seed_set = 0;
+    lotto_game._padding2 = [0; 5];
+    lotto_game.randomness_request = Pubkey::default();
+    lotto_game.randomness_request_slot = 0;
+    lotto_game.randomness_requested_at = 0;
+    lotto_game.sales_close_buffer = sales_close_buffer;
+    lotto_game.liabilities = LottoGameLiabilities::default();
+    lotto_game.jackpot_rollover = 0;
+    lotto_game.claim_deadline = lotto_game.end_date + claim_period as i64;
+    lotto_game.sponsored_prize_pool = [0; 4];
+    lotto_game.min_jackpot = 0;
+    lotto_game.raffle_winning_ticket_number = 0;
+
+    let draw_record = &mut ctx.accounts.draw_record;
+    draw_record.bump = ctx.bumps.draw_record;
+    draw_record.lotto_game = ctx.accounts.lotto_game.key();
+    draw_record.round = lotto_game.round;
+    draw_record.request_count = 0;
+    draw_record.requests = [DrawRecordRequest::default(); DrawRecord::MAX_REQUESTS];
+    draw_record.mapped_slot = 0;
+    draw_record.winning_numbers_nonce = 0;
 
     lollys_lotto.lotto_game_count += 1;
 
     let block_time = Clock::get()?.unix_timestamp;
+    lotto_game.transition_state(
+        ctx.accounts.lotto_game.key(),
+        LottoGameState::Open,
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
     ctx.accounts.event_emitter.emit_new_event(
         Some(block_time),
         LollysLottoProgramEventData::StartLottoGame(StartLottoGameEvent {
@@ -135,6 +189,8 @@ pub fn start_lotto_game(
             round: lotto_game.round,
             round_name,
             game_duration,
+            sales_close_buffer,
+            claim_deadline: lotto_game.claim_deadline,
             authority: lotto_game.authority,
             lotto_game_pubkey: ctx.accounts.lotto_game.key(),
             lotto_game_vault: ctx.accounts.lotto_game_vault.key(),
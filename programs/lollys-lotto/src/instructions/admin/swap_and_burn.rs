@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::{
+    constants::{LOLLY_MINT, USDC_MINT_DEVNET},
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollyBurnState, LollysLottoProgramEventData, LottoGame, SwapAndBurnEvent,
+    },
+    utils::invoke_swap,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct SwapAndBurn<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            LollyBurnState::IDENT,
+            authority.key().as_ref()
+        ],
+        bump = lolly_burn_state.bump)]
+    pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
+
+    /// The round whose buy and burn share is being burnt
+    #[account(
+        has_one = authority,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(mut, address = LOLLY_MINT @LollysLottoError::OnlySwapToLOLLYAllowed)]
+    pub lolly_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = USDC_MINT_DEVNET,
+        associated_token::authority = lolly_burn_state,
+    )]
+    pub lolly_burn_state_usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = LOLLY_MINT,
+        associated_token::authority = lolly_burn_state,
+    )]
+    pub lolly_burn_state_lolly_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    /// CHECK: Any program on the LollyBurnState allowlist, e.g. Jupiter
+    #[account(
+        executable,
+        constraint = lolly_burn_state.is_swap_program_allowed(swap_program.key()) @LollysLottoError::SwapProgramNotAllowed,
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Swaps at most `max_usdc_in` USDC to LOLLY through an allowlisted swap program,
+/// then burns exactly the LOLLY received. LOLLY already in the vault is left untouched.
+/// remaining_accounts and `data` are the route of the swap program, built off-chain.
+pub fn swap_and_burn<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapAndBurn<'info>>,
+    round: u64,
+    max_usdc_in: u64,
+    min_lolly_out: u64,
+    data: Vec<u8>,
+) -> Result<()> {
+    let usdc_before = ctx.accounts.lolly_burn_state_usdc_vault.amount;
+    let lolly_before = ctx.accounts.lolly_burn_state_lolly_vault.amount;
+
+    let authority = ctx.accounts.authority.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        LollyBurnState::IDENT,
+        authority.as_ref(),
+        &[ctx.accounts.lolly_burn_state.bump],
+    ]];
+
+    // lolly_burn_state PDA is signing this swap
+    invoke_swap(
+        &ctx.accounts.swap_program,
+        ctx.remaining_accounts,
+        ctx.accounts.lolly_burn_state.key(),
+        signer_seeds,
+        data,
+    )?;
+
+    ctx.accounts.lolly_burn_state_usdc_vault.reload()?;
+    ctx.accounts.lolly_burn_state_lolly_vault.reload()?;
+    let (usdc_in, lolly_out) = ctx.accounts.lolly_burn_state.record_swap(
+        usdc_before,
+        ctx.accounts.lolly_burn_state_usdc_vault.amount,
+        lolly_before,
+        ctx.accounts.lolly_burn_state_lolly_vault.amount,
+        min_lolly_out,
+    )?;
+    if usdc_in > max_usdc_in {
+        return err!(LollysLottoError::SwapAmountExceeded);
+    }
+
+    ctx.accounts
+        .lolly_burn_state
+        .record_burn(lolly_out, Clock::get()?.epoch)?;
+    token::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lolly_mint.to_account_info(),
+                from: ctx.accounts.lolly_burn_state_lolly_vault.to_account_info(),
+                authority: ctx.accounts.lolly_burn_state.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        lolly_out,
+    )?;
+
+    let round_burn = ctx
+        .accounts
+        .lolly_burn_state
+        .record_round_burn(round, 0, usdc_in, lolly_out)?;
+
+    let block_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::SwapAndBurn(SwapAndBurnEvent {
+            authority,
+            lolly_burn_state: ctx.accounts.lolly_burn_state.key(),
+            round,
+            usdc_in,
+            lolly_out,
+            lolly_burnt: lolly_out,
+            total_lolly_burnt: ctx.accounts.lolly_burn_state.total_lolly_burnt,
+            round_burn,
+        }),
+    )?;
+
+    Ok(())
+}
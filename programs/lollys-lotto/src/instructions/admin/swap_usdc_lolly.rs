@@ -1,7 +1,4 @@
-use anchor_lang::{
-    prelude::*,
-    solana_program::{instruction::Instruction, program::invoke_signed},
-};
+use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 
 use crate::{
@@ -12,21 +9,9 @@ use crate::{
         lolly_burn_state::LollyBurnState, EventEmitter, LollysLottoProgramEventData,
         SwapUsdcLollyEvent,
     },
+    utils::invoke_swap,
 };
 
-mod jupiter {
-    use anchor_lang::declare_id;
-    declare_id!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
-}
-
-#[derive(Clone)]
-pub struct Jupiter;
-impl anchor_lang::Id for Jupiter {
-    fn id() -> Pubkey {
-        jupiter::id()
-    }
-}
-
 #[derive(Accounts)]
 pub struct SwapUsdcLolly<'info> {
     /// CHECK: Authority of the LollyBurnState instance
@@ -41,13 +26,9 @@ pub struct SwapUsdcLolly<'info> {
         ],
         bump = lolly_burn_state.bump)]
     pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
-    /// token_in_mint to be swapped using jupiter
-    /// Mint address of the USDC token
-    // #[account(address = USDC_MINT_DEVNET)]
-    // pub usdc_mint: Account<'info, Mint>,
 
     /// associated_token_account of token_in_mint
-    /// USDC token account which is used to swap USDC to LOLLY using jupiter owned by LollyBurnState PDA
+    /// USDC token account which is swapped to LOLLY, owned by LollyBurnState PDA
     #[account(
         mut,
         associated_token::mint = USDC_MINT_DEVNET,
@@ -55,13 +36,8 @@ pub struct SwapUsdcLolly<'info> {
     )]
     pub lolly_burn_state_usdc_vault: Box<Account<'info, TokenAccount>>,
 
-    /// token_out_mint to be swapped using jupiter
-    /// Mint address of the LOLLY token
-    // #[account(address = LOLLY_MINT @LollysLottoError::OnlySwapToLOLLYAllowed)]
-    // pub lolly_mint: Account<'info, Mint>,
-
     /// associated_token_account of token_out_mint
-    /// LOLLY token account to store LOLLY swapped from USDC of lolly_burn_state_usdc_vault using jupiter owned by LollyBurnState PDA
+    /// LOLLY token account to store LOLLY swapped from USDC of lolly_burn_state_usdc_vault, owned by LollyBurnState PDA
     #[account(
         mut,
         associated_token::mint = LOLLY_MINT,
@@ -72,92 +48,62 @@ pub struct SwapUsdcLolly<'info> {
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 
-    pub jupiter_program: Program<'info, Jupiter>,
+    /// CHECK: Any program on the LollyBurnState allowlist, e.g. Jupiter
+    #[account(
+        executable,
+        constraint = lolly_burn_state.is_swap_program_allowed(swap_program.key()) @LollysLottoError::SwapProgramNotAllowed,
+    )]
+    pub swap_program: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+/// Swaps USDC to LOLLY through an allowlisted swap program.
+/// remaining_accounts and `data` are the route of the swap program, built off-chain.
 pub fn swap_usdc_lolly<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, SwapUsdcLolly<'info>>,
+    min_lolly_out: u64,
     data: Vec<u8>,
 ) -> Result<()> {
-    msg!("Swap on Jupiter");
-
-    // 3rd account in remaining_accounts is the source token account/USDC account
-    let jupiter_source_token_account: Account<TokenAccount> =
-        Account::try_from_unchecked(&ctx.remaining_accounts[3])?;
-
-    if jupiter_source_token_account.mint != USDC_MINT_DEVNET {
-        return err!(LollysLottoError::OnlySwapFromUSDCAllowed);
-    }
-    if (jupiter_source_token_account.mint != ctx.accounts.lolly_burn_state_usdc_vault.mint)
-        && (jupiter_source_token_account.key() != ctx.accounts.lolly_burn_state_usdc_vault.key())
-    {
-        return err!(LollysLottoError::JupiterIxSourceTokenAccountMismatch);
-    }
-
-    // 6th account in remaining_accounts is the destination token account/LOLLY account
-    let jupiter_destination_token_account: Account<TokenAccount> =
-        Account::try_from_unchecked(&ctx.remaining_accounts[6])?;
-
-    if jupiter_destination_token_account.mint != LOLLY_MINT {
-        return err!(LollysLottoError::OnlySwapToLOLLYAllowed);
-    }
-
-    if (jupiter_destination_token_account.mint != ctx.accounts.lolly_burn_state_lolly_vault.mint)
-        && (jupiter_destination_token_account.key()
-            != ctx.accounts.lolly_burn_state_lolly_vault.key())
-    {
-        return err!(LollysLottoError::JupiterIxDestinationTokenAccountMismatch);
-    }
-
-    if (jupiter_source_token_account.owner != ctx.accounts.lolly_burn_state.key())
-        && (jupiter_destination_token_account.owner != ctx.accounts.lolly_burn_state.key())
-    {
-        return err!(LollysLottoError::TokenAccountAuthorityMismatch);
-    }
-
-    let accounts: Vec<AccountMeta> = ctx
-        .remaining_accounts
-        .iter()
-        .map(|acc| AccountMeta {
-            pubkey: *acc.key,
-            is_signer: if *acc.key == ctx.accounts.lolly_burn_state.key() {
-                true
-            } else {
-                acc.is_signer
-            },
-            is_writable: acc.is_writable,
-        })
-        .collect();
-
-    let accounts_infos: Vec<AccountInfo> = ctx
-        .remaining_accounts
-        .iter()
-        .map(|acc| AccountInfo { ..acc.clone() })
-        .collect();
-
-    // lolly_burn_state PDA is signing this jupiter swap transaction
-    let _ = invoke_signed(
-        &Instruction {
-            program_id: *ctx.accounts.jupiter_program.key,
-            accounts,
-            data,
-        },
-        &accounts_infos,
+    let usdc_before = ctx.accounts.lolly_burn_state_usdc_vault.amount;
+    let lolly_before = ctx.accounts.lolly_burn_state_lolly_vault.amount;
+
+    // lolly_burn_state PDA is signing this swap
+    invoke_swap(
+        &ctx.accounts.swap_program,
+        ctx.remaining_accounts,
+        ctx.accounts.lolly_burn_state.key(),
         &[&[
             LollyBurnState::IDENT,
             ctx.accounts.lolly_burn_state.authority.as_ref(),
             &[ctx.accounts.lolly_burn_state.bump],
         ]],
-    );
+        data,
+    )?;
+
+    // The amounts are measured on the vaults rather than trusted from the swap program
+    ctx.accounts.lolly_burn_state_usdc_vault.reload()?;
+    ctx.accounts.lolly_burn_state_lolly_vault.reload()?;
+    let lolly_burn_state = &mut ctx.accounts.lolly_burn_state;
+    let (usdc_spent, lolly_acquired) = lolly_burn_state.record_swap(
+        usdc_before,
+        ctx.accounts.lolly_burn_state_usdc_vault.amount,
+        lolly_before,
+        ctx.accounts.lolly_burn_state_lolly_vault.amount,
+        min_lolly_out,
+    )?;
 
     let block_time = Clock::get()?.unix_timestamp;
     ctx.accounts.event_emitter.emit_new_event(
         Some(block_time),
         LollysLottoProgramEventData::SwapUsdcLolly(SwapUsdcLollyEvent {
             authority: *ctx.accounts.authority.key,
-            lolly_burn_state: *ctx.accounts.lolly_burn_state.to_account_info().key,
+            lolly_burn_state: *lolly_burn_state.to_account_info().key,
+            usdc_spent,
+            lolly_acquired,
+            min_lolly_out,
+            total_usdc_swapped: lolly_burn_state.total_usdc_swapped,
+            total_lolly_acquired: lolly_burn_state.total_lolly_acquired,
         }),
     )?;
 
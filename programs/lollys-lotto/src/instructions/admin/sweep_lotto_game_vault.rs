@@ -0,0 +1,160 @@
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLotto, LollysLottoProgramEventData, LottoGame, LottoGameState,
+        LottoGameVault, SweepLottoGameVaultEvent,
+    },
+    utils::{harvest_withheld_transfer_fees, transfer_quote_tokens},
+};
+pub use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct SweepLottoGameVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    /// Kept so the tickets of the round can still be closed, `close_lotto_game` closes it afterwards.
+    #[account(
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.state == LottoGameState::Finished @LollysLottoError::GameNotClosed,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    /// CHECK: Just a PDA signer
+    #[account(
+        seeds = [
+            LottoGameVault::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = lotto_game.load()?.lotto_game_vault_bump,
+    )]
+    pub lotto_game_vault_signer: UncheckedAccount<'info>,
+
+    /// Receives the transfer fees withheld in the vault before it is closed
+    #[account(mut)]
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = lotto_game_vault_signer,
+        associated_token::token_program = token_program,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account or the vault of `next_lotto_game`, depending on `lollys_lotto.sweep_destination`.
+    #[account(
+        mut,
+        constraint = destination_vault.mint == lotto_game.load()?.lotto_game_mint @LollysLottoError::InvalidSweepDestination,
+    )]
+    pub destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The open round receiving the funds when sweeping to the next round.
+    #[account(mut)]
+    pub next_lotto_game: Option<AccountLoader<'info, LottoGame>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sends whatever is left in the vault of a finished LottoGame to the configured sweep destination,
+/// then closes the vault. The DrawRecord of the round is kept as its audit record.
+pub fn sweep_lotto_game_vault(ctx: Context<SweepLottoGameVault>) -> Result<()> {
+    let lotto_game_pubkey = ctx.accounts.lotto_game.key();
+    let lotto_game = ctx.accounts.lotto_game.load()?;
+    let lotto_game_vault = &ctx.accounts.lotto_game_vault;
+    let sweep_destination = ctx.accounts.lollys_lotto.sweep_destination;
+
+    let block_time = Clock::get()?.unix_timestamp;
+    if !lotto_game.check_claim_deadline_passed(block_time) {
+        return Err(LollysLottoError::ClaimPeriodNotOver.into());
+    }
+
+    // Everything still reserved was never claimed, the rest is rounding dust
+    let swept_amount = lotto_game_vault.amount;
+    let unclaimed = lotto_game.liabilities;
+    let dust = swept_amount.saturating_sub(unclaimed.total()?);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        LottoGameVault::IDENT,
+        lotto_game_pubkey.as_ref(),
+        &[lotto_game.lotto_game_vault_bump],
+    ]];
+
+    // Only what the destination receives is credited to the next round
+    let amount_received = if swept_amount > 0 {
+        transfer_quote_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.lotto_game_mint,
+            lotto_game_vault,
+            &mut ctx.accounts.destination_vault,
+            ctx.accounts.lotto_game_vault_signer.to_account_info(),
+            signer_seeds,
+            swept_amount,
+        )?
+    } else {
+        0
+    };
+    ctx.accounts.lollys_lotto.credit_sweep_destination(
+        &ctx.accounts.destination_vault,
+        ctx.accounts.next_lotto_game.as_ref(),
+        amount_received,
+    )?;
+
+    harvest_withheld_transfer_fees(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        lotto_game_vault,
+    )?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: lotto_game_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.lotto_game_vault_signer.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    let round = lotto_game.round;
+    let destination_vault = ctx.accounts.destination_vault.key();
+    drop(lotto_game);
+
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::SweepLottoGameVault(SweepLottoGameVaultEvent {
+            lotto_game: lotto_game_pubkey,
+            round,
+            sweep_destination,
+            destination_vault,
+            swept_amount,
+            unclaimed,
+            dust,
+        }),
+    )?;
+
+    Ok(())
+}
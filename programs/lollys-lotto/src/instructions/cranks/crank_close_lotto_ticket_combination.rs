@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CloseLottoTicketCombinationEvent, EventEmitter, LollysLottoProgramEventData, LottoGame,
+        LottoGameState, LottoTicketCombination, LottoTicketNumbers,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64, numbers: LottoTicketNumbers)]
+pub struct CrankCloseLottoTicketCombination<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Finished @LollysLottoError::InvalidLottoGameState,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = lotto_game,
+        has_one = payer @LollysLottoError::InvalidCombinationPayer,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_ticket_combination.bump,
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// CHECK: Paid the rent of `lotto_ticket_combination` and gets it back
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+/// Closes a LottoTicketCombination left open once its round is finished, the ones no ticket won
+/// with or whose winners were never settled, and refunds its rent to the payer.
+/// Anyone can crank it, as nothing reads a combination after its round is finished.
+pub fn crank_close_lotto_ticket_combination(
+    ctx: Context<CrankCloseLottoTicketCombination>,
+    round: u64,
+    numbers: LottoTicketNumbers,
+) -> Result<()> {
+    let block_time = Clock::get()?.unix_timestamp;
+    let holders = ctx.accounts.lotto_ticket_combination.holders;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::CloseLottoTicketCombination(
+            CloseLottoTicketCombinationEvent {
+                round,
+                numbers,
+                lotto_game: ctx.accounts.lotto_game.key(),
+                lotto_ticket_combination: ctx.accounts.lotto_ticket_combination.key(),
+                payer: ctx.accounts.payer.key(),
+                holders,
+            },
+        ),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::USDC_MINT_DEVNET,
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CrankDistributeProtocolFeesEvent, EventEmitter, LollysLotto, LollysLottoProgramEventData,
+        LottoGame, LottoGameVault, StakePool, TransferRoundShareToTreasuryEvent,
+    },
+    utils::transfer_quote_tokens,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct CrankDistributeProtocolFees<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    /// CHECK: Just a PDA signer
+    #[account(
+        seeds = [
+            LottoGameVault::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lotto_game_vault_signer: UncheckedAccount<'info>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = lotto_game_vault_signer,
+        associated_token::token_program = token_program,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// USDC token account the stakers claim their share of protocol fees from, only needed for USDC rounds
+    #[account(mut)]
+    pub reward_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Treasury token account receiving the protocol fees of rounds in another mint, stakers only earn USDC
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn crank_distribute_protocol_fees(
+    ctx: Context<CrankDistributeProtocolFees>,
+    round: u64,
+) -> Result<()> {
+    let lotto_game = &mut ctx.accounts.lotto_game.load_mut()?;
+    let lotto_game_pubkey = ctx.accounts.lotto_game.key();
+    let lotto_game_vault_signer = &ctx.accounts.lotto_game_vault_signer;
+    let lotto_game_vault = &ctx.accounts.lotto_game_vault;
+
+    // Transfer the protocol fees from LottoGame vault to the StakePool reward vault
+    let protocol_fees_amount = lotto_game.final_protocol_fees_amount()?;
+
+    // CHECK: The vault must cover every outstanding liability before anything leaves it
+    lotto_game.check_solvency(lotto_game_vault.amount)?;
+    lotto_game
+        .liabilities
+        .release_protocol_fees(protocol_fees_amount)?;
+
+    let seeds = &[
+        LottoGameVault::IDENT,
+        lotto_game_pubkey.as_ref(),
+        &[lotto_game.lotto_game_vault_bump],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+    let block_time = Clock::get()?.unix_timestamp;
+
+    // Stakers are paid in USDC, the protocol fees of rounds in another mint go to the treasury
+    if lotto_game.lotto_game_mint != USDC_MINT_DEVNET {
+        let treasury_token_account = ctx
+            .accounts
+            .treasury_token_account
+            .as_mut()
+            .ok_or(LollysLottoError::InvalidTreasury)?;
+        ctx.accounts
+            .lollys_lotto
+            .check_treasury_token_account(treasury_token_account, lotto_game.lotto_game_mint)?;
+        transfer_quote_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.lotto_game_mint,
+            lotto_game_vault,
+            treasury_token_account,
+            lotto_game_vault_signer.to_account_info(),
+            signer_seeds,
+            protocol_fees_amount,
+        )?;
+        ctx.accounts.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::TransferRoundShareToTreasury(
+                TransferRoundShareToTreasuryEvent {
+                    round,
+                    lotto_game: lotto_game_pubkey,
+                    lotto_game_mint: lotto_game.lotto_game_mint,
+                    treasury_token_account: treasury_token_account.key(),
+                    protocol_fees_amount,
+                    buy_and_burn_amount: 0,
+                },
+            ),
+        )?;
+        return Ok(());
+    }
+
+    let reward_vault = ctx
+        .accounts
+        .reward_vault
+        .as_mut()
+        .ok_or(LollysLottoError::InvalidCrankAccounts)?;
+    if reward_vault.key() != StakePool::reward_vault(ctx.accounts.authority.key()) {
+        return Err(LollysLottoError::InvalidCrankAccounts.into());
+    }
+    transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        lotto_game_vault,
+        reward_vault,
+        lotto_game_vault_signer.to_account_info(),
+        signer_seeds,
+        protocol_fees_amount,
+    )?;
+
+    ctx.accounts
+        .stake_pool
+        .distribute_rewards(protocol_fees_amount)?;
+
+    // Emit event
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::CrankDistributeProtocolFees(
+            CrankDistributeProtocolFeesEvent {
+                round,
+                lotto_game: ctx.accounts.lotto_game.key(),
+                stake_pool: ctx.accounts.stake_pool.key(),
+                protocol_fees_amount,
+                total_staked: ctx.accounts.stake_pool.total_staked,
+                reward_per_share: ctx.accounts.stake_pool.reward_per_share,
+            },
+        ),
+    )?;
+
+    Ok(())
+}
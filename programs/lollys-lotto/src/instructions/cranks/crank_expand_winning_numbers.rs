@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        DrawRecord, EventEmitter, ExpandWinningNumbersEvent, LollysLottoProgramEventData,
+        LottoGame, LottoGameState,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct CrankExpandWinningNumbers<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Drawing @LollysLottoError::InvalidLottoGameState,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+    #[account(mut)]
+    pub event_emitter: Account<'info, EventEmitter>,
+}
+
+pub fn crank_expand_winning_numbers(
+    ctx: Context<CrankExpandWinningNumbers>,
+    round: u64,
+) -> Result<()> {
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+
+    let start_nonce = lotto_game.winning_numbers_nonce;
+    let (winning_numbers_added, duplicates_skipped) =
+        lotto_game.expand_winning_numbers(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE)?;
+
+    let block_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::ExpandWinningNumbers(ExpandWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round,
+            seed: lotto_game.winning_numbers_seed,
+            start_nonce,
+            end_nonce: lotto_game.winning_numbers_nonce,
+            winning_numbers_added,
+            duplicates_skipped,
+            winning_numbers_filled: lotto_game.winning_numbers_filled,
+        }),
+    )?;
+    lotto_game.complete_draw_if_filled(
+        ctx.accounts.lotto_game.key(),
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+    if lotto_game.check_all_winning_numbers_set() {
+        ctx.accounts
+            .draw_record
+            .record_mapped(Clock::get()?.slot, lotto_game.winning_numbers_nonce);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CrankFulfilSubscriptionEvent, EventEmitter, LollysLottoProgramEventData, LottoGame,
+        LottoGameState, LottoTicket, LottoTicketCombination, LottoTicketNumbers, PaymentAsset,
+        StakePool, StakePosition, TicketPurchase, TicketSubscription, UserMetadata,
+    },
+    utils::{transfer_fee_for_amount, transfer_quote_tokens},
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64, numbers: LottoTicketNumbers)]
+pub struct CrankFulfilSubscription<'info> {
+    /// Pays the rent of the ticket up front, reimbursed from the subscription
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = lotto_game_mint,
+        token::token_program = token_program,
+    )]
+    pub cranker_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserMetadata::IDENT,
+            ticket_subscription.user.as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    #[account(
+        mut,
+        has_one = user_metadata,
+        has_one = authority,
+        constraint = ticket_subscription.mint == lotto_game_mint.key() @LollysLottoError::SubscriptionMintMismatch,
+        seeds = [
+            TicketSubscription::IDENT,
+            user_metadata.key().as_ref(),
+            authority.key().as_ref(),
+            ticket_subscription.subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump = ticket_subscription.bump,
+    )]
+    pub ticket_subscription: Box<Account<'info, TicketSubscription>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = ticket_subscription,
+        associated_token::token_program = token_program,
+    )]
+    pub subscription_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + std::mem::size_of::<LottoTicket>(),
+        seeds = [
+            LottoTicket::IDENT,
+            lotto_game.key().as_ref(),
+            user_metadata.key().as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket: Box<Account<'info, LottoTicket>>,
+
+    /// Counts the tickets bought with these numbers, winners sharing them split the prize
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + LottoTicketCombination::SIZE,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// StakePool of the LottoGame authority, only needed for a staker discount
+    #[account(
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// StakePosition of the subscribed user in `stake_pool`, only needed for a staker discount
+    pub stake_position: Option<Box<Account<'info, StakePosition>>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys the ticket of a subscription for the current `Open` round from its escrow.
+/// Permissionless, the cranker earns the `cranker_fee` of the subscription.
+pub fn crank_fulfil_subscription(
+    ctx: Context<CrankFulfilSubscription>,
+    round: u64,
+    numbers: LottoTicketNumbers,
+) -> Result<()> {
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    let ticket_subscription = &mut ctx.accounts.ticket_subscription;
+    let ticket_subscription_pubkey = ticket_subscription.key();
+
+    if ticket_subscription.rounds_remaining == 0 {
+        return Err(LollysLottoError::SubscriptionExhausted.into());
+    }
+    if round < ticket_subscription.next_round {
+        return Err(LollysLottoError::SubscriptionAlreadyFulfilled.into());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    if lotto_game.check_sales_window_over(current_time) {
+        return Err(LollysLottoError::LottoGameSalesClosed.into());
+    }
+    let discount = StakePool::staker_discount(
+        ctx.accounts.stake_pool.as_deref(),
+        ctx.accounts.stake_position.as_deref(),
+        ticket_subscription.user,
+        lotto_game.ticket_price,
+    )?;
+    let max_transfer_fee = transfer_fee_for_amount(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        lotto_game.ticket_price,
+    )?;
+    let (discount, ticket_price) = lotto_game.discounted_ticket_price(discount, max_transfer_fee);
+    if ticket_price > ticket_subscription.max_ticket_price {
+        return Err(LollysLottoError::TicketPriceAboveMax.into());
+    }
+    // CHECK: The cranker can't pick the numbers
+    if numbers
+        != ticket_subscription.numbers_for_round(
+            ticket_subscription_pubkey,
+            round,
+            lotto_game.max_numbers_in_ticket,
+        )
+    {
+        return Err(LollysLottoError::InvalidNumbersInTicket.into());
+    }
+
+    // Pay the ticket and the cranker from the escrow
+    let subscription_id = ticket_subscription.subscription_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        TicketSubscription::IDENT,
+        ticket_subscription.user_metadata.as_ref(),
+        ticket_subscription.authority.as_ref(),
+        subscription_id.as_ref(),
+        &[ticket_subscription.bump],
+    ]];
+    let amount_received = transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        &ctx.accounts.subscription_vault,
+        &mut ctx.accounts.lotto_game_vault,
+        ticket_subscription.to_account_info(),
+        signer_seeds,
+        ticket_price,
+    )?;
+    if ticket_subscription.cranker_fee > 0 {
+        transfer_quote_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.lotto_game_mint,
+            &ctx.accounts.subscription_vault,
+            &mut ctx.accounts.cranker_usdc_token_account,
+            ticket_subscription.to_account_info(),
+            signer_seeds,
+            ticket_subscription.cranker_fee,
+        )?;
+    }
+
+    // Reimburse the ticket rent from the lamports escrowed on the subscription
+    let ticket_rent = TicketSubscription::ticket_rent(&Rent::get()?);
+    **ticket_subscription
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= ticket_rent;
+    **ctx
+        .accounts
+        .cranker
+        .to_account_info()
+        .try_borrow_mut_lamports()? += ticket_rent;
+
+    lotto_game.issue_ticket(
+        ctx.accounts.lotto_game.key(),
+        TicketPurchase {
+            user: ticket_subscription.user,
+            payer: ctx.accounts.cranker.key(),
+            lotto_ticket_combination_bump: ctx.bumps.lotto_ticket_combination,
+            numbers,
+            discount,
+            transfer_fee: ticket_price - amount_received,
+            payment_asset: PaymentAsset::Usdc,
+            buy_date: current_time,
+        },
+        &mut ctx.accounts.lotto_ticket,
+        &mut ctx.accounts.lotto_ticket_combination,
+        &mut ctx.accounts.user_metadata,
+        &mut ctx.accounts.event_emitter,
+    )?;
+
+    ticket_subscription.rounds_remaining -= 1;
+    ticket_subscription.rounds_fulfilled += 1;
+    ticket_subscription.next_round = round + 1;
+
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(current_time),
+        LollysLottoProgramEventData::CrankFulfilSubscription(CrankFulfilSubscriptionEvent {
+            user: ticket_subscription.user,
+            ticket_subscription: ticket_subscription_pubkey,
+            cranker: ctx.accounts.cranker.key(),
+            lotto_game: ctx.accounts.lotto_game.key(),
+            lotto_ticket: ctx.accounts.lotto_ticket.key(),
+            round,
+            numbers,
+            ticket_price,
+            cranker_fee: ticket_subscription.cranker_fee,
+            rounds_remaining: ticket_subscription.rounds_remaining,
+        }),
+    )?;
+
+    Ok(())
+}
@@ -30,22 +30,29 @@ pub struct CrankLottoGameClosed<'info> {
 }
 
 pub fn crank_lotto_game_closed(ctx: Context<CrankLottoGameClosed>, round: u64) -> Result<()> {
-    // if clock is more than lotto_game.end_time, then close the game
+    // The game can only be closed once the sales window is over
     let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
     let current_time = Clock::get()?.unix_timestamp;
-    if current_time > lotto_game.end_date {
-        lotto_game.state = LottoGameState::Closed;
-        let block_time = Clock::get()?.unix_timestamp;
-        ctx.accounts.event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::CrankLottoGameClosed(CrankLottoGameClosedEvent {
-                lotto_game: ctx.accounts.lotto_game.key(),
-                round,
-                ticket_price: lotto_game.ticket_price,
-                game_duration: (lotto_game.end_date - lotto_game.start_date) as u64,
-            }),
-        )?;
+    if !lotto_game.check_sales_window_over(current_time) {
+        return Err(LollysLottoError::LottoGameIsStillOpen.into());
     }
 
+    let block_time = Clock::get()?.unix_timestamp;
+    lotto_game.transition_state(
+        ctx.accounts.lotto_game.key(),
+        LottoGameState::Closed,
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::CrankLottoGameClosed(CrankLottoGameClosedEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round,
+            ticket_price: lotto_game.ticket_price,
+            game_duration: (lotto_game.end_date - lotto_game.start_date) as u64,
+        }),
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLotto, LollysLottoProgramEventData, LottoGame, LottoGameState,
+        LottoGameVault, RefundMinJackpotEvent,
+    },
+    utils::transfer_quote_tokens,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct CrankLottoGameFinished<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = matches!(lotto_game.load()?.state, LottoGameState::Drawn | LottoGameState::Paying) @LollysLottoError::InvalidLottoGameState,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    /// CHECK: Just a PDA signer
+    #[account(
+        seeds = [
+            LottoGameVault::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = lotto_game.load()?.lotto_game_vault_bump,
+    )]
+    pub lotto_game_vault_signer: UncheckedAccount<'info>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account receiving the unused minimum jackpot, only needed when the round has one.
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub event_emitter: Account<'info, EventEmitter>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn crank_lotto_game_finished(ctx: Context<CrankLottoGameFinished>, _round: u64) -> Result<()> {
+    let lotto_game_pubkey = ctx.accounts.lotto_game.key();
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    let block_time = Clock::get()?.unix_timestamp;
+
+    // CHECK: Winners can be settled until the claim deadline, unless every winning numbers drawn
+    // is disbursed. Winning numbers no ticket holds never are, so in practice only a raffle
+    // finishes before its claim deadline, once its single winner is paid or it sold no ticket.
+    if !lotto_game.check_claim_deadline_passed(block_time)
+        && !lotto_game.check_all_winning_amounts_disbursed()
+    {
+        return Err(LollysLottoError::ClaimPeriodNotOver.into());
+    }
+    lotto_game.transition_state(
+        lotto_game_pubkey,
+        LottoGameState::Finished,
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+
+    // The minimum jackpot the round did not need goes back to the treasury
+    let refunded_amount = lotto_game.unused_min_jackpot();
+    if refunded_amount == 0 {
+        return Ok(());
+    }
+    let treasury_token_account = ctx
+        .accounts
+        .treasury_token_account
+        .as_mut()
+        .ok_or(LollysLottoError::InvalidTreasury)?;
+    ctx.accounts
+        .lollys_lotto
+        .check_treasury_token_account(treasury_token_account, lotto_game.lotto_game_mint)?;
+
+    lotto_game.liabilities.release_prize(0, refunded_amount)?;
+    transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        &ctx.accounts.lotto_game_vault,
+        treasury_token_account,
+        ctx.accounts.lotto_game_vault_signer.to_account_info(),
+        &[&[
+            LottoGameVault::IDENT,
+            lotto_game_pubkey.as_ref(),
+            &[lotto_game.lotto_game_vault_bump],
+        ]],
+        refunded_amount,
+    )?;
+
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::RefundMinJackpot(RefundMinJackpotEvent {
+            lotto_game: lotto_game_pubkey,
+            round: lotto_game.round,
+            treasury_token_account: treasury_token_account.key(),
+            min_jackpot: lotto_game.min_jackpot,
+            refunded_amount,
+        }),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+use solana_randomness_service::program::SolanaRandomnessService;
+use switchboard_solana::NativeMint;
+
+use crate::{
+    errors::LollysLottoError,
+    instructions::request_randomness,
+    pda_identifier::PDAIdentifier,
+    state::{
+        DrawRecord, EventEmitter, LollysLotto, LollysLottoProgramEventData, LottoGame,
+        LottoGameState, RandomnessProvider, RequestWinningNumbersEvent,
+        WinningNumbersRequestExpiredEvent,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct CrankRerequestWinningNumbers<'info> {
+    /// The Solana Randomness Service program.
+    pub randomness_service: Program<'info, SolanaRandomnessService>,
+
+    /// The account that will be created on-chain to hold the new randomness request.
+    /// CHECK: Created by the randomness service
+    #[account(
+        mut,
+        signer,
+        owner = system_program.key(),
+        constraint = randomness_request.data_len() == 0 && randomness_request.lamports() == 0,
+    )]
+    pub randomness_request: AccountInfo<'info>,
+
+    /// The TokenAccount that will store the funds for the new randomness request.
+    /// CHECK: Created by the randomness service
+    #[account(
+        mut,
+        owner = system_program.key(),
+        constraint = randomness_escrow.data_len() == 0 && randomness_escrow.lamports() == 0,
+    )]
+    pub randomness_escrow: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"STATE"],
+        bump = randomness_state.bump,
+        seeds::program = randomness_service.key(),
+    )]
+    pub randomness_state: Box<Account<'info, solana_randomness_service::State>>,
+
+    #[account(address = NativeMint::ID)]
+    pub randomness_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = lollys_lotto.randomness_provider == RandomnessProvider::SolanaRandomnessService @LollysLottoError::RandomnessProviderMismatch,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Drawing @LollysLottoError::InvalidLottoGameState,
+        constraint = !lotto_game.load()?.check_winning_numbers_seed_set() @LollysLottoError::WinningNumbersSeedAlreadySet,
+        constraint = lotto_game.load()?.check_randomness_requested() @LollysLottoError::InvalidRandomnessRequest,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn crank_rerequest_winning_numbers(
+    ctx: Context<CrankRerequestWinningNumbers>,
+    round: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    {
+        let lotto_game = ctx.accounts.lotto_game.load()?;
+        if !lotto_game.check_randomness_request_expired(clock.unix_timestamp) {
+            return Err(LollysLottoError::RandomnessRequestNotExpired.into());
+        }
+    }
+
+    request_randomness(
+        CpiContext::new(
+            ctx.accounts.randomness_service.to_account_info(),
+            solana_randomness_service::cpi::accounts::SimpleRandomnessV1Request {
+                request: ctx.accounts.randomness_request.to_account_info(),
+                escrow: ctx.accounts.randomness_escrow.to_account_info(),
+                state: ctx.accounts.randomness_state.to_account_info(),
+                mint: ctx.accounts.randomness_mint.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            },
+        ),
+        ctx.accounts.authority.key(),
+        ctx.accounts.lotto_game.key(),
+        ctx.accounts.draw_record.key(),
+        ctx.accounts.event_emitter.key(),
+    )?;
+
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    let block_time = clock.unix_timestamp;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::WinningNumbersRequestExpired(
+            WinningNumbersRequestExpiredEvent {
+                lotto_game: ctx.accounts.lotto_game.key(),
+                round,
+                expired_request: lotto_game.randomness_request,
+                requested_at: lotto_game.randomness_requested_at,
+                expired_at: block_time,
+            },
+        ),
+    )?;
+
+    lotto_game.set_randomness_request(
+        ctx.accounts.randomness_request.key(),
+        clock.slot,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.draw_record.record_request(
+        RandomnessProvider::SolanaRandomnessService,
+        ctx.accounts.randomness_request.key(),
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::RequestWinningNumbers(RequestWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round,
+            randomness_request: lotto_game.randomness_request,
+            requested_at: lotto_game.randomness_requested_at,
+        }),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use num_traits::ToPrimitive;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CrankSettleWinningTicketEvent, EventEmitter, LollysLottoProgramEventData, LottoGame,
+        LottoGameVault, LottoGameVersion, LottoTicket, LottoTicketCombination, LottoTicketNumbers,
+        UserMetadata,
+    },
+    utils::transfer_quote_tokens,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64, winning_numbers: LottoTicketNumbers)]
+pub struct CrankSettleWinningTicket<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    /// CHECK: Just a PDA signer
+    #[account(
+        seeds = [
+            LottoGameVault::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lotto_game_vault_signer: UncheckedAccount<'info>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = lotto_game_vault_signer,
+        associated_token::token_program = token_program,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: User account
+    #[account()]
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    /// Rewards vault of the user in `lotto_game_mint`
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = user_metadata,
+        associated_token::token_program = token_program,
+    )]
+    pub user_rewards_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        has_one = user,
+        constraint = lotto_ticket.round == lotto_game.load()?.round @LollysLottoError::InvalidRound,
+        constraint = lotto_ticket.numbers == winning_numbers @LollysLottoError::InvalidWinningTicket,
+        seeds = [
+            LottoTicket::IDENT,
+            lotto_game.key().as_ref(),
+            user_metadata.key().as_ref(),
+            lotto_ticket.numbers.number1.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number2.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number3.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number4.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number5.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket: Box<Account<'info, LottoTicket>>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            lotto_ticket.numbers.number1.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number2.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number3.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number4.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.number5.to_le_bytes().as_ref(),
+            lotto_ticket.numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_ticket_combination.bump,
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// CHECK: Gets the rent of `lotto_ticket_combination` back once its last holder is settled
+    #[account(
+        mut,
+        address = lotto_ticket_combination.payer @LollysLottoError::InvalidCombinationPayer,
+    )]
+    pub lotto_ticket_combination_payer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Declares `lotto_ticket` a winner and transfers its prize to the user rewards vault
+/// in a single instruction. Tickets with the same winning numbers split the prize evenly,
+/// their LottoTicketCombination is closed once the last of them is settled.
+/// Anyone can crank it, the prize only ever goes to the rewards vault of the ticket owner.
+pub fn crank_settle_winning_ticket(
+    ctx: Context<CrankSettleWinningTicket>,
+    _round: u64,
+    winning_numbers: LottoTicketNumbers,
+) -> Result<()> {
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    let lotto_game_vault = &ctx.accounts.lotto_game_vault;
+    let lotto_game_vault_signer = &ctx.accounts.lotto_game_vault_signer;
+    let user_metadata = &mut ctx.accounts.user_metadata;
+    let lotto_ticket = &mut ctx.accounts.lotto_ticket;
+    let lotto_ticket_pubkey = lotto_ticket.key();
+    let lotto_ticket_combination = &mut ctx.accounts.lotto_ticket_combination;
+    let lotto_ticket_combination_payer = &ctx.accounts.lotto_ticket_combination_payer;
+    let event_emitter = &mut ctx.accounts.event_emitter;
+
+    // CHECK: Check if the ticket has already been settled
+    if lotto_ticket.is_winner != 0 {
+        return Err(LollysLottoError::AlreadyDeclaredWinner.into());
+    }
+
+    let block_time = Clock::get()?.unix_timestamp;
+    if !lotto_game.check_game_state_closed() || block_time < lotto_game.end_date {
+        return Err(LollysLottoError::LottoGameIsStillOpen.into());
+    }
+
+    // CHECK: Prizes of this round have expired
+    if lotto_game.check_claim_deadline_passed(block_time) {
+        return Err(LollysLottoError::ClaimDeadlinePassed.into());
+    }
+
+    // CHECK: Winners can only be settled once the draw is complete
+    lotto_game.start_paying_if_drawn(ctx.accounts.lotto_game.key(), event_emitter, block_time)?;
+
+    // The tier and index of the winning numbers are derived from the ticket, never supplied by the client
+    let (winning_tier, winning_number_index) =
+        lotto_game.get_tier_and_index_by_lotto_ticket(lotto_ticket)?;
+
+    let amount_to_be_disbursed = lotto_game.get_amount_to_be_disbursed(
+        winning_tier,
+        winning_number_index,
+        lotto_ticket.numbers,
+        lotto_game_vault.amount,
+    )?;
+    let amount_to_be_disbursed = amount_to_be_disbursed
+        .to_u64()
+        .ok_or(LollysLottoError::MathError)?;
+
+    // The winner of a raffle is drawn by ticket number, tickets sharing its numbers didn't win
+    let (prize, number_of_tickets_with_duplicate_numbers) =
+        if lotto_game.version == LottoGameVersion::Raffle {
+            lotto_game.mark_winning_amount_disbursed(winning_tier, winning_number_index)?;
+            (amount_to_be_disbursed, 0)
+        } else {
+            // The winning numbers are disbursed once every ticket holding them is settled
+            let (prize, all_holders_settled) =
+                lotto_ticket_combination.settle_holder(amount_to_be_disbursed)?;
+            let number_of_tickets_with_duplicate_numbers = lotto_ticket_combination.holders - 1;
+            if all_holders_settled {
+                lotto_game.mark_winning_amount_disbursed(winning_tier, winning_number_index)?;
+                lotto_ticket_combination.close(lotto_ticket_combination_payer.to_account_info())?;
+            }
+            (prize, number_of_tickets_with_duplicate_numbers)
+        };
+
+    lotto_ticket.is_winner = 1;
+    lotto_ticket.is_duplicated = number_of_tickets_with_duplicate_numbers;
+    lotto_ticket.prize = prize;
+
+    // CHECK: The vault must cover every outstanding liability before anything leaves it
+    lotto_game.check_solvency(lotto_game_vault.amount)?;
+    lotto_game
+        .liabilities
+        .release_prize(winning_tier, lotto_ticket.prize)?;
+
+    // Transfer the winning amount to the user_rewards_vault, the user is credited what it receives
+    let amount_received = transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        lotto_game_vault,
+        &mut ctx.accounts.user_rewards_vault,
+        lotto_game_vault_signer.to_account_info(),
+        &[&[
+            LottoGameVault::IDENT,
+            ctx.accounts.lotto_game.key().as_ref(),
+            &[lotto_game.lotto_game_vault_bump],
+        ]],
+        lotto_ticket.prize,
+    )?;
+    user_metadata.credit_winning_amount(amount_received, lotto_game.claim_deadline);
+
+    event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::CrankSettleWinningTicket(CrankSettleWinningTicketEvent {
+            round: lotto_game.round,
+            winning_numbers,
+            winning_tier,
+            winning_number_index: winning_number_index as u64,
+            number_of_tickets_with_duplicate_numbers,
+            lotto_game: ctx.accounts.lotto_game.key(),
+            user: *ctx.accounts.user.key,
+            lotto_ticket: lotto_ticket_pubkey,
+            winning_amount: lotto_ticket.prize,
+            claim_deadline: lotto_game.claim_deadline,
+        }),
+    )?;
+
+    Ok(())
+}
@@ -1,14 +1,15 @@
 pub use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::USDC_MINT_DEVNET,
     errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
     state::{
-        CrankTransferToBuyAndBurnVaultEvent, EventEmitter, LollyBurnState,
-        LollysLottoProgramEventData, LottoGame, LottoGameVault,
+        CrankTransferToBuyAndBurnVaultEvent, EventEmitter, LollyBurnState, LollysLotto,
+        LollysLottoProgramEventData, LottoGame, LottoGameVault, TransferRoundShareToTreasuryEvent,
     },
+    utils::transfer_quote_tokens,
 };
 
 #[derive(Accounts)]
@@ -17,10 +18,21 @@ pub struct CrankTransferToBuyAndBurnVault<'info> {
     /// CHECK: Authority of the LottoGame instance
     pub authority: Signer<'info>,
 
+    #[account(
+        has_one = authority,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
     #[account(
         mut,
         has_one = authority,
         has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
         constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
         seeds = [
             LottoGame::IDENT,
@@ -41,12 +53,15 @@ pub struct CrankTransferToBuyAndBurnVault<'info> {
     )]
     pub lotto_game_vault_signer: UncheckedAccount<'info>,
 
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         mut,
-        associated_token::mint = USDC_MINT_DEVNET,
+        associated_token::mint = lotto_game_mint,
         associated_token::authority = lotto_game_vault_signer,
+        associated_token::token_program = token_program,
     )]
-    pub lotto_game_vault: Box<Account<'info, TokenAccount>>,
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
@@ -57,34 +72,39 @@ pub struct CrankTransferToBuyAndBurnVault<'info> {
         ],
         bump = lolly_burn_state.bump)]
     pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
-    /// LOLLY token account to burn tokens, owned by LollyBurnState PDA
-    #[account(
-        mut,
-        constraint = lotto_game.load()?.lotto_game_mint == USDC_MINT_DEVNET @LollysLottoError::OnlySwapFromUSDCAllowed,
-        associated_token::mint = USDC_MINT_DEVNET,
-        associated_token::authority = lolly_burn_state,
-    )]
-    pub lolly_burn_state_usdc_vault: Box<Account<'info, TokenAccount>>,
+    /// USDC token account the buy and burn swaps from, owned by LollyBurnState PDA. Only needed for USDC rounds
+    #[account(mut)]
+    pub lolly_burn_state_usdc_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Treasury token account receiving the buy and burn share of rounds in another mint,
+    /// `swap_and_burn` only swaps from USDC
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn crank_transfer_to_buy_and_burn_vault(
     ctx: Context<CrankTransferToBuyAndBurnVault>,
     round: u64,
 ) -> Result<()> {
-    let lotto_game = &mut ctx.accounts.lotto_game.load()?;
+    let lotto_game = &mut ctx.accounts.lotto_game.load_mut()?;
     let lotto_game_pubkey = ctx.accounts.lotto_game.key();
     let lotto_game_vault_signer = &ctx.accounts.lotto_game_vault_signer;
-    let lolly_burn_state_usdc_vault = &ctx.accounts.lolly_burn_state_usdc_vault;
     let lotto_game_vault = &ctx.accounts.lotto_game_vault;
 
     // Transfer USDC from LottoGame vault to LollyBurnState USDC vault
     let buy_and_burn_amount = lotto_game.final_buy_and_burn_amount()?;
 
+    // CHECK: The vault must cover every outstanding liability before anything leaves it
+    lotto_game.check_solvency(lotto_game_vault.amount)?;
+    lotto_game
+        .liabilities
+        .release_buy_and_burn(buy_and_burn_amount)?;
+
     let seeds = &[
         LottoGameVault::IDENT,
         lotto_game_pubkey.as_ref(),
@@ -92,22 +112,68 @@ pub fn crank_transfer_to_buy_and_burn_vault(
     ];
 
     let signer_seeds = &[&seeds[..]];
+    let block_time = Clock::get()?.unix_timestamp;
 
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: lotto_game_vault.to_account_info(),
-                to: lolly_burn_state_usdc_vault.to_account_info(),
-                authority: lotto_game_vault_signer.to_account_info(),
-            },
+    // LOLLY is only bought with USDC, the buy and burn share of rounds in another mint goes to the treasury
+    if lotto_game.lotto_game_mint != USDC_MINT_DEVNET {
+        let treasury_token_account = ctx
+            .accounts
+            .treasury_token_account
+            .as_mut()
+            .ok_or(LollysLottoError::InvalidTreasury)?;
+        ctx.accounts
+            .lollys_lotto
+            .check_treasury_token_account(treasury_token_account, lotto_game.lotto_game_mint)?;
+        transfer_quote_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.lotto_game_mint,
+            lotto_game_vault,
+            treasury_token_account,
+            lotto_game_vault_signer.to_account_info(),
             signer_seeds,
-        ),
+            buy_and_burn_amount,
+        )?;
+        ctx.accounts.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::TransferRoundShareToTreasury(
+                TransferRoundShareToTreasuryEvent {
+                    round,
+                    lotto_game: lotto_game_pubkey,
+                    lotto_game_mint: lotto_game.lotto_game_mint,
+                    treasury_token_account: treasury_token_account.key(),
+                    protocol_fees_amount: 0,
+                    buy_and_burn_amount,
+                },
+            ),
+        )?;
+        return Ok(());
+    }
+
+    let lolly_burn_state_usdc_vault = ctx
+        .accounts
+        .lolly_burn_state_usdc_vault
+        .as_mut()
+        .ok_or(LollysLottoError::InvalidCrankAccounts)?;
+    if lolly_burn_state_usdc_vault.key() != LollyBurnState::usdc_vault(ctx.accounts.authority.key())
+    {
+        return Err(LollysLottoError::InvalidCrankAccounts.into());
+    }
+    transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        lotto_game_vault,
+        lolly_burn_state_usdc_vault,
+        lotto_game_vault_signer.to_account_info(),
+        signer_seeds,
         buy_and_burn_amount,
     )?;
 
+    let round_burn =
+        ctx.accounts
+            .lolly_burn_state
+            .record_round_burn(round, buy_and_burn_amount, 0, 0)?;
+
     // Emit event
-    let block_time = Clock::get()?.unix_timestamp;
     ctx.accounts.event_emitter.emit_new_event(
         Some(block_time),
         LollysLottoProgramEventData::CrankTransferToBuyAndBurnVault(
@@ -115,9 +181,10 @@ pub fn crank_transfer_to_buy_and_burn_vault(
                 round,
                 lotto_game: ctx.accounts.lotto_game.key(),
                 lolly_burn_state: ctx.accounts.lolly_burn_state.key(),
-                lolly_burn_state_usdc_vault: ctx.accounts.lolly_burn_state_usdc_vault.key(),
+                lolly_burn_state_usdc_vault: lolly_burn_state_usdc_vault.key(),
                 lotto_game_vault: ctx.accounts.lotto_game_vault.key(),
                 buy_and_burn_amount,
+                round_burn,
             },
         ),
     )?;
@@ -1,9 +1,21 @@
+pub mod crank_close_lotto_ticket_combination;
+pub mod crank_distribute_protocol_fees;
+pub mod crank_expand_winning_numbers;
+pub mod crank_fulfil_subscription;
 pub mod crank_lotto_game_closed;
-pub mod crank_lotto_game_winners;
+pub mod crank_lotto_game_finished;
+pub mod crank_rerequest_winning_numbers;
+pub mod crank_settle_winning_ticket;
 pub mod crank_transfer_to_buy_and_burn_vault;
-pub mod crank_transfer_winning_amount_to_user_rewards_vault;
+pub mod verify_lotto_game_solvency;
 
+pub use crank_close_lotto_ticket_combination::*;
+pub use crank_distribute_protocol_fees::*;
+pub use crank_expand_winning_numbers::*;
+pub use crank_fulfil_subscription::*;
 pub use crank_lotto_game_closed::*;
-pub use crank_lotto_game_winners::*;
+pub use crank_lotto_game_finished::*;
+pub use crank_rerequest_winning_numbers::*;
+pub use crank_settle_winning_ticket::*;
 pub use crank_transfer_to_buy_and_burn_vault::*;
-pub use crank_transfer_winning_amount_to_user_rewards_vault::*;
+pub use verify_lotto_game_solvency::*;
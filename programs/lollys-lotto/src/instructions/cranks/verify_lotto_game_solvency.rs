@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLottoProgramEventData, LottoGame, LottoGameSolvencyEvent,
+        LottoGameVault,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct VerifyLottoGameSolvency<'info> {
+    #[account(
+        has_one = lotto_game_vault,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        seeds = [
+            LottoGame::IDENT,
+            lotto_game.load()?.authority.as_ref(),
+            round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    /// CHECK: Just a PDA signer
+    #[account(
+        seeds = [
+            LottoGameVault::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lotto_game_vault_signer: UncheckedAccount<'info>,
+
+    /// Held by the SPL Token or the Token-2022 program, depending on the LottoGame mint
+    #[account(
+        token::authority = lotto_game_vault_signer,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+/// Compares the LottoGame vault balance with the outstanding liabilities of the round and
+/// reports any shortfall in an event. Never fails on a shortfall, so anyone can monitor the vault.
+pub fn verify_lotto_game_solvency(ctx: Context<VerifyLottoGameSolvency>, round: u64) -> Result<()> {
+    let lotto_game = ctx.accounts.lotto_game.load()?;
+    let vault_amount = ctx.accounts.lotto_game_vault.amount;
+    let total_liabilities = lotto_game.liabilities.total()?;
+
+    let block_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::LottoGameSolvency(LottoGameSolvencyEvent {
+            round,
+            lotto_game: ctx.accounts.lotto_game.key(),
+            lotto_game_vault: ctx.accounts.lotto_game_vault.key(),
+            vault_amount,
+            liabilities: lotto_game.liabilities,
+            total_liabilities,
+            shortfall: total_liabilities.saturating_sub(vault_amount),
+        }),
+    )?;
+
+    Ok(())
+}
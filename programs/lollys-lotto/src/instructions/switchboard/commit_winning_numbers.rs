@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::{
+    constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CommitWinningNumbersEvent, DrawRecord, EventEmitter, LollysLotto,
+        LollysLottoProgramEventData, LottoGame, LottoGameState, RandomnessProvider,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CommitWinningNumbers<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = lollys_lotto.randomness_provider == RandomnessProvider::SwitchboardOnDemand @LollysLottoError::RandomnessProviderMismatch,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        // A commit is final: the committed randomness can be revealed by anyone, so the
+        // authority can't read it off-chain and reroll the draw with a new commit.
+        constraint = !lotto_game.load()?.check_randomness_requested() @LollysLottoError::WinningNumbersAlreadyRequested,
+        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::InvalidLottoGameState,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
+    /// CHECK: Parsed as a Switchboard On-Demand randomness account
+    #[account(
+        owner = SWITCHBOARD_ON_DEMAND_PROGRAM_ID @LollysLottoError::InvalidRandomnessAccount,
+    )]
+    pub randomness_account_data: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+pub fn commit_winning_numbers(ctx: Context<CommitWinningNumbers>) -> Result<()> {
+    let clock = Clock::get()?;
+    let randomness_data =
+        RandomnessAccountData::parse(ctx.accounts.randomness_account_data.data.borrow())
+            .map_err(|_| LollysLottoError::InvalidRandomnessAccount)?;
+    // The randomness must have been committed in the previous slot, so it can't be known yet.
+    if randomness_data.seed_slot != clock.slot.saturating_sub(1) {
+        return Err(LollysLottoError::RandomnessAlreadyRevealed.into());
+    }
+
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    let block_time = clock.unix_timestamp;
+    // The draw can only be committed to once the LottoGame has ended
+    if !lotto_game.check_game_ended(block_time) {
+        return Err(LollysLottoError::LottoGameIsStillOpen.into());
+    }
+    lotto_game.set_randomness_request(
+        ctx.accounts.randomness_account_data.key(),
+        randomness_data.seed_slot,
+        block_time,
+    );
+    ctx.accounts.draw_record.record_request(
+        RandomnessProvider::SwitchboardOnDemand,
+        ctx.accounts.randomness_account_data.key(),
+        clock.slot,
+        block_time,
+    )?;
+    lotto_game.transition_state(
+        ctx.accounts.lotto_game.key(),
+        LottoGameState::Drawing,
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::CommitWinningNumbers(CommitWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round: lotto_game.round,
+            randomness_account: lotto_game.randomness_request,
+            seed_slot: lotto_game.randomness_request_slot,
+        }),
+    )?;
+    Ok(())
+}
@@ -1,7 +1,11 @@
+pub mod commit_winning_numbers;
 pub mod process_winning_numbers;
 pub mod request_winning_numbers;
+pub mod reveal_winning_numbers;
 pub mod test_emit_winning_numbers;
 
+pub use commit_winning_numbers::*;
 pub use process_winning_numbers::*;
 pub use request_winning_numbers::*;
+pub use reveal_winning_numbers::*;
 pub use test_emit_winning_numbers::*;
@@ -3,12 +3,12 @@ use solana_randomness_service::SimpleRandomnessV1Account;
 use solana_randomness_service::ID as SolanaRandomnessServiceID;
 
 use crate::errors::LollysLottoError;
-use crate::state::DuplicateWinningNumbersEvent;
 use crate::state::LottoGameState;
-use crate::state::LottoTicketNumbers;
 use crate::{
     pda_identifier::PDAIdentifier,
-    state::{EventEmitter, LollysLottoProgramEventData, LottoGame, ProcessWinningNumbersEvent},
+    state::{
+        DrawRecord, EventEmitter, ExpandWinningNumbersEvent, LollysLottoProgramEventData, LottoGame,
+    },
 };
 
 #[derive(Accounts)]
@@ -30,7 +30,8 @@ pub struct ProcessWinningNumbers<'info> {
     #[account(
         mut,
         has_one = authority,
-        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        constraint = lotto_game.load()?.randomness_request == request.key() @LollysLottoError::InvalidRandomnessRequest,
+        constraint = lotto_game.load()?.state == LottoGameState::Drawing @LollysLottoError::InvalidLottoGameState,
         seeds = [
             LottoGame::IDENT,
             authority.key().as_ref(),
@@ -40,6 +41,17 @@ pub struct ProcessWinningNumbers<'info> {
     )]
     pub lotto_game: AccountLoader<'info, LottoGame>,
 
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 }
@@ -49,55 +61,39 @@ pub fn process_winning_numbers(
     result: Vec<u8>,
 ) -> anchor_lang::prelude::Result<()> {
     msg!("Randomness received: {:?}", result);
-    let timestamp = Clock::get()?.unix_timestamp;
-    msg!("Timestamp: {:?}", timestamp);
     let event_emitter = &mut ctx.accounts.event_emitter;
     let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
 
-    let winning_numbers: [u8; LottoTicketNumbers::MAX_SLOT_IN_TICKET_V1] = if result.len() == 6 {
-        // Attempt to convert the Vec<u8> into an array [u8; 6]
-        let mut arr = [0u8; LottoTicketNumbers::MAX_SLOT_IN_TICKET_V1];
-        arr.copy_from_slice(&result);
-        arr[0] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number1;
-        arr[1] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number2;
-        arr[2] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number3;
-        arr[3] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number4;
-        arr[4] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number5;
-        arr[5] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.jackpot_number;
-        arr
-    } else {
-        // Handle the error case where the vector does not have exactly 6 elements
-        panic!("Result vector does not contain exactly 6 elements.");
-        // Alternatively, you can return a default value or handle the error in another way.
-    };
+    lotto_game.set_winning_numbers_seed(&result)?;
+    ctx.accounts.draw_record.record_randomness(
+        ctx.accounts.request.key(),
+        &result,
+        Clock::get()?.slot,
+    )?;
 
-    let (is_duplicate, is_updated, tier_indices) =
-        lotto_game.update_winning_numbers(winning_numbers)?;
+    let start_nonce = lotto_game.winning_numbers_nonce;
+    let (winning_numbers_added, duplicates_skipped) =
+        lotto_game.expand_winning_numbers(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE)?;
 
     let block_time = Clock::get()?.unix_timestamp;
-    if is_duplicate {
-        event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::DuplicateWinningNumbers(DuplicateWinningNumbersEvent {
-                lotto_game: ctx.accounts.lotto_game.key(),
-                round: lotto_game.round,
-                randomness: result.clone(),
-                duplicate_numbers: winning_numbers,
-                duplicate_number_detected_index: tier_indices,
-            }),
-        )?;
-    }
-    if is_updated {
-        event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::ProcessWinningNumbers(ProcessWinningNumbersEvent {
-                lotto_game: ctx.accounts.lotto_game.key(),
-                round: lotto_game.round,
-                randomness: result,
-                winning_numbers,
-                winning_numbers_updated_index: tier_indices,
-            }),
-        )?;
+    event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::ExpandWinningNumbers(ExpandWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round: lotto_game.round,
+            seed: lotto_game.winning_numbers_seed,
+            start_nonce,
+            end_nonce: lotto_game.winning_numbers_nonce,
+            winning_numbers_added,
+            duplicates_skipped,
+            winning_numbers_filled: lotto_game.winning_numbers_filled,
+        }),
+    )?;
+    lotto_game.complete_draw_if_filled(ctx.accounts.lotto_game.key(), event_emitter, block_time)?;
+    if lotto_game.check_all_winning_numbers_set() {
+        ctx.accounts
+            .draw_record
+            .record_mapped(Clock::get()?.slot, lotto_game.winning_numbers_nonce);
     }
     Ok(())
 }
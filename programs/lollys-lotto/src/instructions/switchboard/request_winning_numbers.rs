@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use solana_randomness_service::cpi::accounts::SimpleRandomnessV1Request;
 use solana_randomness_service::program::SolanaRandomnessService;
 use solana_randomness_service::TransactionOptions;
 use switchboard_solana::{utils::get_ixn_discriminator, NativeMint};
@@ -8,9 +9,11 @@ use anchor_spl::{
     token::{Mint, Token},
 };
 
+use crate::errors::LollysLottoError;
 use crate::pda_identifier::PDAIdentifier;
 use crate::state::{
-    EventEmitter, LollysLottoProgramEventData, LottoGame, RequestWinningNumbersEvent,
+    DrawRecord, EventEmitter, LollysLotto, LollysLottoProgramEventData, LottoGame, LottoGameState,
+    RandomnessProvider, RequestWinningNumbersEvent,
 };
 
 #[derive(Accounts)]
@@ -60,6 +63,21 @@ pub struct RequestWinningNumbers<'info> {
 
     #[account(
         has_one = authority,
+        constraint = lollys_lotto.randomness_provider == RandomnessProvider::SolanaRandomnessService @LollysLottoError::RandomnessProviderMismatch,
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::InvalidLottoGameState,
+        constraint = !lotto_game.load()?.check_winning_numbers_seed_set() @LollysLottoError::WinningNumbersSeedAlreadySet,
+        constraint = !lotto_game.load()?.check_randomness_requested() @LollysLottoError::WinningNumbersAlreadyRequested,
         seeds = [
             LottoGame::IDENT,
             authority.key().as_ref(),
@@ -69,6 +87,17 @@ pub struct RequestWinningNumbers<'info> {
     )]
     pub lotto_game: AccountLoader<'info, LottoGame>,
 
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 
@@ -87,8 +116,18 @@ pub fn request_winning_numbers(
 ) -> anchor_lang::prelude::Result<()> {
     msg!("Requesting randomness...");
 
-    // Call the randomness service and request a new value
-    solana_randomness_service::cpi::simple_randomness_v1(
+    // The draw can only be requested once the LottoGame has ended
+    let clock = Clock::get()?;
+    if !ctx
+        .accounts
+        .lotto_game
+        .load()?
+        .check_game_ended(clock.unix_timestamp)
+    {
+        return Err(LollysLottoError::LottoGameIsStillOpen.into());
+    }
+
+    request_randomness(
         CpiContext::new(
             ctx.accounts.randomness_service.to_account_info(),
             solana_randomness_service::cpi::accounts::SimpleRandomnessV1Request {
@@ -102,15 +141,69 @@ pub fn request_winning_numbers(
                 associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
             },
         ),
-        6, // Request 6 bytes of randomness
+        ctx.accounts.authority.key(),
+        ctx.accounts.lotto_game.key(),
+        ctx.accounts.draw_record.key(),
+        ctx.accounts.event_emitter.key(),
+    )?;
+
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    lotto_game.set_randomness_request(
+        ctx.accounts.randomness_request.key(),
+        clock.slot,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.draw_record.record_request(
+        RandomnessProvider::SolanaRandomnessService,
+        ctx.accounts.randomness_request.key(),
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+
+    // Here we can emit some event to index our requests
+    let block_time = clock.unix_timestamp;
+    lotto_game.transition_state(
+        ctx.accounts.lotto_game.key(),
+        LottoGameState::Drawing,
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::RequestWinningNumbers(RequestWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round: lotto_game.round,
+            randomness_request: lotto_game.randomness_request,
+            requested_at: lotto_game.randomness_requested_at,
+        }),
+    )?;
+
+    Ok(())
+}
+
+/// Requests a 32 byte seed from the Solana Randomness Service, to be delivered
+/// to `process_winning_numbers` for the given LottoGame.
+pub fn request_randomness<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, SimpleRandomnessV1Request<'info>>,
+    authority: Pubkey,
+    lotto_game: Pubkey,
+    draw_record: Pubkey,
+    event_emitter: Pubkey,
+) -> Result<()> {
+    let randomness_state = ctx.accounts.state.key();
+    let randomness_request = ctx.accounts.request.key();
+    solana_randomness_service::cpi::simple_randomness_v1(
+        ctx,
+        LottoGame::WINNING_NUMBERS_SEED_LEN as u8, // Request a single 32 byte seed
         solana_randomness_service::Callback {
             program_id: crate::ID,
             accounts: vec![
-                AccountMeta::new_readonly(ctx.accounts.randomness_state.key(), true).into(),
-                AccountMeta::new_readonly(ctx.accounts.randomness_request.key(), false).into(),
-                AccountMeta::new_readonly(ctx.accounts.authority.key(), false).into(),
-                AccountMeta::new_readonly(ctx.accounts.lotto_game.key(), false).into(),
-                AccountMeta::new(ctx.accounts.event_emitter.key(), false).into(),
+                AccountMeta::new_readonly(randomness_state, true).into(),
+                AccountMeta::new_readonly(randomness_request, false).into(),
+                AccountMeta::new_readonly(authority, false).into(),
+                AccountMeta::new(lotto_game, false).into(),
+                AccountMeta::new(draw_record, false).into(),
+                AccountMeta::new(event_emitter, false).into(),
             ],
             ix_data: get_ixn_discriminator("process_winning_numbers").to_vec(), // TODO: hardcode this discriminator [190,217,49,162,99,26,73,234]
         },
@@ -118,18 +211,5 @@ pub fn request_winning_numbers(
             compute_units: Some(1_000_000),
             compute_unit_price: Some(100),
         }),
-    )?;
-    // Here we can emit some event to index our requests
-    let event_emitter = &mut ctx.accounts.event_emitter;
-    let lotto_game = &mut ctx.accounts.lotto_game;
-    let block_time = Clock::get()?.unix_timestamp;
-    event_emitter.emit_new_event(
-        Some(block_time),
-        LollysLottoProgramEventData::RequestWinningNumbers(RequestWinningNumbersEvent {
-            lotto_game: lotto_game.key(),
-            round: lotto_game.load()?.round,
-        }),
-    )?;
-
-    Ok(())
+    )
 }
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        DrawRecord, EventEmitter, ExpandWinningNumbersEvent, LollysLottoProgramEventData,
+        LottoGame, LottoGameState,
+    },
+};
+
+#[derive(Accounts)]
+pub struct RevealWinningNumbers<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = lotto_game.load()?.state == LottoGameState::Drawing @LollysLottoError::InvalidLottoGameState,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        has_one = lotto_game,
+        seeds = [
+            DrawRecord::IDENT,
+            lotto_game.key().as_ref(),
+        ],
+        bump = draw_record.bump,
+    )]
+    pub draw_record: Box<Account<'info, DrawRecord>>,
+
+    /// CHECK: The randomness account committed to in `commit_winning_numbers`
+    #[account(
+        address = lotto_game.load()?.randomness_request @LollysLottoError::InvalidRandomnessAccount,
+    )]
+    pub randomness_account_data: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+pub fn reveal_winning_numbers(ctx: Context<RevealWinningNumbers>) -> Result<()> {
+    let clock = Clock::get()?;
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+
+    let randomness_data =
+        RandomnessAccountData::parse(ctx.accounts.randomness_account_data.data.borrow())
+            .map_err(|_| LollysLottoError::InvalidRandomnessAccount)?;
+    if randomness_data.seed_slot != lotto_game.randomness_request_slot {
+        return Err(LollysLottoError::InvalidRandomnessAccount.into());
+    }
+    // A revealed value can't be replaced by a new commit, so it is still used after its reveal slot
+    if randomness_data.reveal_slot <= randomness_data.seed_slot {
+        return Err(LollysLottoError::RandomnessNotResolved.into());
+    }
+    let randomness = randomness_data.value;
+    msg!("Randomness revealed: {:?}", randomness);
+
+    lotto_game.set_winning_numbers_seed(&randomness)?;
+    ctx.accounts.draw_record.record_randomness(
+        ctx.accounts.randomness_account_data.key(),
+        &randomness,
+        clock.slot,
+    )?;
+
+    let start_nonce = lotto_game.winning_numbers_nonce;
+    let (winning_numbers_added, duplicates_skipped) =
+        lotto_game.expand_winning_numbers(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE)?;
+
+    let block_time = clock.unix_timestamp;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::ExpandWinningNumbers(ExpandWinningNumbersEvent {
+            lotto_game: ctx.accounts.lotto_game.key(),
+            round: lotto_game.round,
+            seed: lotto_game.winning_numbers_seed,
+            start_nonce,
+            end_nonce: lotto_game.winning_numbers_nonce,
+            winning_numbers_added,
+            duplicates_skipped,
+            winning_numbers_filled: lotto_game.winning_numbers_filled,
+        }),
+    )?;
+    lotto_game.complete_draw_if_filled(
+        ctx.accounts.lotto_game.key(),
+        &mut ctx.accounts.event_emitter,
+        block_time,
+    )?;
+    if lotto_game.check_all_winning_numbers_set() {
+        ctx.accounts
+            .draw_record
+            .record_mapped(clock.slot, lotto_game.winning_numbers_nonce);
+    }
+    Ok(())
+}
@@ -1,23 +1,22 @@
 use anchor_lang::prelude::*;
 
+#[cfg(feature = "testing")]
+use crate::state::{ExpandWinningNumbersEvent, LollysLottoProgramEventData};
 use crate::{
     errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
-    state::{
-        DuplicateWinningNumbersEvent, EventEmitter, LollysLottoProgramEventData, LottoGame,
-        LottoGameState, LottoTicketNumbers, ProcessWinningNumbersEvent,
-    },
+    state::{EventEmitter, LottoGame, LottoGameState},
 };
 
 #[derive(Accounts)]
 pub struct TestEmitWinningNumbers<'info> {
-    /// CHECK: Authority of the LottoGame instance
-    pub authority: AccountInfo<'info>,
+    /// Authority of the LottoGame instance
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
         has_one = authority,
-        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::LottoGameNotOpen,
+        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::InvalidLottoGameState,
         seeds = [
             LottoGame::IDENT,
             authority.key().as_ref(),
@@ -31,60 +30,53 @@ pub struct TestEmitWinningNumbers<'info> {
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 }
 
+/// Only built with the `testing` feature, other builds reject it like an unknown instruction.
+#[cfg(not(feature = "testing"))]
+pub fn test_emit_winning_numbers(
+    _ctx: Context<TestEmitWinningNumbers>,
+    _result: Vec<u8>,
+) -> Result<()> {
+    Err(ErrorCode::InstructionFallbackNotFound.into())
+}
+
+/// Starts the draw of a `Closed` LottoGame with `result` standing in for the randomness seed.
+/// The winning numbers are derived from it like any other draw: the first batch right away,
+/// like `process_winning_numbers`, the rest by `crank_expand_winning_numbers`.
+#[cfg(feature = "testing")]
 pub fn test_emit_winning_numbers(
     ctx: Context<TestEmitWinningNumbers>,
     result: Vec<u8>,
 ) -> Result<()> {
     msg!("Randomness received: {:?}", result);
-    let timestamp = Clock::get()?.unix_timestamp;
-    msg!("Timestamp: {:?}", timestamp);
+    let block_time = Clock::get()?.unix_timestamp;
+    let lotto_game_pubkey = ctx.accounts.lotto_game.key();
     let event_emitter = &mut ctx.accounts.event_emitter;
     let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
 
-    let winning_numbers: [u8; LottoTicketNumbers::MAX_SLOT_IN_TICKET_V1] = if result.len() == 6 {
-        // Attempt to convert the Vec<u8> into an array [u8; 6]
-        let mut arr = [0u8; LottoTicketNumbers::MAX_SLOT_IN_TICKET_V1];
-        arr.copy_from_slice(&result);
-        arr[0] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number1;
-        arr[1] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number2;
-        arr[2] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number3;
-        arr[3] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number4;
-        arr[4] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.number5;
-        arr[5] %= LottoTicketNumbers::MAX_NUMBERS_IN_TICKET_V1.jackpot_number;
-        arr
-    } else {
-        // Handle the error case where the vector does not have exactly 6 elements
-        panic!("Result vector does not contain exactly 6 elements.");
-        // Alternatively, you can return a default value or handle the error in another way.
-    };
-
-    let (is_duplicate, is_updated, tier_indices) =
-        lotto_game.update_winning_numbers(winning_numbers)?;
+    lotto_game.transition_state(
+        lotto_game_pubkey,
+        LottoGameState::Drawing,
+        event_emitter,
+        block_time,
+    )?;
+    lotto_game.set_winning_numbers_seed(&result)?;
 
-    let block_time = Clock::get()?.unix_timestamp;
-    if is_duplicate {
-        event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::DuplicateWinningNumbers(DuplicateWinningNumbersEvent {
-                lotto_game: ctx.accounts.lotto_game.key(),
-                round: lotto_game.round,
-                randomness: result.clone(),
-                duplicate_numbers: winning_numbers,
-                duplicate_number_detected_index: tier_indices,
-            }),
-        )?;
-    }
-    if is_updated {
-        event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::ProcessWinningNumbers(ProcessWinningNumbersEvent {
-                lotto_game: ctx.accounts.lotto_game.key(),
-                round: lotto_game.round,
-                randomness: result,
-                winning_numbers,
-                winning_numbers_updated_index: tier_indices,
-            }),
-        )?;
-    }
+    let start_nonce = lotto_game.winning_numbers_nonce;
+    let (winning_numbers_added, duplicates_skipped) =
+        lotto_game.expand_winning_numbers(LottoGame::WINNING_NUMBERS_EXPANSION_BATCH_SIZE)?;
+    event_emitter.emit_new_event(
+        Some(block_time),
+        LollysLottoProgramEventData::ExpandWinningNumbers(ExpandWinningNumbersEvent {
+            lotto_game: lotto_game_pubkey,
+            round: lotto_game.round,
+            seed: lotto_game.winning_numbers_seed,
+            start_nonce,
+            end_nonce: lotto_game.winning_numbers_nonce,
+            winning_numbers_added,
+            duplicates_skipped,
+            winning_numbers_filled: lotto_game.winning_numbers_filled,
+        }),
+    )?;
+    lotto_game.complete_draw_if_filled(lotto_game_pubkey, event_emitter, block_time)?;
     Ok(())
 }
@@ -0,0 +1,89 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLottoProgramEventData, LottoGame, LottoGameState, SponsorPrizePoolEvent,
+    },
+    utils::transfer_quote_tokens,
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64)]
+pub struct AddToPrizePool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = lotto_game_mint,
+        token::authority = sponsor,
+        token::token_program = token_program,
+    )]
+    pub sponsor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        seeds = [
+            LottoGame::IDENT,
+            lotto_game.load()?.authority.as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(mut)]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AddToPrizePool<'info> {
+    pub fn process(&mut self, round: u64, tier: u8, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(LollysLottoError::InvalidSponsorAmount.into());
+        }
+
+        // Transfer USDC from the sponsor to LottoGameVault
+        let amount_received = transfer_quote_tokens(
+            &self.token_program,
+            &self.lotto_game_mint,
+            &self.sponsor_token_account,
+            &mut self.lotto_game_vault,
+            self.sponsor.to_account_info(),
+            &[],
+            amount,
+        )?;
+
+        let lotto_game = &mut *self.lotto_game.load_mut()?;
+        lotto_game.credit_sponsored_prize_pool(tier, amount_received)?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SponsorPrizePool(SponsorPrizePoolEvent {
+                sponsor: self.sponsor.key(),
+                lotto_game: self.lotto_game.key(),
+                round,
+                tier,
+                amount,
+                amount_received,
+                sponsored_prize_pool: lotto_game.sponsored_prize_pool[tier as usize],
+            }),
+        )?;
+
+        Ok(())
+    }
+}
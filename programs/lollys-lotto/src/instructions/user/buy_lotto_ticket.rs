@@ -1,21 +1,22 @@
 pub use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
     errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
     state::{
-        validate_for_max_min_numbers, BuyLottoTicketEvent, EventEmitter,
-        LollysLottoProgramEventData, LottoGame, LottoGameState, LottoGameVault, LottoTicket,
-        LottoTicketNumbers, UserMetadata,
+        validate_for_max_min_numbers, EventEmitter, LottoGame, LottoGameState, LottoGameVault,
+        LottoTicket, LottoTicketCombination, LottoTicketNumbers, PaymentAsset, StakePool,
+        StakePosition, TicketPurchase, UserMetadata,
     },
+    utils::{transfer_fee_for_amount, transfer_quote_tokens},
 };
 
 #[derive(Accounts)]
-#[instruction(round: u64, numbers: [u8; 6])]
+#[instruction(round: u64, numbers: LottoTicketNumbers)]
 pub struct BuyLottoTicket<'info> {
     /// CHECK: Authority of the LottoGame instance
     pub authority: AccountInfo<'info>,
@@ -38,10 +39,11 @@ pub struct BuyLottoTicket<'info> {
         constraint = user_usdc_token_account.amount >= lotto_game.load()?.ticket_price @LollysLottoError::InsufficientFunds,
         associated_token::mint = lotto_game_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_usdc_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub lotto_game_mint: Box<Account<'info, Mint>>,
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
@@ -63,8 +65,9 @@ pub struct BuyLottoTicket<'info> {
         mut,
         associated_token::mint = lotto_game_mint,
         associated_token::authority = LottoGameVault::signer_address(lotto_game.key()),
+        associated_token::token_program = token_program,
     )]
-    pub lotto_game_vault: Box<Account<'info, TokenAccount>>,
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -74,55 +77,84 @@ pub struct BuyLottoTicket<'info> {
             LottoTicket::IDENT,
             lotto_game.key().as_ref(),
             user_metadata.key().as_ref(),
-            numbers[0].to_le_bytes().as_ref(),
-            numbers[1].to_le_bytes().as_ref(),
-            numbers[2].to_le_bytes().as_ref(),
-            numbers[3].to_le_bytes().as_ref(),
-            numbers[4].to_le_bytes().as_ref(),
-            numbers[5].to_le_bytes().as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
         ],
         bump
     )]
     pub lotto_ticket: Box<Account<'info, LottoTicket>>,
 
+    /// Counts the tickets bought with these numbers, winners sharing them split the prize
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + LottoTicketCombination::SIZE,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// StakePool of the LottoGame authority, only needed for a staker discount
+    #[account(
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// StakePosition of the user in `stake_pool`, only needed for a staker discount
+    pub stake_position: Option<Box<Account<'info, StakePosition>>>,
+
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> BuyLottoTicket<'info> {
-    pub fn process(&mut self, round: u64, numbers: LottoTicketNumbers) -> Result<()> {
+    pub fn process(
+        &mut self,
+        lotto_ticket_combination_bump: u8,
+        _round: u64,
+        numbers: LottoTicketNumbers,
+    ) -> Result<()> {
         let lotto_game = &mut *self.lotto_game.load_mut()?;
-        let lotto_ticket = &mut self.lotto_ticket;
-        let user_usdc_token_account = &self.user_usdc_token_account;
-        let lotto_game_vault = &self.lotto_game_vault;
-        let user_metadata = &mut self.user_metadata;
-
-        // // Check if the LottoGame is open
-        // if lotto_game.state != LottoGameState::Open {
-        //     return Err(LollysLottoError::LottoGameNotOpen.into());
-        // }
-
-        // // Check if the round is correct
-        // if lotto_game.round != round {
-        //     return Err(LollysLottoError::InvalidRound.into());
-        // }
-
-        // Check the balance of the user's USDC token account to see if they have enough to buy a ticket
-        // let user_usdc_balance = user_usdc_token_account.amount;
-        // if user_usdc_balance < lotto_game.ticket_price {
-        //     return Err(LollysLottoError::InsufficientFunds.into());
-        // }
-
-        // Check the time at which the ticket is being purchased with end_date of the LottoGame
+        let discount = StakePool::staker_discount(
+            self.stake_pool.as_deref(),
+            self.stake_position.as_deref(),
+            self.user.key(),
+            lotto_game.ticket_price,
+        )?;
+        let max_transfer_fee = transfer_fee_for_amount(
+            &self.token_program,
+            &self.lotto_game_mint,
+            lotto_game.ticket_price,
+        )?;
+        let (discount, ticket_price) =
+            lotto_game.discounted_ticket_price(discount, max_transfer_fee);
+
+        // Check the time at which the ticket is being purchased with the sales close date of the LottoGame.
+        // The LottoGame itself is closed by `crank_lotto_game_closed`.
         let current_time = Clock::get()?.unix_timestamp;
-        if current_time > lotto_game.end_date {
-            lotto_game.state = LottoGameState::Closed;
-            //TODO: emit event
-            return Err(LollysLottoError::LottoGameEnded.into());
+        if lotto_game.check_sales_window_over(current_time) {
+            return Err(LollysLottoError::LottoGameSalesClosed.into());
         }
 
         // check the numbers with MAX_NUMBERS_IN_TICKET
@@ -130,55 +162,32 @@ impl<'info> BuyLottoTicket<'info> {
             return Err(LollysLottoError::InvalidNumbersInTicket.into());
         }
         // Transfer USDC from user to LottoGameVault
-        token::transfer(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: user_usdc_token_account.to_account_info(),
-                    to: lotto_game_vault.to_account_info(),
-                    authority: self.user.to_account_info(),
-                },
-            ),
-            lotto_game.ticket_price,
+        let amount_received = transfer_quote_tokens(
+            &self.token_program,
+            &self.lotto_game_mint,
+            &self.user_usdc_token_account,
+            &mut self.lotto_game_vault,
+            self.user.to_account_info(),
+            &[],
+            ticket_price,
         )?;
 
-        lotto_ticket.user = *self.user.key;
-        lotto_ticket.ticket_number = lotto_game.tickets_sold;
-        lotto_ticket.lotto_game = self.lotto_game.key();
-        lotto_ticket.round = round;
-        lotto_ticket.numbers = numbers;
-        lotto_ticket.ticket_price = lotto_game.ticket_price;
-        lotto_ticket.buy_date = current_time;
-        lotto_ticket.check_date = 0;
-        lotto_ticket.is_checked = 0;
-        lotto_ticket.is_duplicated = 0;
-        lotto_ticket.is_winner = 0;
-        lotto_ticket.prize = 0;
-
-        lotto_game.tickets_sold += 1;
-
-        user_metadata.total_tickets_purchased += 1;
-
-        let clock = Clock::get()?;
-        let block_time = clock.unix_timestamp;
-
-        self.event_emitter.emit_new_event(
-            Some(block_time),
-            LollysLottoProgramEventData::BuyLottoTicket(BuyLottoTicketEvent {
-                user: *self.user.key,
-                user_metadata: user_metadata.key(),
-                user_ticket_count: user_metadata.total_tickets_purchased,
-                lotto_ticket: lotto_ticket.key(),
-                lotto_game: self.lotto_game.key(),
-                tickets_sold: lotto_game.tickets_sold,
-                round,
-                ticket_number: lotto_ticket.ticket_number,
+        lotto_game.issue_ticket(
+            self.lotto_game.key(),
+            TicketPurchase {
+                user: self.user.key(),
+                payer: self.user.key(),
+                lotto_ticket_combination_bump,
                 numbers,
-                ticket_price: lotto_game.ticket_price,
-                buy_date: lotto_ticket.buy_date,
-            }),
-        )?;
-
-        Ok(())
+                discount,
+                transfer_fee: ticket_price - amount_received,
+                payment_asset: PaymentAsset::Usdc,
+                buy_date: current_time,
+            },
+            &mut self.lotto_ticket,
+            &mut self.lotto_ticket_combination,
+            &mut self.user_metadata,
+            &mut self.event_emitter,
+        )
     }
 }
@@ -0,0 +1,260 @@
+pub use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        validate_for_max_min_numbers, BuyLottoTicketWithIntentEvent, EventEmitter,
+        LollysLottoProgramEventData, LottoGame, LottoGameState, LottoGameVault, LottoTicket,
+        LottoTicketCombination, PaymentAsset, StakePool, StakePosition, TicketPurchase,
+        TicketPurchaseIntent, UserMetadata,
+    },
+    utils::{transfer_fee_for_amount, transfer_quote_tokens, verify_ed25519_signature},
+};
+
+#[derive(Accounts)]
+#[instruction(intent: TicketPurchaseIntent)]
+pub struct BuyLottoTicketWithIntent<'info> {
+    /// Pays the transaction fee and the rent of the ticket, reimbursed in USDC
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = lotto_game_mint,
+        token::token_program = token_program,
+    )]
+    pub relayer_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    /// CHECK: The user who signed the intent, verified through the Ed25519 precompile
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    /// The user pre-approves their UserMetadata as delegate of this account
+    #[account(
+        mut,
+        constraint = user_usdc_token_account.delegate.contains(&user_metadata.key()) @LollysLottoError::InvalidUsdcDelegate,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == intent.round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = LottoGameVault::signer_address(lotto_game.key()),
+        associated_token::token_program = token_program,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<LottoTicket>(),
+        seeds = [
+            LottoTicket::IDENT,
+            lotto_game.key().as_ref(),
+            user_metadata.key().as_ref(),
+            intent.numbers.number1.to_le_bytes().as_ref(),
+            intent.numbers.number2.to_le_bytes().as_ref(),
+            intent.numbers.number3.to_le_bytes().as_ref(),
+            intent.numbers.number4.to_le_bytes().as_ref(),
+            intent.numbers.number5.to_le_bytes().as_ref(),
+            intent.numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket: Box<Account<'info, LottoTicket>>,
+
+    /// Counts the tickets bought with these numbers, winners sharing them split the prize
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + LottoTicketCombination::SIZE,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            intent.numbers.number1.to_le_bytes().as_ref(),
+            intent.numbers.number2.to_le_bytes().as_ref(),
+            intent.numbers.number3.to_le_bytes().as_ref(),
+            intent.numbers.number4.to_le_bytes().as_ref(),
+            intent.numbers.number5.to_le_bytes().as_ref(),
+            intent.numbers.jackpot_number.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// StakePool of the LottoGame authority, only needed for a staker discount
+    #[account(
+        seeds = [
+            StakePool::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Option<Box<Account<'info, StakePool>>>,
+
+    /// StakePosition of the user in `stake_pool`, only needed for a staker discount
+    pub stake_position: Option<Box<Account<'info, StakePosition>>>,
+
+    /// CHECK: Instructions sysvar, holds the Ed25519 precompile instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyLottoTicketWithIntent<'info> {
+    /// Buys a ticket for a user who has USDC but no SOL.
+    /// The instruction right before this one must be an Ed25519 precompile instruction
+    /// verifying the user's signature over `TicketPurchaseIntent::message`.
+    pub fn process(
+        &mut self,
+        lotto_ticket_combination_bump: u8,
+        intent: TicketPurchaseIntent,
+    ) -> Result<()> {
+        let lotto_game = &mut *self.lotto_game.load_mut()?;
+        let user_metadata = &mut self.user_metadata;
+
+        // CHECK: The intent binds this exact LottoGame and was signed by the user
+        if intent.lotto_game != self.lotto_game.key() {
+            return Err(LollysLottoError::InvalidIntentSignature.into());
+        }
+        verify_ed25519_signature(&self.instructions, self.user.key, &intent.message())?;
+
+        // CHECK: Replay protection
+        if intent.nonce != user_metadata.intent_nonce {
+            return Err(LollysLottoError::InvalidIntentNonce.into());
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        if intent.check_expired(current_time) {
+            return Err(LollysLottoError::IntentExpired.into());
+        }
+        let discount = StakePool::staker_discount(
+            self.stake_pool.as_deref(),
+            self.stake_position.as_deref(),
+            self.user.key(),
+            lotto_game.ticket_price,
+        )?;
+        let max_transfer_fee = transfer_fee_for_amount(
+            &self.token_program,
+            &self.lotto_game_mint,
+            lotto_game.ticket_price,
+        )?;
+        let (discount, ticket_price) =
+            lotto_game.discounted_ticket_price(discount, max_transfer_fee);
+        if ticket_price > intent.max_ticket_price {
+            return Err(LollysLottoError::TicketPriceAboveMax.into());
+        }
+        if lotto_game.check_sales_window_over(current_time) {
+            return Err(LollysLottoError::LottoGameSalesClosed.into());
+        }
+        if !validate_for_max_min_numbers(intent.numbers) {
+            return Err(LollysLottoError::InvalidNumbersInTicket.into());
+        }
+
+        // Pay the ticket and reimburse the relayer through the UserMetadata delegate
+        let user_key = self.user.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            UserMetadata::IDENT,
+            user_key.as_ref(),
+            &[user_metadata.bump],
+        ]];
+        let amount_received = transfer_quote_tokens(
+            &self.token_program,
+            &self.lotto_game_mint,
+            &self.user_usdc_token_account,
+            &mut self.lotto_game_vault,
+            user_metadata.to_account_info(),
+            signer_seeds,
+            ticket_price,
+        )?;
+        if intent.relayer_fee > 0 {
+            transfer_quote_tokens(
+                &self.token_program,
+                &self.lotto_game_mint,
+                &self.user_usdc_token_account,
+                &mut self.relayer_usdc_token_account,
+                user_metadata.to_account_info(),
+                signer_seeds,
+                intent.relayer_fee,
+            )?;
+        }
+
+        user_metadata.intent_nonce += 1;
+        lotto_game.issue_ticket(
+            self.lotto_game.key(),
+            TicketPurchase {
+                user: user_key,
+                payer: self.relayer.key(),
+                lotto_ticket_combination_bump,
+                numbers: intent.numbers,
+                discount,
+                transfer_fee: ticket_price - amount_received,
+                payment_asset: PaymentAsset::Usdc,
+                buy_date: current_time,
+            },
+            &mut self.lotto_ticket,
+            &mut self.lotto_ticket_combination,
+            user_metadata,
+            &mut self.event_emitter,
+        )?;
+        self.event_emitter.emit_new_event(
+            Some(current_time),
+            LollysLottoProgramEventData::BuyLottoTicketWithIntent(BuyLottoTicketWithIntentEvent {
+                user: user_key,
+                user_metadata: user_metadata.key(),
+                relayer: self.relayer.key(),
+                relayer_usdc_token_account: self.relayer_usdc_token_account.key(),
+                lotto_game: self.lotto_game.key(),
+                lotto_ticket: self.lotto_ticket.key(),
+                round: intent.round,
+                intent_nonce: intent.nonce,
+                ticket_price,
+                relayer_fee: intent.relayer_fee,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{spl_token::native_mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenInterface},
+};
+
+use crate::{
+    constants::LOLLY_MINT,
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        validate_for_max_min_numbers, BuyLottoTicketWithSwapEvent, EventEmitter, LollyBurnState,
+        LollysLotto, LollysLottoProgramEventData, LottoGame, LottoGameState, LottoGameVault,
+        LottoTicket, LottoTicketCombination, LottoTicketNumbers, PaymentAsset, TicketPurchase,
+        UserMetadata,
+    },
+    utils::{invoke_swap, transfer_fee_for_amount, transfer_quote_tokens, wrap_native},
+};
+
+#[derive(Accounts)]
+#[instruction(round: u64, numbers: [u8; 6])]
+pub struct BuyLottoTicketWithSwap<'info> {
+    /// CHECK: Authority of the LottoGame instance
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    /// Receives the USDC of the swap, the ticket is paid from it
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Wrapped SOL or LOLLY token account of the user, spent by the swap
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub user_payment_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub lotto_game_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        has_one = lotto_game_vault,
+        has_one = lotto_game_mint,
+        constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
+        constraint = lotto_game.load()?.state == LottoGameState::Open @LollysLottoError::LottoGameNotOpen,
+        seeds = [
+            LottoGame::IDENT,
+            authority.key().as_ref(),
+            lotto_game.load()?.round.to_le_bytes().as_ref(),
+        ],
+        bump = lotto_game.load()?.bump,
+    )]
+    pub lotto_game: AccountLoader<'info, LottoGame>,
+
+    #[account(
+        mut,
+        associated_token::mint = lotto_game_mint,
+        associated_token::authority = LottoGameVault::signer_address(lotto_game.key()),
+        associated_token::token_program = token_program,
+    )]
+    pub lotto_game_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + std::mem::size_of::<LottoTicket>(),
+        seeds = [
+            LottoTicket::IDENT,
+            lotto_game.key().as_ref(),
+            user_metadata.key().as_ref(),
+            numbers[0].to_le_bytes().as_ref(),
+            numbers[1].to_le_bytes().as_ref(),
+            numbers[2].to_le_bytes().as_ref(),
+            numbers[3].to_le_bytes().as_ref(),
+            numbers[4].to_le_bytes().as_ref(),
+            numbers[5].to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket: Box<Account<'info, LottoTicket>>,
+
+    /// Counts the tickets bought with these numbers, winners sharing them split the prize
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + LottoTicketCombination::SIZE,
+        seeds = [
+            LottoTicketCombination::IDENT,
+            lotto_game.key().as_ref(),
+            numbers[0].to_le_bytes().as_ref(),
+            numbers[1].to_le_bytes().as_ref(),
+            numbers[2].to_le_bytes().as_ref(),
+            numbers[3].to_le_bytes().as_ref(),
+            numbers[4].to_le_bytes().as_ref(),
+            numbers[5].to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub lotto_ticket_combination: Box<Account<'info, LottoTicketCombination>>,
+
+    /// Holds the LOLLY payment discount
+    #[account(
+        seeds = [
+            LollysLotto::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lollys_lotto.bump,
+    )]
+    pub lollys_lotto: Box<Account<'info, LollysLotto>>,
+
+    /// Holds the swap program allowlist
+    #[account(
+        seeds = [
+            LollyBurnState::IDENT,
+            authority.key().as_ref(),
+        ],
+        bump = lolly_burn_state.bump,
+    )]
+    pub lolly_burn_state: Box<Account<'info, LollyBurnState>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    /// CHECK: Any program on the LollyBurnState allowlist, e.g. Jupiter
+    #[account(
+        executable,
+        constraint = lolly_burn_state.is_swap_program_allowed(swap_program.key()) @LollysLottoError::SwapProgramNotAllowed,
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+    /// Token program of the LottoGame mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program of wrapped SOL and LOLLY
+    pub payment_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys a ticket with SOL or LOLLY. At most `max_amount_in` is swapped to USDC through an
+/// allowlisted swap program, the USDC is received by the user who then pays the ticket as usual.
+/// SOL is wrapped into `user_payment_token_account` first, any of it left unswapped stays wrapped.
+/// remaining_accounts and `data` are the route of the swap program, built off-chain.
+pub fn buy_lotto_ticket_with_swap<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, BuyLottoTicketWithSwap<'info>>,
+    round: u64,
+    numbers: LottoTicketNumbers,
+    payment_asset: PaymentAsset,
+    max_amount_in: u64,
+    data: Vec<u8>,
+) -> Result<()> {
+    let payment_mint = match payment_asset {
+        PaymentAsset::Sol => native_mint::ID,
+        PaymentAsset::Lolly => LOLLY_MINT,
+        PaymentAsset::Usdc => return err!(LollysLottoError::InvalidPaymentAsset),
+    };
+    if ctx.accounts.user_payment_token_account.mint != payment_mint {
+        return err!(LollysLottoError::InvalidPaymentAsset);
+    }
+    if !validate_for_max_min_numbers(numbers) {
+        return err!(LollysLottoError::InvalidNumbersInTicket);
+    }
+
+    let (discount, ticket_price) = {
+        let lotto_game = ctx.accounts.lotto_game.load()?;
+        let discount = ctx
+            .accounts
+            .lollys_lotto
+            .payment_discount(payment_asset, lotto_game.ticket_price);
+        let max_transfer_fee = transfer_fee_for_amount(
+            &ctx.accounts.token_program,
+            &ctx.accounts.lotto_game_mint,
+            lotto_game.ticket_price,
+        )?;
+        lotto_game.discounted_ticket_price(discount, max_transfer_fee)
+    };
+
+    if payment_asset == PaymentAsset::Sol {
+        wrap_native(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.payment_token_program.to_account_info(),
+            &ctx.accounts.user_payment_token_account,
+            &ctx.accounts.user.to_account_info(),
+            &[],
+            max_amount_in,
+        )?;
+        ctx.accounts.user_payment_token_account.reload()?;
+    }
+
+    let payment_before = ctx.accounts.user_payment_token_account.amount;
+    let usdc_before = ctx.accounts.user_usdc_token_account.amount;
+
+    // The user is signing this swap
+    invoke_swap(
+        &ctx.accounts.swap_program,
+        ctx.remaining_accounts,
+        ctx.accounts.user.key(),
+        &[],
+        data,
+    )?;
+
+    ctx.accounts.user_payment_token_account.reload()?;
+    ctx.accounts.user_usdc_token_account.reload()?;
+    let amount_in = payment_before
+        .checked_sub(ctx.accounts.user_payment_token_account.amount)
+        .ok_or(LollysLottoError::InvalidSwapBalanceChange)?;
+    let usdc_out = ctx
+        .accounts
+        .user_usdc_token_account
+        .amount
+        .checked_sub(usdc_before)
+        .ok_or(LollysLottoError::InvalidSwapBalanceChange)?;
+    if amount_in > max_amount_in {
+        return err!(LollysLottoError::SwapAmountExceeded);
+    }
+    if usdc_out < ticket_price {
+        return err!(LollysLottoError::SwapSlippageExceeded);
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let lotto_game = &mut *ctx.accounts.lotto_game.load_mut()?;
+    if lotto_game.check_sales_window_over(current_time) {
+        return err!(LollysLottoError::LottoGameSalesClosed);
+    }
+
+    let amount_received = transfer_quote_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.lotto_game_mint,
+        &ctx.accounts.user_usdc_token_account,
+        &mut ctx.accounts.lotto_game_vault,
+        ctx.accounts.user.to_account_info(),
+        &[],
+        ticket_price,
+    )?;
+
+    lotto_game.issue_ticket(
+        ctx.accounts.lotto_game.key(),
+        TicketPurchase {
+            user: ctx.accounts.user.key(),
+            payer: ctx.accounts.user.key(),
+            lotto_ticket_combination_bump: ctx.bumps.lotto_ticket_combination,
+            numbers,
+            discount,
+            transfer_fee: ticket_price - amount_received,
+            payment_asset,
+            buy_date: current_time,
+        },
+        &mut ctx.accounts.lotto_ticket,
+        &mut ctx.accounts.lotto_ticket_combination,
+        &mut ctx.accounts.user_metadata,
+        &mut ctx.accounts.event_emitter,
+    )?;
+    ctx.accounts.event_emitter.emit_new_event(
+        Some(current_time),
+        LollysLottoProgramEventData::BuyLottoTicketWithSwap(BuyLottoTicketWithSwapEvent {
+            user: ctx.accounts.user.key(),
+            lotto_game: ctx.accounts.lotto_game.key(),
+            lotto_ticket: ctx.accounts.lotto_ticket.key(),
+            round,
+            payment_asset,
+            amount_in,
+            usdc_out,
+            ticket_price,
+            discount,
+        }),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{
+        CancelTicketSubscriptionEvent, EventEmitter, LollysLottoProgramEventData,
+        TicketSubscription,
+    },
+    utils::{harvest_withheld_transfer_fees, transfer_quote_tokens},
+};
+
+#[derive(Accounts)]
+pub struct CancelTicketSubscription<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Returns the escrowed ticket rent to the user as well
+    #[account(
+        mut,
+        has_one = user,
+        close = user,
+        seeds = [
+            TicketSubscription::IDENT,
+            ticket_subscription.user_metadata.as_ref(),
+            ticket_subscription.authority.as_ref(),
+            ticket_subscription.subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump = ticket_subscription.bump,
+    )]
+    pub ticket_subscription: Box<Account<'info, TicketSubscription>>,
+
+    /// Receives the transfer fees withheld in the vault before it is closed
+    #[account(
+        mut,
+        address = ticket_subscription.mint,
+    )]
+    pub subscription_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = subscription_mint,
+        associated_token::authority = ticket_subscription,
+        associated_token::token_program = token_program,
+    )]
+    pub subscription_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = subscription_mint,
+        token::token_program = token_program,
+    )]
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelTicketSubscription<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let ticket_subscription = &self.ticket_subscription;
+        let vault_amount = self.subscription_vault.amount;
+        let subscription_id = ticket_subscription.subscription_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            TicketSubscription::IDENT,
+            ticket_subscription.user_metadata.as_ref(),
+            ticket_subscription.authority.as_ref(),
+            subscription_id.as_ref(),
+            &[ticket_subscription.bump],
+        ]];
+
+        let withdrawn_amount = transfer_quote_tokens(
+            &self.token_program,
+            &self.subscription_mint,
+            &self.subscription_vault,
+            &mut self.user_usdc_token_account,
+            ticket_subscription.to_account_info(),
+            signer_seeds,
+            vault_amount,
+        )?;
+        harvest_withheld_transfer_fees(
+            &self.token_program,
+            &self.subscription_mint,
+            &self.subscription_vault,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.subscription_vault.to_account_info(),
+                destination: self.user.to_account_info(),
+                authority: ticket_subscription.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::CancelTicketSubscription(CancelTicketSubscriptionEvent {
+                user: self.user.key(),
+                ticket_subscription: ticket_subscription.key(),
+                rounds_fulfilled: ticket_subscription.rounds_fulfilled,
+                rounds_remaining: ticket_subscription.rounds_remaining,
+                withdrawn_amount,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{ClaimAllUserRewardsEvent, EventEmitter, LollysLottoProgramEventData, UserMetadata},
+    utils::transfer_quote_tokens,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimAllUserRewards<'info> {
+    /// The user or their claim authority
+    pub claimant: Signer<'info>,
+
+    /// CHECK: User account
+    #[account()]
+    pub user: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = user,
+        constraint = user_metadata.check_claimant(claimant.key()) @LollysLottoError::InvalidClaimAuthority,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Account<'info, UserMetadata>,
+
+    /// Mint of the rewards, USDC or the Token-2022 mint of the LottoGames they were won in
+    pub rewards_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// Holds the unclaimed rewards won in LottoGames of `rewards_mint`
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = user_metadata,
+        associated_token::token_program = token_program,
+    )]
+    pub user_rewards_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Any token account of `rewards_mint`, e.g. another wallet or an exchange deposit address
+    #[account(
+        mut,
+        token::mint = rewards_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimAllUserRewards<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let user_metadata = &mut self.user_metadata;
+
+        let amount_claimed = self.user_rewards_vault.amount;
+        if amount_claimed == 0 {
+            return Err(LollysLottoError::NoRewardsToClaimFromVault.into());
+        }
+
+        transfer_quote_tokens(
+            &self.token_program,
+            &self.rewards_mint,
+            &self.user_rewards_vault,
+            &mut self.destination,
+            user_metadata.to_account_info(),
+            &[&[
+                UserMetadata::IDENT,
+                self.user.key().as_ref(),
+                &[user_metadata.bump],
+            ]],
+            amount_claimed,
+        )?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        user_metadata.total_amount_claimed += amount_claimed;
+        user_metadata.last_claimed_at = block_time;
+
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::ClaimAllUserRewards(ClaimAllUserRewardsEvent {
+                user: user_metadata.user,
+                user_metadata: user_metadata.key(),
+                claimant: self.claimant.key(),
+                user_rewards_vault: self.user_rewards_vault.key(),
+                destination: self.destination.key(),
+                amount_claimed,
+                total_amount_claimed: user_metadata.total_amount_claimed,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    constants::USDC_MINT_DEVNET,
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        ClaimStakeRewardsEvent, EventEmitter, LollysLottoProgramEventData, StakePool, StakePosition,
+    },
+};
+
+#[derive(Accounts)]
+pub struct ClaimStakeRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            StakePool::IDENT,
+            stake_pool.authority.as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        mut,
+        has_one = user,
+        has_one = stake_pool,
+        seeds = [
+            StakePosition::IDENT,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump = stake_position.bump,
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        associated_token::mint = USDC_MINT_DEVNET,
+        associated_token::authority = user,
+    )]
+    pub user_usdc_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = USDC_MINT_DEVNET,
+        associated_token::authority = stake_pool,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimStakeRewards<'info> {
+    /// Pays out the USDC protocol fees earned by the position.
+    pub fn process(&mut self) -> Result<()> {
+        let amount = self.stake_position.take_rewards(&self.stake_pool)?;
+        if amount == 0 {
+            return Err(LollysLottoError::NoRewardsToClaimFromVault.into());
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.reward_vault.to_account_info(),
+                    to: self.user_usdc_token_account.to_account_info(),
+                    authority: self.stake_pool.to_account_info(),
+                },
+                &[&[
+                    StakePool::IDENT,
+                    self.stake_pool.authority.as_ref(),
+                    &[self.stake_pool.bump],
+                ]],
+            ),
+            amount,
+        )?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::ClaimStakeRewards(ClaimStakeRewardsEvent {
+                user: self.user.key(),
+                stake_pool: self.stake_pool.key(),
+                stake_position: self.stake_position.key(),
+                amount,
+                total_rewards_claimed: self.stake_position.total_rewards_claimed,
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
-    constants::USDC_MINT_DEVNET,
     errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
     state::{ClaimUserRewardsEvent, EventEmitter, LollysLottoProgramEventData, UserMetadata},
+    utils::transfer_quote_tokens,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct ClaimUserRewards<'info> {
@@ -14,10 +14,11 @@ pub struct ClaimUserRewards<'info> {
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = rewards_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_usdc_token_account: Account<'info, TokenAccount>,
+    pub user_usdc_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -30,28 +31,28 @@ pub struct ClaimUserRewards<'info> {
     )]
     pub user_metadata: Account<'info, UserMetadata>,
 
-    /// Mint address of the USDC token
-    #[account(address = USDC_MINT_DEVNET)]
-    pub usdc_mint: Box<Account<'info, Mint>>,
+    /// Mint of the rewards, USDC or the Token-2022 mint of the LottoGames they were won in
+    pub rewards_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// Holds the unclaimed rewards won in LottoGames of `rewards_mint`
     #[account(
         mut,
         constraint = user_rewards_vault.amount > 0 @LollysLottoError::NoRewardsToClaimFromVault,
-        associated_token::mint = usdc_mint,
+        associated_token::mint = rewards_mint,
         associated_token::authority = user_metadata,
+        associated_token::token_program = token_program,
     )]
-    pub user_rewards_vault: Box<Account<'info, TokenAccount>>,
+    pub user_rewards_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub event_emitter: Box<Account<'info, EventEmitter>>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> ClaimUserRewards<'info> {
     pub fn process(&mut self, amount_to_be_claimed: u64) -> Result<()> {
         let user_metadata = &mut self.user_metadata;
 
-        let amount_left_to_claim =
-            user_metadata.total_amount_won - user_metadata.total_amount_claimed;
+        let amount_left_to_claim = self.user_rewards_vault.amount;
         if amount_left_to_claim == 0 {
             return Err(LollysLottoError::NoRewardsToClaimFromVault.into());
         }
@@ -60,20 +61,17 @@ impl<'info> ClaimUserRewards<'info> {
             return Err(LollysLottoError::NotSufficientRewardsInVault.into());
         }
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: self.user_rewards_vault.to_account_info(),
-                    to: self.user_usdc_token_account.to_account_info(),
-                    authority: user_metadata.to_account_info(),
-                },
-                &[&[
-                    UserMetadata::IDENT,
-                    self.user.key().as_ref(),
-                    &[user_metadata.bump],
-                ]],
-            ),
+        transfer_quote_tokens(
+            &self.token_program,
+            &self.rewards_mint,
+            &self.user_rewards_vault,
+            &mut self.user_usdc_token_account,
+            user_metadata.to_account_info(),
+            &[&[
+                UserMetadata::IDENT,
+                self.user.key().as_ref(),
+                &[user_metadata.bump],
+            ]],
             amount_to_be_claimed,
         )?;
 
@@ -4,7 +4,7 @@ use crate::{
     errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
     state::{
-        CloseLottoTicketEvent, EventEmitter, LollysLottoProgramEventData, LottoGame, LottoGameState, LottoTicket, LottoTicketNumbers, UserMetadata
+        CloseLottoTicketEvent, EventEmitter, LollysLottoProgramEventData, LottoGame, LottoTicket, LottoTicketNumbers, UserMetadata
     },
 };
 
@@ -28,7 +28,7 @@ pub struct CloseLottoTicket<'info> {
     pub user_metadata: Box<Account<'info, UserMetadata>>,
 
     #[account(
-        constraint = lotto_game.load()?.state == LottoGameState::Closed @LollysLottoError::LottoGameIsStillOpen,
+        constraint = lotto_game.load()?.check_game_state_closed() @LollysLottoError::LottoGameIsStillOpen,
         constraint = lotto_game.load()?.round == round @LollysLottoError::InvalidRound,
         seeds = [
             LottoGame::IDENT,
@@ -70,6 +70,11 @@ pub struct CloseLottoTicket<'info> {
 
 impl<'info> CloseLottoTicket<'info> {
     pub fn process(&mut self, round: u64, numbers: LottoTicketNumbers) -> Result<()> {
+        // CHECK: Unpaid winning tickets must not be destroyed
+        self.lotto_game
+            .load()?
+            .check_lotto_ticket_closable(&self.lotto_ticket)?;
+
         let block_time = Clock::get()?.unix_timestamp;
         self.event_emitter.emit_new_event(
             Some(block_time),
@@ -0,0 +1,87 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        CloseLottoTicketEvent, EventEmitter, LollysLottoProgramEventData, LottoGame, LottoTicket,
+        UserMetadata,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CloseLottoTickets<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes many tickets of `user` at once, across rounds.
+/// `remaining_accounts` holds a `[lotto_game, lotto_ticket]` pair per ticket, the tickets writable.
+/// Every ticket must be closable on its own, see [LottoGame::check_lotto_ticket_closable].
+pub fn close_lotto_tickets<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CloseLottoTickets<'info>>,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() || ctx.remaining_accounts.len() % 2 != 0 {
+        return Err(LollysLottoError::InvalidRemainingAccounts.into());
+    }
+
+    let user = ctx.accounts.user.to_account_info();
+    let block_time = Clock::get()?.unix_timestamp;
+
+    for accounts in ctx.remaining_accounts.chunks_exact(2) {
+        let lotto_game_loader = AccountLoader::<LottoGame>::try_from(&accounts[0])?;
+        let lotto_ticket = Account::<LottoTicket>::try_from(&accounts[1])?;
+
+        // CHECK: The ticket belongs to the user and was bought in this LottoGame
+        let lotto_ticket_pda = LottoTicket::address(
+            lotto_game_loader.key(),
+            ctx.accounts.user_metadata.key(),
+            lotto_ticket.numbers,
+        );
+        if lotto_ticket.key() != lotto_ticket_pda
+            || lotto_ticket.user != user.key()
+            || lotto_ticket.lotto_game != lotto_game_loader.key()
+        {
+            return Err(LollysLottoError::InvalidLottoTicketPDA.into());
+        }
+
+        // CHECK: Unpaid winning tickets must not be destroyed
+        let round = {
+            let lotto_game = lotto_game_loader.load()?;
+            lotto_game.check_lotto_ticket_closable(&lotto_ticket)?;
+            lotto_game.round
+        };
+
+        let numbers = lotto_ticket.numbers;
+        let lotto_ticket_pubkey = lotto_ticket.key();
+        lotto_ticket.close(user.clone())?;
+
+        ctx.accounts.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::CloseLottoTicket(CloseLottoTicketEvent {
+                round,
+                numbers,
+                lotto_game: lotto_game_loader.key(),
+                lotto_ticket: lotto_ticket_pubkey,
+                user: user.key(),
+            }),
+        )?;
+    }
+
+    Ok(())
+}
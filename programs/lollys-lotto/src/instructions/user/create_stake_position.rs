@@ -0,0 +1,67 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{
+        CreateStakePositionEvent, EventEmitter, LollysLottoProgramEventData, StakePool,
+        StakePosition,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CreateStakePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            StakePool::IDENT,
+            stake_pool.authority.as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + std::mem::size_of::<StakePosition>(),
+        seeds = [
+            StakePosition::IDENT,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStakePosition<'info> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let stake_position = &mut self.stake_position;
+        stake_position.bump = bump;
+        stake_position.user = self.user.key();
+        stake_position.stake_pool = self.stake_pool.key();
+        stake_position.staked_amount = 0;
+        stake_position.reward_debt = 0;
+        stake_position.pending_rewards = 0;
+        stake_position.total_rewards_claimed = 0;
+        stake_position.last_staked_at = 0;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::CreateStakePosition(CreateStakePositionEvent {
+                user: self.user.key(),
+                stake_pool: self.stake_pool.key(),
+                stake_position: self.stake_position.key(),
+            }),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+pub use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{
+        validate_for_max_min_numbers, CreateTicketSubscriptionEvent, EventEmitter,
+        LollysLottoProgramEventData, LottoTicketNumbers, TicketSubscription, UserMetadata,
+    },
+    utils::{transfer_fee_for_net_amount, transfer_quote_tokens},
+};
+
+#[derive(Accounts)]
+#[instruction(subscription_id: u64)]
+pub struct CreateTicketSubscription<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    /// CHECK: Authority of the LottoGame instances to play
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + std::mem::size_of::<TicketSubscription>(),
+        seeds = [
+            TicketSubscription::IDENT,
+            user_metadata.key().as_ref(),
+            authority.key().as_ref(),
+            subscription_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub ticket_subscription: Box<Account<'info, TicketSubscription>>,
+
+    /// Quote mint of the LottoGame instances to play
+    pub subscription_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = subscription_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrows the quote tokens of the subscription
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = subscription_mint,
+        associated_token::authority = ticket_subscription,
+        associated_token::token_program = token_program,
+    )]
+    pub subscription_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTicketSubscription<'info> {
+    /// `numbers` of None picks fresh numbers every round.
+    pub fn process(
+        &mut self,
+        bump: u8,
+        subscription_id: u64,
+        rounds: u64,
+        numbers: Option<LottoTicketNumbers>,
+        max_ticket_price: u64,
+        cranker_fee: u64,
+    ) -> Result<()> {
+        if rounds == 0 {
+            return Err(LollysLottoError::InvalidSubscriptionRounds.into());
+        }
+        if let Some(numbers) = numbers {
+            if !validate_for_max_min_numbers(numbers) {
+                return Err(LollysLottoError::InvalidNumbersInTicket.into());
+            }
+        }
+
+        let escrow_amount =
+            TicketSubscription::escrow_amount(rounds, max_ticket_price, cranker_fee)
+                .ok_or(LollysLottoError::MathError)?;
+        // The user pays the transfer fee on top so the escrow covers every round
+        let transfer_fee = transfer_fee_for_net_amount(
+            &self.token_program,
+            &self.subscription_mint,
+            escrow_amount,
+        )?;
+        let escrowed_amount = transfer_quote_tokens(
+            &self.token_program,
+            &self.subscription_mint,
+            &self.user_usdc_token_account,
+            &mut self.subscription_vault,
+            self.user.to_account_info(),
+            &[],
+            escrow_amount
+                .checked_add(transfer_fee)
+                .ok_or(LollysLottoError::MathError)?,
+        )?;
+
+        // Escrow the rent of every ticket as well, the cranker pays it up front
+        let ticket_rent = TicketSubscription::ticket_rent(&Rent::get()?)
+            .checked_mul(rounds)
+            .ok_or(LollysLottoError::MathError)?;
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.user.to_account_info(),
+                    to: self.ticket_subscription.to_account_info(),
+                },
+            ),
+            ticket_rent,
+        )?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        let ticket_subscription = &mut self.ticket_subscription;
+        ticket_subscription.bump = bump;
+        ticket_subscription.user = self.user.key();
+        ticket_subscription.user_metadata = self.user_metadata.key();
+        ticket_subscription.authority = self.authority.key();
+        ticket_subscription.subscription_id = subscription_id;
+        ticket_subscription.numbers = numbers.unwrap_or_default();
+        ticket_subscription.quick_pick = numbers.is_none() as u8;
+        ticket_subscription.rounds_remaining = rounds;
+        ticket_subscription.rounds_fulfilled = 0;
+        ticket_subscription.next_round = 0;
+        ticket_subscription.max_ticket_price = max_ticket_price;
+        ticket_subscription.cranker_fee = cranker_fee;
+        ticket_subscription.created_at = block_time;
+        ticket_subscription.mint = self.subscription_mint.key();
+
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::CreateTicketSubscription(CreateTicketSubscriptionEvent {
+                user: self.user.key(),
+                user_metadata: self.user_metadata.key(),
+                ticket_subscription: ticket_subscription.key(),
+                authority: self.authority.key(),
+                subscription_id,
+                numbers,
+                rounds,
+                max_ticket_price,
+                cranker_fee,
+                escrowed_amount,
+            }),
+        )?;
+
+        Ok(())
+    }
+}
@@ -54,6 +54,10 @@ impl<'info> CreateUserMetadata<'info> {
         user_metadata.total_tickets_purchased = 0;
         user_metadata.total_amount_won = 0;
         user_metadata.total_amount_claimed = 0;
+        user_metadata.claim_deadline = 0;
+        user_metadata.total_amount_expired = 0;
+        user_metadata.claim_authority = Pubkey::default();
+        user_metadata.intent_nonce = 0;
         user_metadata.last_claimed_at = 0;
         user_metadata.referral_count = 0;
         user_metadata.referral_revenue = 0;
@@ -0,0 +1,60 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollysLottoProgramEventData, MigrateAccountEvent, UserMetadata},
+    utils::migrate_account,
+};
+
+#[derive(Accounts)]
+pub struct MigrateUserMetadata<'info> {
+    /// Anyone can pay for the migration, it only appends zeroed fields
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Owner of the UserMetadata
+    pub user: AccountInfo<'info>,
+
+    /// CHECK: UserMetadata created by an earlier version of the program, which can't be
+    /// deserialized before it is migrated. Checked in `migrate_account`.
+    #[account(
+        mut,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_metadata: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserMetadata<'info> {
+    /// Grows the UserMetadata of `user` to the current layout, without a claim deadline,
+    /// expired amount, claim authority or intent nonce.
+    pub fn process(&mut self) -> Result<()> {
+        let space = 8 + std::mem::size_of::<UserMetadata>();
+        let previous_space = migrate_account::<UserMetadata>(
+            &self.user_metadata,
+            space,
+            &self.payer,
+            &self.system_program,
+        )?;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::MigrateAccount(MigrateAccountEvent {
+                account: self.user_metadata.key(),
+                payer: self.payer.key(),
+                previous_space: previous_space as u64,
+                space: space as u64,
+            }),
+        )?;
+        Ok(())
+    }
+}
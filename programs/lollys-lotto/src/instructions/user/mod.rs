@@ -1,11 +1,37 @@
+pub mod add_to_prize_pool;
 pub mod buy_lotto_ticket;
+pub mod buy_lotto_ticket_with_intent;
+pub mod buy_lotto_ticket_with_swap;
+pub mod cancel_ticket_subscription;
+pub mod claim_all_user_rewards;
+pub mod claim_stake_rewards;
 pub mod claim_user_rewards;
 pub mod close_lotto_ticket;
+pub mod close_lotto_tickets;
 pub mod close_user_metadata;
+pub mod create_stake_position;
+pub mod create_ticket_subscription;
 pub mod create_user_metadata;
+pub mod migrate_user_metadata;
+pub mod set_claim_authority;
+pub mod stake_lolly;
+pub mod unstake_lolly;
 
+pub use add_to_prize_pool::*;
 pub use buy_lotto_ticket::*;
+pub use buy_lotto_ticket_with_intent::*;
+pub use buy_lotto_ticket_with_swap::*;
+pub use cancel_ticket_subscription::*;
+pub use claim_all_user_rewards::*;
+pub use claim_stake_rewards::*;
 pub use claim_user_rewards::*;
 pub use close_lotto_ticket::*;
+pub use close_lotto_tickets::*;
 pub use close_user_metadata::*;
+pub use create_stake_position::*;
+pub use create_ticket_subscription::*;
 pub use create_user_metadata::*;
+pub use migrate_user_metadata::*;
+pub use set_claim_authority::*;
+pub use stake_lolly::*;
+pub use unstake_lolly::*;
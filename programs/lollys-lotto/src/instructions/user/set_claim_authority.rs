@@ -0,0 +1,43 @@
+pub use anchor_lang::prelude::*;
+
+use crate::{
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollysLottoProgramEventData, SetClaimAuthorityEvent, UserMetadata},
+};
+
+#[derive(Accounts)]
+pub struct SetClaimAuthority<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = user,
+        seeds = [
+            UserMetadata::IDENT,
+            user.key().as_ref(),
+        ],
+        bump = user_metadata.bump,
+    )]
+    pub user_metadata: Box<Account<'info, UserMetadata>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+}
+
+impl<'info> SetClaimAuthority<'info> {
+    /// Pubkey::default() clears the claim authority.
+    pub fn process(&mut self, claim_authority: Pubkey) -> Result<()> {
+        self.user_metadata.claim_authority = claim_authority;
+
+        let block_time = Clock::get()?.unix_timestamp;
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::SetClaimAuthority(SetClaimAuthorityEvent {
+                user: self.user.key(),
+                user_metadata: self.user_metadata.key(),
+                claim_authority,
+            }),
+        )?;
+        Ok(())
+    }
+}
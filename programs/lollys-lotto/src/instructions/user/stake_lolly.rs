@@ -0,0 +1,89 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    constants::LOLLY_MINT,
+    pda_identifier::PDAIdentifier,
+    state::{EventEmitter, LollysLottoProgramEventData, StakeLollyEvent, StakePool, StakePosition},
+};
+
+#[derive(Accounts)]
+pub struct StakeLolly<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::IDENT,
+            stake_pool.authority.as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        mut,
+        has_one = user,
+        has_one = stake_pool,
+        seeds = [
+            StakePosition::IDENT,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump = stake_position.bump,
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        associated_token::mint = LOLLY_MINT,
+        associated_token::authority = user,
+    )]
+    pub user_lolly_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = LOLLY_MINT,
+        associated_token::authority = stake_pool,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> StakeLolly<'info> {
+    /// Stakes `amount` LOLLY, which restarts the unstake cooldown of the position.
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let block_time = Clock::get()?.unix_timestamp;
+        self.stake_position
+            .stake(&mut self.stake_pool, amount, block_time)?;
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.user_lolly_token_account.to_account_info(),
+                    to: self.stake_vault.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::StakeLolly(StakeLollyEvent {
+                user: self.user.key(),
+                stake_pool: self.stake_pool.key(),
+                stake_position: self.stake_position.key(),
+                amount,
+                staked_amount: self.stake_position.staked_amount,
+                total_staked: self.stake_pool.total_staked,
+            }),
+        )?;
+        Ok(())
+    }
+}
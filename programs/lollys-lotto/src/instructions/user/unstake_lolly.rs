@@ -0,0 +1,97 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    constants::LOLLY_MINT,
+    pda_identifier::PDAIdentifier,
+    state::{
+        EventEmitter, LollysLottoProgramEventData, StakePool, StakePosition, UnstakeLollyEvent,
+    },
+};
+
+#[derive(Accounts)]
+pub struct UnstakeLolly<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::IDENT,
+            stake_pool.authority.as_ref(),
+        ],
+        bump = stake_pool.bump,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        mut,
+        has_one = user,
+        has_one = stake_pool,
+        seeds = [
+            StakePosition::IDENT,
+            stake_pool.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump = stake_position.bump,
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(
+        mut,
+        associated_token::mint = LOLLY_MINT,
+        associated_token::authority = user,
+    )]
+    pub user_lolly_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = LOLLY_MINT,
+        associated_token::authority = stake_pool,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub event_emitter: Box<Account<'info, EventEmitter>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnstakeLolly<'info> {
+    /// Unstakes `amount` LOLLY once the cooldown since the last stake is over.
+    /// Rewards earned so far stay claimable.
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let block_time = Clock::get()?.unix_timestamp;
+        self.stake_position
+            .unstake(&mut self.stake_pool, amount, block_time)?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.stake_vault.to_account_info(),
+                    to: self.user_lolly_token_account.to_account_info(),
+                    authority: self.stake_pool.to_account_info(),
+                },
+                &[&[
+                    StakePool::IDENT,
+                    self.stake_pool.authority.as_ref(),
+                    &[self.stake_pool.bump],
+                ]],
+            ),
+            amount,
+        )?;
+
+        self.event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::UnstakeLolly(UnstakeLollyEvent {
+                user: self.user.key(),
+                stake_pool: self.stake_pool.key(),
+                stake_position: self.stake_position.key(),
+                amount,
+                staked_amount: self.stake_position.staked_amount,
+                total_staked: self.stake_pool.total_staked,
+            }),
+        )?;
+        Ok(())
+    }
+}
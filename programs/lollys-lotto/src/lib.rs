@@ -8,7 +8,10 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
-use state::LottoTicketNumbers;
+use state::{
+    LottoGameVersion, LottoTicketNumbers, PaymentAsset, RandomnessProvider, StakeDiscountTier,
+    StakePool, SweepDestination, TicketPurchaseIntent,
+};
 
 declare_id!("EQHT3TFXS3hBMzSpJiKb84sHE7iBXnYBpWvQTU8r91m6");
 
@@ -49,55 +52,162 @@ pub mod lollys_lotto {
         ctx.accounts.process(ctx.bumps.lollys_lotto)
     }
 
+    pub fn create_stake_pool(
+        ctx: Context<CreateStakePool>,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    ) -> Result<()> {
+        ctx.accounts
+            .process(ctx.bumps.stake_pool, unstake_cooldown, discount_tiers)
+    }
+
+    pub fn expire_user_rewards(ctx: Context<ExpireUserRewards>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn fund_min_jackpot(ctx: Context<FundMinJackpot>, round: u64, amount: u64) -> Result<()> {
+        ctx.accounts.process(round, amount)
+    }
+
+    pub fn migrate_lollys_lotto(ctx: Context<MigrateLollysLotto>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_burn_cap(ctx: Context<SetBurnCap>, burn_cap_per_epoch: u64) -> Result<()> {
+        ctx.accounts.process(burn_cap_per_epoch)
+    }
+
+    pub fn set_lolly_payment_discount(
+        ctx: Context<SetLollyPaymentDiscount>,
+        lolly_payment_discount_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(lolly_payment_discount_bps)
+    }
+
+    pub fn set_randomness_provider(
+        ctx: Context<SetRandomnessProvider>,
+        randomness_provider: RandomnessProvider,
+    ) -> Result<()> {
+        ctx.accounts.process(randomness_provider)
+    }
+
+    pub fn set_stake_pool_config(
+        ctx: Context<SetStakePoolConfig>,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    ) -> Result<()> {
+        ctx.accounts.process(unstake_cooldown, discount_tiers)
+    }
+
+    pub fn set_swap_programs(
+        ctx: Context<SetSwapPrograms>,
+        swap_programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.process(swap_programs)
+    }
+
+    pub fn set_sweep_destination(
+        ctx: Context<SetSweepDestination>,
+        sweep_destination: SweepDestination,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(sweep_destination, treasury)
+    }
+
     pub fn start_lotto_game(
         ctx: Context<StartLottoGame>,
         round: u64,
         ticket_price: u64,
         game_duration: u64,
+        sales_close_buffer: u64,
+        claim_period: u64,
         round_name: String,
+        lotto_game_version: LottoGameVersion,
     ) -> Result<()> {
-        start_lotto_game::start_lotto_game(ctx, round, ticket_price, game_duration, round_name)
+        start_lotto_game::start_lotto_game(
+            ctx,
+            round,
+            ticket_price,
+            game_duration,
+            sales_close_buffer,
+            claim_period,
+            round_name,
+            lotto_game_version,
+        )
+    }
+
+    pub fn swap_and_burn<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapAndBurn<'info>>,
+        round: u64,
+        max_usdc_in: u64,
+        min_lolly_out: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        swap_and_burn::swap_and_burn(ctx, round, max_usdc_in, min_lolly_out, data)
     }
 
     pub fn swap_usdc_lolly<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, SwapUsdcLolly<'info>>,
+        min_lolly_out: u64,
         data: Vec<u8>,
     ) -> Result<()> {
-        swap_usdc_lolly::swap_usdc_lolly(ctx, data)
+        swap_usdc_lolly::swap_usdc_lolly(ctx, min_lolly_out, data)
+    }
+
+    pub fn sweep_lotto_game_vault(ctx: Context<SweepLottoGameVault>) -> Result<()> {
+        sweep_lotto_game_vault::sweep_lotto_game_vault(ctx)
     }
 
     // Crank instructions
 
+    pub fn crank_close_lotto_ticket_combination(
+        ctx: Context<CrankCloseLottoTicketCombination>,
+        round: u64,
+        numbers: LottoTicketNumbers,
+    ) -> Result<()> {
+        crank_close_lotto_ticket_combination::crank_close_lotto_ticket_combination(
+            ctx, round, numbers,
+        )
+    }
+
+    pub fn crank_distribute_protocol_fees(
+        ctx: Context<CrankDistributeProtocolFees>,
+        round: u64,
+    ) -> Result<()> {
+        crank_distribute_protocol_fees::crank_distribute_protocol_fees(ctx, round)
+    }
+
+    pub fn crank_expand_winning_numbers(
+        ctx: Context<CrankExpandWinningNumbers>,
+        round: u64,
+    ) -> Result<()> {
+        crank_expand_winning_numbers::crank_expand_winning_numbers(ctx, round)
+    }
+
     pub fn crank_lotto_game_closed(ctx: Context<CrankLottoGameClosed>, round: u64) -> Result<()> {
         crank_lotto_game_closed::crank_lotto_game_closed(ctx, round)
     }
 
-    pub fn crank_lotto_game_winners(
-        ctx: Context<CrankLottoGameWinners>,
+    pub fn crank_lotto_game_finished(
+        ctx: Context<CrankLottoGameFinished>,
         round: u64,
-        winning_numbers: LottoTicketNumbers,
-        winning_numbers_index: [i64; 4],
     ) -> Result<()> {
-        crank_lotto_game_winners::crank_lotto_game_winners(
-            ctx,
-            round,
-            winning_numbers,
-            winning_numbers_index,
-        )
+        crank_lotto_game_finished::crank_lotto_game_finished(ctx, round)
     }
 
-    pub fn crank_transfer_winning_amount_to_user_rewards_vault(
-        ctx: Context<CrankTransferWinningAmountToUserRewardsVault>,
+    pub fn crank_rerequest_winning_numbers(
+        ctx: Context<CrankRerequestWinningNumbers>,
+        round: u64,
+    ) -> Result<()> {
+        crank_rerequest_winning_numbers::crank_rerequest_winning_numbers(ctx, round)
+    }
+
+    pub fn crank_settle_winning_ticket(
+        ctx: Context<CrankSettleWinningTicket>,
         round: u64,
         winning_numbers: LottoTicketNumbers,
-        number_of_tickets_with_duplicate_numbers: u32,
     ) -> Result<()> {
-        crank_transfer_winning_amount_to_user_rewards_vault::crank_transfer_winning_amount_to_user_rewards_vault(
-            ctx,
-            round,
-            winning_numbers,
-            number_of_tickets_with_duplicate_numbers,
-        )
+        crank_settle_winning_ticket::crank_settle_winning_ticket(ctx, round, winning_numbers)
     }
 
     pub fn crank_transfer_to_buy_and_burn_vault(
@@ -107,7 +217,26 @@ pub mod lollys_lotto {
         crank_transfer_to_buy_and_burn_vault::crank_transfer_to_buy_and_burn_vault(ctx, round)
     }
 
+    pub fn crank_fulfil_subscription(
+        ctx: Context<CrankFulfilSubscription>,
+        round: u64,
+        numbers: LottoTicketNumbers,
+    ) -> Result<()> {
+        crank_fulfil_subscription::crank_fulfil_subscription(ctx, round, numbers)
+    }
+
+    pub fn verify_lotto_game_solvency(
+        ctx: Context<VerifyLottoGameSolvency>,
+        round: u64,
+    ) -> Result<()> {
+        verify_lotto_game_solvency::verify_lotto_game_solvency(ctx, round)
+    }
+
     // Switchboard instructions
+    pub fn commit_winning_numbers(ctx: Context<CommitWinningNumbers>) -> Result<()> {
+        commit_winning_numbers::commit_winning_numbers(ctx)
+    }
+
     pub fn process_winning_numbers(
         ctx: Context<ProcessWinningNumbers>,
         result: Vec<u8>,
@@ -119,6 +248,10 @@ pub mod lollys_lotto {
         request_winning_numbers::request_winning_numbers(ctx)
     }
 
+    pub fn reveal_winning_numbers(ctx: Context<RevealWinningNumbers>) -> Result<()> {
+        reveal_winning_numbers::reveal_winning_numbers(ctx)
+    }
+
     pub fn test_emit_winning_numbers(
         ctx: Context<TestEmitWinningNumbers>,
         result: Vec<u8>,
@@ -127,12 +260,60 @@ pub mod lollys_lotto {
     }
 
     // User instructions
+    pub fn add_to_prize_pool(
+        ctx: Context<AddToPrizePool>,
+        round: u64,
+        tier: u8,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(round, tier, amount)
+    }
+
     pub fn buy_lotto_ticket(
         ctx: Context<BuyLottoTicket>,
         round: u64,
         numbers: LottoTicketNumbers,
     ) -> Result<()> {
-        ctx.accounts.process(round, numbers)
+        ctx.accounts
+            .process(ctx.bumps.lotto_ticket_combination, round, numbers)
+    }
+
+    pub fn buy_lotto_ticket_with_intent(
+        ctx: Context<BuyLottoTicketWithIntent>,
+        intent: TicketPurchaseIntent,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(ctx.bumps.lotto_ticket_combination, intent)
+    }
+
+    pub fn buy_lotto_ticket_with_swap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, BuyLottoTicketWithSwap<'info>>,
+        round: u64,
+        numbers: LottoTicketNumbers,
+        payment_asset: PaymentAsset,
+        max_amount_in: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        buy_lotto_ticket_with_swap::buy_lotto_ticket_with_swap(
+            ctx,
+            round,
+            numbers,
+            payment_asset,
+            max_amount_in,
+            data,
+        )
+    }
+
+    pub fn cancel_ticket_subscription(ctx: Context<CancelTicketSubscription>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_all_user_rewards(ctx: Context<ClaimAllUserRewards>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_stake_rewards(ctx: Context<ClaimStakeRewards>) -> Result<()> {
+        ctx.accounts.process()
     }
 
     pub fn claim_user_rewards(
@@ -150,11 +331,58 @@ pub mod lollys_lotto {
         ctx.accounts.process(round, numbers)
     }
 
+    pub fn close_lotto_tickets<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseLottoTickets<'info>>,
+    ) -> Result<()> {
+        close_lotto_tickets::close_lotto_tickets(ctx)
+    }
+
     pub fn close_user_metadata(ctx: Context<CloseUserMetadata>) -> Result<()> {
         ctx.accounts.process()
     }
 
+    pub fn create_stake_position(ctx: Context<CreateStakePosition>) -> Result<()> {
+        ctx.accounts.process(ctx.bumps.stake_position)
+    }
+
+    pub fn create_ticket_subscription(
+        ctx: Context<CreateTicketSubscription>,
+        subscription_id: u64,
+        rounds: u64,
+        numbers: Option<LottoTicketNumbers>,
+        max_ticket_price: u64,
+        cranker_fee: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            ctx.bumps.ticket_subscription,
+            subscription_id,
+            rounds,
+            numbers,
+            max_ticket_price,
+            cranker_fee,
+        )
+    }
+
     pub fn create_user_metadata(ctx: Context<CreateUserMetadata>) -> Result<()> {
         ctx.accounts.process(ctx.bumps.user_metadata)
     }
+
+    pub fn migrate_user_metadata(ctx: Context<MigrateUserMetadata>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_claim_authority(
+        ctx: Context<SetClaimAuthority>,
+        claim_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(claim_authority)
+    }
+
+    pub fn stake_lolly(ctx: Context<StakeLolly>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    pub fn unstake_lolly(ctx: Context<UnstakeLolly>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
 }
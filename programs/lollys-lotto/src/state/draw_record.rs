@@ -0,0 +1,135 @@
+use crate::errors::LollysLottoError;
+use crate::pda_identifier::PDAIdentifier;
+use anchor_lang::prelude::*;
+
+use super::RandomnessProvider;
+
+/// Auditable record of how the winning numbers of a round/LottoGame instance were drawn.
+/// Unlike the event logs, this survives RPC history pruning.
+#[account]
+#[derive(Debug)]
+pub struct DrawRecord {
+    pub bump: u8,
+    /// The LottoGame instance this record belongs to.
+    pub lotto_game: Pubkey,
+    /// The round number of the LottoGame instance this record belongs to.
+    pub round: u64,
+    /// The number of randomness requests made for this round.
+    pub request_count: u64,
+    /// The latest `MAX_REQUESTS` randomness requests made for this round, as a ring buffer:
+    /// request `n` is kept at `n % MAX_REQUESTS` until it is overwritten by a later request.
+    pub requests: [DrawRecordRequest; DrawRecord::MAX_REQUESTS],
+    /// The slot in which the randomness was fully mapped into the winning numbers.
+    pub mapped_slot: u64,
+    /// The number of combinations derived from the randomness seed to fill the winning numbers.
+    pub winning_numbers_nonce: u64,
+}
+
+impl DrawRecord {
+    pub const MAX_REQUESTS: usize = 16;
+    pub const SIZE: usize =
+        1 + 32 + 8 + 8 + DrawRecordRequest::SIZE * DrawRecord::MAX_REQUESTS + 8 + 8;
+
+    pub fn address(lotto_game: Pubkey) -> Pubkey {
+        Self::get_address(&[lotto_game.as_ref()])
+    }
+
+    pub fn address_with_bump(lotto_game: Pubkey) -> (Pubkey, u8) {
+        Self::get_address_with_bump(&[lotto_game.as_ref()])
+    }
+
+    /// Records a new randomness request, overwriting the oldest one once `requests` is full.
+    /// Only expired requests get overwritten, as no request is made once one is fulfilled.
+    pub fn record_request(
+        &mut self,
+        randomness_provider: RandomnessProvider,
+        randomness_request: Pubkey,
+        requested_slot: u64,
+        requested_at: i64,
+    ) -> Result<()> {
+        let index = (self.request_count % DrawRecord::MAX_REQUESTS as u64) as usize;
+        self.requests[index] = DrawRecordRequest {
+            randomness_provider,
+            randomness_request,
+            requested_slot,
+            requested_at,
+            ..DrawRecordRequest::default()
+        };
+        self.request_count = self
+            .request_count
+            .checked_add(1)
+            .ok_or(LollysLottoError::OverflowError)?;
+        Ok(())
+    }
+
+    /// Stores the raw randomness received for `randomness_request`.
+    pub fn record_randomness(
+        &mut self,
+        randomness_request: Pubkey,
+        randomness: &[u8],
+        received_slot: u64,
+    ) -> Result<()> {
+        let kept_requests = self.kept_requests();
+        let request = self.requests[..kept_requests]
+            .iter_mut()
+            .rev()
+            .find(|request| request.randomness_request == randomness_request)
+            .ok_or(LollysLottoError::InvalidRandomnessRequest)?;
+        if randomness.len() != request.randomness.len() {
+            return Err(LollysLottoError::InvalidRandomnessLength.into());
+        }
+        request.randomness.copy_from_slice(randomness);
+        request.received_slot = received_slot;
+        request.fulfilled = 1;
+        Ok(())
+    }
+
+    /// Marks the draw as fully mapped into the LottoGame winning numbers.
+    pub fn record_mapped(&mut self, mapped_slot: u64, winning_numbers_nonce: u64) {
+        self.mapped_slot = mapped_slot;
+        self.winning_numbers_nonce = winning_numbers_nonce;
+    }
+
+    /// The request the winning numbers were drawn from, if any.
+    pub fn fulfilled_request(&self) -> Option<&DrawRecordRequest> {
+        self.requests[..self.kept_requests()]
+            .iter()
+            .find(|request| request.fulfilled != 0)
+    }
+
+    /// The number of entries used in `requests`.
+    fn kept_requests(&self) -> usize {
+        self.request_count.min(DrawRecord::MAX_REQUESTS as u64) as usize
+    }
+}
+
+impl PDAIdentifier for DrawRecord {
+    const IDENT: &'static [u8] = b"draw-record";
+
+    fn program_id() -> &'static Pubkey {
+        &crate::ID
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DrawRecordRequest {
+    /// The provider the randomness was requested from.
+    pub randomness_provider: RandomnessProvider,
+    /// The Solana Randomness Service request account or the Switchboard On-Demand
+    /// randomness account committed to.
+    pub randomness_request: Pubkey,
+    /// The slot the request was made at.
+    pub requested_slot: u64,
+    /// The timestamp the request was made at.
+    pub requested_at: i64,
+    /// The raw randomness bytes received.
+    pub randomness: [u8; 32],
+    /// The slot the randomness was received at.
+    pub received_slot: u64,
+    /// Indication if the randomness was received. 0 = pending or expired, 1 = received.
+    pub fulfilled: u8,
+}
+
+impl DrawRecordRequest {
+    pub const SIZE: usize = 1 + 32 + 8 + 8 + 32 + 8 + 1;
+}
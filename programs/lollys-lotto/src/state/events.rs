@@ -1,4 +1,7 @@
-use super::{LottoGameState, LottoGameVersion, LottoTicketNumbers};
+use super::{
+    LottoGameLiabilities, LottoGameState, LottoGameVersion, LottoTicketNumbers, PaymentAsset,
+    RandomnessProvider, RoundBurn, StakeDiscountTier, StakePool, SweepDestination,
+};
 use crate::pda_identifier::PDAIdentifier;
 use anchor_lang::prelude::*;
 
@@ -67,11 +70,14 @@ pub enum ProgramInstruction {
     CloseLottoGame,
     CreateLollyBurnState,
     CreateLollysLotto,
+    SetRandomnessProvider,
     StartLottoGame,
     SwapUsdcLolly,
     // switchboard
+    CommitWinningNumbers,
     ProcessWinningNumbers,
     RequestWinningNumbers,
+    RevealWinningNumbers,
     TestEmitWinningNumbers,
     // user
     BuyLottoTicket,
@@ -84,6 +90,38 @@ pub enum ProgramInstruction {
     CrankLottoGameWinners,
     CrankTransferWinningAmountToUserRewardsVault,
     CrankTransferToBuyAndBurnVault,
+    CrankExpandWinningNumbers,
+    CrankRerequestWinningNumbers,
+    CrankLottoGameFinished,
+    CrankSettleWinningTicket,
+    VerifyLottoGameSolvency,
+    SetSweepDestination,
+    SweepLottoGameVault,
+    ExpireUserRewards,
+    CloseLottoTickets,
+    ClaimAllUserRewards,
+    SetClaimAuthority,
+    BuyLottoTicketWithIntent,
+    CreateTicketSubscription,
+    CrankFulfilSubscription,
+    CancelTicketSubscription,
+    SetSwapPrograms,
+    SwapAndBurn,
+    SetBurnCap,
+    CreateStakePool,
+    SetStakePoolConfig,
+    CreateStakePosition,
+    StakeLolly,
+    UnstakeLolly,
+    ClaimStakeRewards,
+    CrankDistributeProtocolFees,
+    BuyLottoTicketWithSwap,
+    SetLollyPaymentDiscount,
+    AddToPrizePool,
+    FundMinJackpot,
+    CrankCloseLottoTicketCombination,
+    MigrateLollysLotto,
+    MigrateUserMetadata,
 }
 
 /// Created with `EventSigner::new_event()`.
@@ -135,6 +173,41 @@ pub enum LollysLottoProgramEventData {
 
     // Add new events here
     DuplicateWinningNumbers(DuplicateWinningNumbersEvent),
+    ExpandWinningNumbers(ExpandWinningNumbersEvent),
+    SetRandomnessProvider(SetRandomnessProviderEvent),
+    CommitWinningNumbers(CommitWinningNumbersEvent),
+    WinningNumbersRequestExpired(WinningNumbersRequestExpiredEvent),
+    LottoGameStateTransition(LottoGameStateTransitionEvent),
+    CrankSettleWinningTicket(CrankSettleWinningTicketEvent),
+    LottoGameSolvency(LottoGameSolvencyEvent),
+    SetSweepDestination(SetSweepDestinationEvent),
+    SweepLottoGameVault(SweepLottoGameVaultEvent),
+    ExpireUserRewards(ExpireUserRewardsEvent),
+    ClaimAllUserRewards(ClaimAllUserRewardsEvent),
+    SetClaimAuthority(SetClaimAuthorityEvent),
+    BuyLottoTicketWithIntent(BuyLottoTicketWithIntentEvent),
+    CreateTicketSubscription(CreateTicketSubscriptionEvent),
+    CrankFulfilSubscription(CrankFulfilSubscriptionEvent),
+    CancelTicketSubscription(CancelTicketSubscriptionEvent),
+    SetSwapPrograms(SetSwapProgramsEvent),
+    SwapAndBurn(SwapAndBurnEvent),
+    SetBurnCap(SetBurnCapEvent),
+    CreateStakePool(CreateStakePoolEvent),
+    SetStakePoolConfig(SetStakePoolConfigEvent),
+    CreateStakePosition(CreateStakePositionEvent),
+    StakeLolly(StakeLollyEvent),
+    UnstakeLolly(UnstakeLollyEvent),
+    ClaimStakeRewards(ClaimStakeRewardsEvent),
+    CrankDistributeProtocolFees(CrankDistributeProtocolFeesEvent),
+    BuyLottoTicketWithSwap(BuyLottoTicketWithSwapEvent),
+    SetLollyPaymentDiscount(SetLollyPaymentDiscountEvent),
+    SponsorPrizePool(SponsorPrizePoolEvent),
+    FundMinJackpot(FundMinJackpotEvent),
+    RefundMinJackpot(RefundMinJackpotEvent),
+    DrawRaffleWinner(DrawRaffleWinnerEvent),
+    TransferRoundShareToTreasury(TransferRoundShareToTreasuryEvent),
+    CloseLottoTicketCombination(CloseLottoTicketCombinationEvent),
+    MigrateAccount(MigrateAccountEvent),
 }
 
 /// Event emitted when a user burns $LOLLY tokens.
@@ -188,6 +261,57 @@ pub struct CreateLollysLottoEvent {
     pub lotto_game_count: u64,
 }
 
+/// Event emitted on every LottoGame lifecycle state change.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct LottoGameStateTransitionEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub previous_state: LottoGameState,
+    pub new_state: LottoGameState,
+}
+
+/// Event emitted when the authority changes the randomness provider.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetRandomnessProviderEvent {
+    pub lollys_lotto: Pubkey,
+    pub previous_randomness_provider: RandomnessProvider,
+    pub randomness_provider: RandomnessProvider,
+}
+
+/// Event emitted when the authority changes where residual LottoGame vault funds are swept to.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetSweepDestinationEvent {
+    pub lollys_lotto: Pubkey,
+    pub sweep_destination: SweepDestination,
+    pub treasury: Pubkey,
+}
+
+/// Event emitted when the residual funds of a finished LottoGame are swept and the LottoGame is closed.
+/// `unclaimed` holds the liabilities nobody collected, `dust` the rounding residue on top of them.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SweepLottoGameVaultEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub sweep_destination: SweepDestination,
+    pub destination_vault: Pubkey,
+    pub swept_amount: u64,
+    pub unclaimed: LottoGameLiabilities,
+    pub dust: u64,
+}
+
+/// Event emitted when the rewards a user left unclaimed past their claim deadline are swept.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExpireUserRewardsEvent {
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    pub user_rewards_vault: Pubkey,
+    pub sweep_destination: SweepDestination,
+    pub destination_vault: Pubkey,
+    pub claim_deadline: i64,
+    pub expired_amount: u64,
+    pub total_amount_expired: u64,
+}
+
 /// Event emitted when a user starts a lotto game.
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct StartLottoGameEvent {
@@ -195,6 +319,8 @@ pub struct StartLottoGameEvent {
     pub round: u64,
     pub round_name: String,
     pub game_duration: u64,
+    pub sales_close_buffer: u64,
+    pub claim_deadline: i64,
     pub authority: Pubkey,
     pub lotto_game_pubkey: Pubkey,
     pub lotto_game_vault: Pubkey,
@@ -211,6 +337,41 @@ pub struct StartLottoGameEvent {
 pub struct SwapUsdcLollyEvent {
     pub authority: Pubkey,
     pub lolly_burn_state: Pubkey,
+    pub usdc_spent: u64,
+    pub lolly_acquired: u64,
+    pub min_lolly_out: u64,
+    pub total_usdc_swapped: u64,
+    pub total_lolly_acquired: u64,
+}
+
+/// Event emitted when the authority replaces the swap program allowlist of a LollyBurnState.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetSwapProgramsEvent {
+    pub authority: Pubkey,
+    pub lolly_burn_state: Pubkey,
+    pub swap_programs: Vec<Pubkey>,
+}
+
+/// Event emitted when the buy and burn share of a round is swapped to LOLLY and burnt.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SwapAndBurnEvent {
+    pub authority: Pubkey,
+    pub lolly_burn_state: Pubkey,
+    pub round: u64,
+    pub usdc_in: u64,
+    pub lolly_out: u64,
+    pub lolly_burnt: u64,
+    pub total_lolly_burnt: u64,
+    /// Buy and burn totals of the round after this burn.
+    pub round_burn: RoundBurn,
+}
+
+/// Event emitted when the authority sets the per-epoch burn cap of a LollyBurnState.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetBurnCapEvent {
+    pub authority: Pubkey,
+    pub lolly_burn_state: Pubkey,
+    pub burn_cap_per_epoch: u64,
 }
 
 /// Event emitted when a user updates a lotto game.
@@ -245,12 +406,46 @@ pub struct DuplicateWinningNumbersEvent {
     pub duplicate_number_detected_index: [i64; 4],
 }
 
+/// Event emitted when winning numbers are expanded from the randomness seed.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExpandWinningNumbersEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub seed: [u8; 32],
+    pub start_nonce: u64,
+    pub end_nonce: u64,
+    pub winning_numbers_added: u16,
+    pub duplicates_skipped: u16,
+    pub winning_numbers_filled: u16,
+}
+
+/// Event emitted when a Switchboard On-Demand randomness account is committed to.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CommitWinningNumbersEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+}
+
 /// Event emitted when a user requests randomness.
 
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct RequestWinningNumbersEvent {
     pub lotto_game: Pubkey,
     pub round: u64,
+    pub randomness_request: Pubkey,
+    pub requested_at: i64,
+}
+
+/// Event emitted when a pending randomness request times out and is replaced.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WinningNumbersRequestExpiredEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub expired_request: Pubkey,
+    pub requested_at: i64,
+    pub expired_at: i64,
 }
 
 /// Test Event emitted when a user requests randomness.
@@ -273,10 +468,30 @@ pub struct BuyLottoTicketEvent {
     pub round: u64,
     pub ticket_number: u64,
     pub numbers: LottoTicketNumbers,
+    /// Price paid in USDC, after the staker or LOLLY payment discount.
     pub ticket_price: u64,
+    pub discount: u64,
+    /// Withheld by a Token-2022 transfer fee of the LottoGame mint, the vault is credited the rest.
+    pub transfer_fee: u64,
+    pub payment_asset: PaymentAsset,
     pub buy_date: i64,
 }
 
+/// Event emitted alongside `BuyLottoTicketEvent` when a relayer submits a signed purchase intent.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct BuyLottoTicketWithIntentEvent {
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    pub relayer: Pubkey,
+    pub relayer_usdc_token_account: Pubkey,
+    pub lotto_game: Pubkey,
+    pub lotto_ticket: Pubkey,
+    pub round: u64,
+    pub intent_nonce: u64,
+    pub ticket_price: u64,
+    pub relayer_fee: u64,
+}
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct ClaimUserRewardsEvent {
     pub user: Pubkey,
@@ -286,6 +501,26 @@ pub struct ClaimUserRewardsEvent {
     pub total_amount_claimed: u64,
 }
 
+/// Event emitted when the user or their claim authority claims all unclaimed rewards.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimAllUserRewardsEvent {
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    pub claimant: Pubkey,
+    pub user_rewards_vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount_claimed: u64,
+    pub total_amount_claimed: u64,
+}
+
+/// Event emitted when a user sets or clears the delegate allowed to claim their rewards.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetClaimAuthorityEvent {
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    pub claim_authority: Pubkey,
+}
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct CloseLottoTicketEvent {
     pub round: u64,
@@ -314,7 +549,8 @@ pub struct CreateUserMetadataEvent {
 pub struct CrankLottoGameWinnersEvent {
     pub round: u64,
     pub winning_numbers: LottoTicketNumbers,
-    pub winning_numbers_index: [i64; 4],
+    pub winning_tier: u8,
+    pub winning_number_index: u64,
     pub winning_user: Pubkey,
     pub lotto_ticket: Pubkey,
     pub lotto_game: Pubkey,
@@ -330,6 +566,36 @@ pub struct CrankTransferWinningAmountToUserRewardsVaultEvent {
     pub user: Pubkey,
     pub lotto_ticket: Pubkey,
     pub winning_amount: u64,
+    pub claim_deadline: i64,
+}
+
+/// Event emitted when admin crank declares a winning ticket and transfers its prize
+/// to the user rewards vault in a single instruction.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CrankSettleWinningTicketEvent {
+    pub round: u64,
+    pub winning_numbers: LottoTicketNumbers,
+    pub winning_tier: u8,
+    pub winning_number_index: u64,
+    pub number_of_tickets_with_duplicate_numbers: u32,
+    pub lotto_game: Pubkey,
+    pub user: Pubkey,
+    pub lotto_ticket: Pubkey,
+    pub winning_amount: u64,
+    pub claim_deadline: i64,
+}
+
+/// Event emitted when the LottoGame vault balance is checked against its outstanding liabilities.
+/// `shortfall` is zero while the vault is solvent.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct LottoGameSolvencyEvent {
+    pub round: u64,
+    pub lotto_game: Pubkey,
+    pub lotto_game_vault: Pubkey,
+    pub vault_amount: u64,
+    pub liabilities: LottoGameLiabilities,
+    pub total_liabilities: u64,
+    pub shortfall: u64,
 }
 
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -340,4 +606,219 @@ pub struct CrankTransferToBuyAndBurnVaultEvent {
     pub lolly_burn_state_usdc_vault: Pubkey,
     pub lotto_game_vault: Pubkey,
     pub buy_and_burn_amount: u64,
+    /// Buy and burn totals of the round after this transfer.
+    pub round_burn: RoundBurn,
+}
+
+/// Event emitted when a user escrows USDC to play the next rounds.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CreateTicketSubscriptionEvent {
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    pub ticket_subscription: Pubkey,
+    pub authority: Pubkey,
+    pub subscription_id: u64,
+    pub numbers: Option<LottoTicketNumbers>,
+    pub rounds: u64,
+    pub max_ticket_price: u64,
+    pub cranker_fee: u64,
+    pub escrowed_amount: u64,
+}
+
+/// Event emitted alongside `BuyLottoTicketEvent` when a subscription ticket is bought.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CrankFulfilSubscriptionEvent {
+    pub user: Pubkey,
+    pub ticket_subscription: Pubkey,
+    pub cranker: Pubkey,
+    pub lotto_game: Pubkey,
+    pub lotto_ticket: Pubkey,
+    pub round: u64,
+    pub numbers: LottoTicketNumbers,
+    pub ticket_price: u64,
+    pub cranker_fee: u64,
+    pub rounds_remaining: u64,
+}
+
+/// Event emitted when a user cancels a subscription and withdraws the remaining escrow.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CancelTicketSubscriptionEvent {
+    pub user: Pubkey,
+    pub ticket_subscription: Pubkey,
+    pub rounds_fulfilled: u64,
+    pub rounds_remaining: u64,
+    pub withdrawn_amount: u64,
+}
+
+/// Event emitted when the authority creates its StakePool.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CreateStakePoolEvent {
+    pub authority: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stake_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    pub unstake_cooldown: i64,
+    pub discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+}
+
+/// Event emitted when the authority changes the cooldown or discount tiers of its StakePool.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetStakePoolConfigEvent {
+    pub authority: Pubkey,
+    pub stake_pool: Pubkey,
+    pub unstake_cooldown: i64,
+    pub discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+}
+
+/// Event emitted when a user opens a StakePosition.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CreateStakePositionEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stake_position: Pubkey,
+}
+
+/// Event emitted when a user stakes LOLLY.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct StakeLollyEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stake_position: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub total_staked: u64,
+}
+
+/// Event emitted when a user unstakes LOLLY.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeLollyEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stake_position: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub total_staked: u64,
+}
+
+/// Event emitted when a user claims the USDC rewards of a StakePosition.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimStakeRewardsEvent {
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub stake_position: Pubkey,
+    pub amount: u64,
+    pub total_rewards_claimed: u64,
+}
+
+/// Event emitted when the protocol fees of a round are distributed to stakers.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CrankDistributeProtocolFeesEvent {
+    pub round: u64,
+    pub lotto_game: Pubkey,
+    pub stake_pool: Pubkey,
+    pub protocol_fees_amount: u64,
+    pub total_staked: u64,
+    pub reward_per_share: u128,
+}
+
+/// Event emitted alongside `BuyLottoTicketEvent` when a ticket is paid with SOL or LOLLY.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct BuyLottoTicketWithSwapEvent {
+    pub user: Pubkey,
+    pub lotto_game: Pubkey,
+    pub lotto_ticket: Pubkey,
+    pub round: u64,
+    pub payment_asset: PaymentAsset,
+    /// SOL or LOLLY spent by the swap.
+    pub amount_in: u64,
+    /// USDC received from the swap, anything above the ticket price stays with the user.
+    pub usdc_out: u64,
+    pub ticket_price: u64,
+    pub discount: u64,
+}
+
+/// Event emitted when the authority sets the discount for tickets paid with LOLLY.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetLollyPaymentDiscountEvent {
+    pub authority: Pubkey,
+    pub lollys_lotto: Pubkey,
+    pub lolly_payment_discount_bps: u16,
+}
+
+/// Event emitted when a sponsor tops up the prize pool of a LottoGame tier.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SponsorPrizePoolEvent {
+    pub sponsor: Pubkey,
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    /// Always 0, only the jackpot can be topped up.
+    pub tier: u8,
+    pub amount: u64,
+    /// Amount credited to the LottoGameVault, net of any transfer fee.
+    pub amount_received: u64,
+    /// Total sponsored so far for `tier` of this round.
+    pub sponsored_prize_pool: u64,
+}
+
+/// Event emitted when the treasury funds the minimum jackpot of a LottoGame.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct FundMinJackpotEvent {
+    pub treasury: Pubkey,
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub amount: u64,
+    /// Minimum jackpot of the round, the amount credited to the LottoGameVault net of any transfer fee.
+    pub min_jackpot: u64,
+}
+
+/// Event emitted when the part of the minimum jackpot the round did not need goes back to the treasury.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RefundMinJackpotEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub treasury_token_account: Pubkey,
+    pub min_jackpot: u64,
+    pub refunded_amount: u64,
+}
+
+/// Event emitted when the winning ticket number of a raffle is drawn.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DrawRaffleWinnerEvent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub seed: [u8; 32],
+    pub tickets_sold: u64,
+    pub winning_ticket_number: u64,
+}
+
+/// Event emitted when the protocol fees or the buy and burn share of a round outside USDC go to
+/// the treasury, as stakers and the buy and burn only take USDC.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TransferRoundShareToTreasuryEvent {
+    pub round: u64,
+    pub lotto_game: Pubkey,
+    pub lotto_game_mint: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub protocol_fees_amount: u64,
+    pub buy_and_burn_amount: u64,
+}
+
+/// Event emitted when the LottoTicketCombination of a finished round is closed and its rent refunded.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CloseLottoTicketCombinationEvent {
+    pub round: u64,
+    pub numbers: LottoTicketNumbers,
+    pub lotto_game: Pubkey,
+    pub lotto_ticket_combination: Pubkey,
+    pub payer: Pubkey,
+    pub holders: u32,
+}
+
+/// Event emitted when an account created by an earlier version of the program is grown to the current layout.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateAccountEvent {
+    pub account: Pubkey,
+    pub payer: Pubkey,
+    pub previous_space: u64,
+    pub space: u64,
 }
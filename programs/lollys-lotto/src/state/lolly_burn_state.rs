@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::get_associated_token_address;
 
 use crate::{
-    constants::{LOLLY_MINT, USDC_MINT_DEVNET},
+    constants::{JUPITER_V6_PROGRAM_ID, LOLLY_MINT, USDC_MINT_DEVNET},
+    errors::LollysLottoError,
     pda_identifier::PDAIdentifier,
 };
 
@@ -11,6 +12,32 @@ pub struct LollyBurnState {
     pub bump: u8,
     pub total_lolly_burnt: u64,
     pub authority: Pubkey,
+    /// Cumulative USDC spent by `swap_usdc_lolly`.
+    pub total_usdc_swapped: u64,
+    /// Cumulative LOLLY received by `swap_usdc_lolly`.
+    pub total_lolly_acquired: u64,
+    /// Programs `swap_usdc_lolly` may route through. Pubkey::default() = empty slot.
+    pub swap_programs: [Pubkey; LollyBurnState::MAX_SWAP_PROGRAMS],
+    /// Maximum LOLLY burnt per Solana epoch, 0 = uncapped.
+    pub burn_cap_per_epoch: u64,
+    /// Epoch `lolly_burnt_in_epoch` is counted for.
+    pub burn_epoch: u64,
+    /// LOLLY burnt during `burn_epoch`.
+    pub lolly_burnt_in_epoch: u64,
+    /// Ring buffer of the buy and burn flow of the latest rounds, indexed by `round % ROUND_BURNS_LEN`.
+    pub round_burns: [RoundBurn; LollyBurnState::ROUND_BURNS_LEN],
+}
+
+/// Buy and burn flow attributed to one round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RoundBurn {
+    pub round: u64,
+    /// USDC received from the round by `crank_transfer_to_buy_and_burn_vault`.
+    pub usdc_received: u64,
+    /// USDC swapped by `swap_and_burn` for the round.
+    pub usdc_swapped: u64,
+    /// LOLLY burnt by `swap_and_burn` for the round.
+    pub lolly_burnt: u64,
 }
 
 impl PDAIdentifier for LollyBurnState {
@@ -22,6 +49,9 @@ impl PDAIdentifier for LollyBurnState {
 }
 
 impl LollyBurnState {
+    pub const MAX_SWAP_PROGRAMS: usize = 4;
+    pub const ROUND_BURNS_LEN: usize = 16;
+
     pub fn signer_address(authority: Pubkey) -> Pubkey {
         Self::get_address(&[authority.as_ref()])
     }
@@ -40,4 +70,125 @@ impl LollyBurnState {
     pub fn lolly_vault(authority: Pubkey) -> Pubkey {
         get_associated_token_address(&Self::signer_address(authority), &LOLLY_MINT)
     }
+
+    pub fn default_swap_programs() -> [Pubkey; Self::MAX_SWAP_PROGRAMS] {
+        let mut swap_programs = [Pubkey::default(); Self::MAX_SWAP_PROGRAMS];
+        swap_programs[0] = JUPITER_V6_PROGRAM_ID;
+        swap_programs
+    }
+
+    pub fn is_swap_program_allowed(&self, swap_program: Pubkey) -> bool {
+        swap_program != Pubkey::default() && self.swap_programs.contains(&swap_program)
+    }
+
+    pub fn set_swap_programs(&mut self, swap_programs: &[Pubkey]) -> Result<()> {
+        // This program can't be its own swap program, the swap is signed by the LollyBurnState PDA
+        if swap_programs.len() > Self::MAX_SWAP_PROGRAMS
+            || swap_programs.iter().any(|swap_program| {
+                *swap_program == crate::ID || *swap_program == Pubkey::default()
+            })
+        {
+            return Err(LollysLottoError::InvalidSwapProgramAllowlist.into());
+        }
+        self.swap_programs = [Pubkey::default(); Self::MAX_SWAP_PROGRAMS];
+        self.swap_programs[..swap_programs.len()].copy_from_slice(swap_programs);
+        Ok(())
+    }
+
+    /// Records a swap from the vault balances around it, failing if it returned less than `min_lolly_out`.
+    /// Returns the USDC spent and the LOLLY acquired.
+    pub fn record_swap(
+        &mut self,
+        usdc_before: u64,
+        usdc_after: u64,
+        lolly_before: u64,
+        lolly_after: u64,
+        min_lolly_out: u64,
+    ) -> Result<(u64, u64)> {
+        // The swap must take USDC from the USDC vault and only add LOLLY to the LOLLY vault
+        let usdc_spent = usdc_before
+            .checked_sub(usdc_after)
+            .filter(|usdc_spent| *usdc_spent > 0)
+            .ok_or(LollysLottoError::InvalidSwapBalanceChange)?;
+        let lolly_acquired = lolly_after
+            .checked_sub(lolly_before)
+            .ok_or(LollysLottoError::InvalidSwapBalanceChange)?;
+        if lolly_acquired < min_lolly_out {
+            return Err(LollysLottoError::SwapSlippageExceeded.into());
+        }
+        self.total_usdc_swapped += usdc_spent;
+        self.total_lolly_acquired += lolly_acquired;
+        Ok((usdc_spent, lolly_acquired))
+    }
+
+    /// Counts `amount` against the burn cap of `epoch`, failing if the cap would be exceeded.
+    pub fn record_burn(&mut self, amount: u64, epoch: u64) -> Result<()> {
+        if epoch != self.burn_epoch {
+            self.burn_epoch = epoch;
+            self.lolly_burnt_in_epoch = 0;
+        }
+        let lolly_burnt_in_epoch = self
+            .lolly_burnt_in_epoch
+            .checked_add(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        if self.burn_cap_per_epoch != 0 && lolly_burnt_in_epoch > self.burn_cap_per_epoch {
+            return Err(LollysLottoError::BurnCapExceeded.into());
+        }
+        self.lolly_burnt_in_epoch = lolly_burnt_in_epoch;
+        self.total_lolly_burnt = self
+            .total_lolly_burnt
+            .checked_add(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        Ok(())
+    }
+
+    /// Tracked buy and burn flow of `round`, unless a later round took its slot.
+    pub fn round_burn(&self, round: u64) -> Option<&RoundBurn> {
+        let round_burn = &self.round_burns[Self::round_burn_slot(round)];
+        (round_burn.round == round).then_some(round_burn)
+    }
+
+    /// Adds a buy and burn flow to `round`, taking over the slot of an older round.
+    /// Returns the totals of the round, or only this flow if a later round holds the slot.
+    pub fn record_round_burn(
+        &mut self,
+        round: u64,
+        usdc_received: u64,
+        usdc_swapped: u64,
+        lolly_burnt: u64,
+    ) -> Result<RoundBurn> {
+        let flow = RoundBurn {
+            round,
+            usdc_received,
+            usdc_swapped,
+            lolly_burnt,
+        };
+        let round_burn = &mut self.round_burns[Self::round_burn_slot(round)];
+        if round_burn.round != round {
+            if round_burn.round > round {
+                return Ok(flow);
+            }
+            *round_burn = RoundBurn {
+                round,
+                ..Default::default()
+            };
+        }
+        round_burn.usdc_received = round_burn
+            .usdc_received
+            .checked_add(usdc_received)
+            .ok_or(LollysLottoError::MathError)?;
+        round_burn.usdc_swapped = round_burn
+            .usdc_swapped
+            .checked_add(usdc_swapped)
+            .ok_or(LollysLottoError::MathError)?;
+        round_burn.lolly_burnt = round_burn
+            .lolly_burnt
+            .checked_add(lolly_burnt)
+            .ok_or(LollysLottoError::MathError)?;
+        Ok(*round_burn)
+    }
+
+    fn round_burn_slot(round: u64) -> usize {
+        (round % Self::ROUND_BURNS_LEN as u64) as usize
+    }
 }
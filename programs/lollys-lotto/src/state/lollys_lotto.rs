@@ -1,12 +1,21 @@
-use crate::pda_identifier::PDAIdentifier;
+use super::{LottoGame, LottoGameState, PaymentAsset};
+use crate::{errors::LollysLottoError, pda_identifier::PDAIdentifier};
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 #[derive(Debug)]
 #[account]
 pub struct LollysLotto {
-    pub bump: u8,              // 1
-    pub lotto_game_count: u64, // 8
-    pub authority: Pubkey,     // 32
+    pub bump: u8,                                // 1
+    pub lotto_game_count: u64,                   // 8
+    pub authority: Pubkey,                       // 32
+    pub randomness_provider: RandomnessProvider, // 1
+    /// Owner of the token accounts residual LottoGame vault funds are swept to.
+    pub treasury: Pubkey, // 32
+    /// Where `sweep_lotto_game_vault` sends residual LottoGame vault funds.
+    pub sweep_destination: SweepDestination, // 1
+    /// Discount on the ticket price when paying with LOLLY, funded by the DAO share.
+    pub lolly_payment_discount_bps: u16, // 2
 }
 
 impl PDAIdentifier for LollysLotto {
@@ -18,7 +27,9 @@ impl PDAIdentifier for LollysLotto {
 }
 
 impl LollysLotto {
-    pub const SIZE: usize = 1 + 8 + 32;
+    pub const SIZE: usize = 1 + 8 + 32 + 1 + 32 + 1 + 2;
+    /// Discounts are funded by the DAO share of the ticket, so they can't exceed it.
+    pub const MAX_LOLLY_PAYMENT_DISCOUNT_BPS: u16 = LottoGame::DAO_BPS as u16;
 
     pub fn signer_address(authority: Pubkey) -> Pubkey {
         Self::get_address(&[authority.as_ref()])
@@ -30,4 +41,91 @@ impl LollysLotto {
     pub fn address_with_bump(authority: Pubkey) -> (Pubkey, u8) {
         Self::get_address_with_bump(&[authority.as_ref()])
     }
+
+    /// Discount on `ticket_price` for a ticket paid with `payment_asset`.
+    pub fn payment_discount(&self, payment_asset: PaymentAsset, ticket_price: u64) -> u64 {
+        match payment_asset {
+            PaymentAsset::Lolly => {
+                (ticket_price as u128 * self.lolly_payment_discount_bps as u128 / 10000) as u64
+            }
+            PaymentAsset::Usdc | PaymentAsset::Sol => 0,
+        }
+    }
+
+    /// Fails unless `token_account` is a token account of `mint` owned by the treasury.
+    pub fn check_treasury_token_account(
+        &self,
+        token_account: &InterfaceAccount<TokenAccount>,
+        mint: Pubkey,
+    ) -> Result<()> {
+        if token_account.owner != self.treasury || token_account.mint != mint {
+            return Err(LollysLottoError::InvalidTreasury.into());
+        }
+        Ok(())
+    }
+
+    /// Checks `destination_vault` against the configured sweep destination and, when sweeping
+    /// to the next round, credits `amount` to the jackpot of `next_lotto_game`.
+    pub fn credit_sweep_destination(
+        &self,
+        destination_vault: &InterfaceAccount<TokenAccount>,
+        next_lotto_game: Option<&AccountLoader<LottoGame>>,
+        amount: u64,
+    ) -> Result<()> {
+        match self.sweep_destination {
+            SweepDestination::Treasury => {
+                if destination_vault.owner != self.treasury {
+                    return Err(LollysLottoError::InvalidSweepDestination.into());
+                }
+            }
+            SweepDestination::NextRound => {
+                let next_lotto_game = &mut *next_lotto_game
+                    .ok_or(LollysLottoError::InvalidSweepDestination)?
+                    .load_mut()?;
+                if next_lotto_game.authority != self.authority
+                    || next_lotto_game.lotto_game_vault != destination_vault.key()
+                {
+                    return Err(LollysLottoError::InvalidSweepDestination.into());
+                }
+                if next_lotto_game.state != LottoGameState::Open {
+                    return Err(LollysLottoError::InvalidLottoGameState.into());
+                }
+                next_lotto_game.credit_jackpot_rollover(amount)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The randomness backend used to draw the winning numbers of every LottoGame
+/// started by this LollysLotto instance.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub enum RandomnessProvider {
+    /// `request_winning_numbers` -> `process_winning_numbers` callback.
+    SolanaRandomnessService,
+    /// `commit_winning_numbers` -> `reveal_winning_numbers`.
+    SwitchboardOnDemand,
+}
+
+impl Default for RandomnessProvider {
+    fn default() -> Self {
+        RandomnessProvider::SolanaRandomnessService
+    }
+}
+
+/// The destination of the funds left in a LottoGame vault once its claim period is over.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub enum SweepDestination {
+    /// A token account owned by `LollysLotto::treasury`.
+    Treasury,
+    /// The jackpot of the currently open round.
+    NextRound,
+}
+
+impl Default for SweepDestination {
+    fn default() -> Self {
+        SweepDestination::Treasury
+    }
 }
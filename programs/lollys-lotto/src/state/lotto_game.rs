@@ -1,12 +1,18 @@
-use anchor_lang::{prelude::*, Result as AnchorResult};
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_lang::{prelude::*, solana_program::hash::hashv, Result as AnchorResult};
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
 use bytemuck::{Pod, Zeroable};
 use num_traits::ToPrimitive;
 use rust_decimal::Decimal;
 
 use crate::{constants::USDC_MINT_DEVNET, errors::LollysLottoError, pda_identifier::PDAIdentifier};
 
-use super::LottoTicketNumbers;
+use super::{
+    BuyLottoTicketEvent, DrawRaffleWinnerEvent, EventEmitter, LollysLottoProgramEventData,
+    LottoGameStateTransitionEvent, LottoTicket, LottoTicketCombination, LottoTicketNumbers,
+    TicketPurchase, UserMetadata,
+};
 
 #[account(zero_copy)]
 #[derive(Debug)]
@@ -42,35 +48,115 @@ pub struct LottoGame {
     /// Ticket has only 6 numbers. But the last two bytes are reserved for padding.
     pub max_numbers_in_ticket: [u8; 6], //6
     pub _padding1: [u8; 2], //2
-    /// The jackpot winning numbers of this round/LottoGame instance.
-    /// 7th byte is indication if the winning numbers are set. 0 = not set, 1 = set.
-    /// 8th byte is an indication if jackpot_winning_amount is disbursed or not (0 = not disbursed, 1 = disbursed).
-    pub jackpot_winning_numbers: LottoGameWinningNumbers, //8
-    /// The tier 1 winning numbers of this round/LottoGame instance.
-    /// 7th byte is indication if the winning numbers are set. 0 = not set, 1 = set. 8th byte is padding.
-    pub tier_1_winning_numbers: [LottoGameWinningNumbers; 10], //80
-    /// The tier 2 winning numbers of this round/LottoGame instance.
-    /// 7th byte is indication if the winning numbers are set. 0 = not set, 1 = set. 8th byte is padding.
-    pub tier_2_winning_numbers: [LottoGameWinningNumbers; 100], //800
-    /// The tier 3 winning numbers of this round/LottoGame instance.
-    /// 7th byte is indication if the winning numbers are set. 0 = not set, 1 = set. 8th byte is padding.
-    pub tier_3_winning_numbers: [LottoGameWinningNumbers; 1000], //8000
+    /// The winning combinations of this round/LottoGame instance packed with `pack_winning_numbers`,
+    /// in draw order: the jackpot, then tier 1, tier 2 and tier 3.
+    /// Only the first `winning_numbers_filled` entries are set.
+    pub winning_numbers: [u32; 1111], //4444
+    /// Bitmap of the winning amounts disbursed, one bit per entry of `winning_numbers`.
+    pub winning_amount_disbursed: [u8; 140], //140
+    /// Open-addressing table from a packed winning combination to its position in
+    /// `winning_numbers` plus one. 0 = empty slot.
+    pub winning_numbers_index: [u16; 2048], //4096
+    /// The randomness seed the winning numbers of this round/LottoGame instance are expanded from.
+    pub winning_numbers_seed: [u8; 32], //32
+    /// The next nonce to be hashed with `winning_numbers_seed` to derive a winning combination.
+    pub winning_numbers_nonce: u64, //8
+    /// The number of winning number slots (jackpot and tiers) filled so far.
+    pub winning_numbers_filled: u16, //2
+    /// Indication if `winning_numbers_seed` is set. 0 = not set, 1 = set.
+    pub winning_numbers_seed_set: u8, //1
+    pub _padding2: [u8; 5], //5
+    /// The pending randomness request of this round/LottoGame instance, i.e. the
+    /// Solana Randomness Service request account or the Switchboard On-Demand
+    /// randomness account committed to.
+    pub randomness_request: Pubkey, //32
+    /// The slot the pending randomness request was made at.
+    pub randomness_request_slot: u64, //8
+    /// The timestamp the pending randomness request was made at.
+    pub randomness_requested_at: i64, //8
+    /// Seconds before `end_date` at which ticket sales stop.
+    pub sales_close_buffer: u64, //8
+    /// The amounts of the vault still owed to each prize and fee bucket.
+    pub liabilities: LottoGameLiabilities, //56
+    /// Residual funds of earlier rounds swept into the jackpot of this round/LottoGame instance.
+    pub jackpot_rollover: u64, //8
+    /// The time after which winners of this round/LottoGame instance can no longer be settled
+    /// and unclaimed prizes expire.
+    pub claim_deadline: i64, //8
+    /// Sponsor top-ups on top of the ticket-funded pools, by tier. Only the jackpot can be topped up.
+    pub sponsored_prize_pool: [u64; 4], //32
+    /// The jackpot guaranteed by the treasury, whichever is higher of this and the ticket-funded
+    /// jackpot is paid out. Funded into the vault by `fund_min_jackpot`.
+    pub min_jackpot: u64, //8
+    /// The `ticket_number` winning the jackpot of a raffle, set once the raffle is drawn.
+    pub raffle_winning_ticket_number: u64, //8
 }
 
 impl LottoGame {
-    pub const SIZE_V1: usize =
-        1 + 1 + 2 + 4 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 6 + 2 + 8 + 80 + 800 + 8000; //9072
+    pub const SIZE_V1: usize = 1
+        + 1
+        + 2
+        + 4
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 32
+        + 32
+        + 6
+        + 2
+        + 4444
+        + 140
+        + 4096
+        + 32
+        + 8
+        + 2
+        + 1
+        + 5
+        + 32
+        + 8
+        + 8
+        + 8
+        + LottoGameLiabilities::SIZE
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8; //9088
 
     pub const JACKPOT_WINNERS_V1: usize = 1;
     pub const MAX_TIER_1_WINNERS_V1: usize = 10;
     pub const MAX_TIER_2_WINNERS_V1: usize = 100;
     pub const MAX_TIER_3_WINNERS_V1: usize = 1000;
+    // Jackpot + Tier 1 + Tier 2 + Tier 3
+    pub const MAX_WINNING_NUMBERS_V1: usize = 1111;
+    // Position of the first tier 1, tier 2 and tier 3 combination in `winning_numbers`
+    pub const TIER_1_WINNING_NUMBERS_OFFSET: usize = 1;
+    pub const TIER_2_WINNING_NUMBERS_OFFSET: usize = 11;
+    pub const TIER_3_WINNING_NUMBERS_OFFSET: usize = 111;
+    // Number of slots in `winning_numbers_index`, kept at about twice MAX_WINNING_NUMBERS_V1
+    pub const WINNING_NUMBERS_INDEX_BITS: u32 = 11;
+    pub const WINNING_NUMBERS_INDEX_SIZE: usize = 1 << LottoGame::WINNING_NUMBERS_INDEX_BITS;
+    // Number of randomness bytes requested once per round
+    pub const WINNING_NUMBERS_SEED_LEN: usize = 32;
+    // Maximum number of combinations derived from the seed in a single instruction
+    pub const WINNING_NUMBERS_EXPANSION_BATCH_SIZE: usize = 25;
+    // Seconds after which an unanswered randomness request can be made again
+    pub const RANDOMNESS_REQUEST_TIMEOUT: i64 = 600;
     // Jackpot, Tier 1, Tier 2, Tier 3
     pub const MAX_WINNING_TIERS_V1: usize = 4;
     pub const JACKPOT_WINNING_BPS: usize = 5000;
     pub const TIER_1_WINNING_BPS: usize = 1000;
     pub const TIER_2_WINNING_BPS: usize = 1000;
     pub const TIER_3_WINNING_BPS: usize = 1000;
+    // A raffle has no tiers, its single winner is paid the shares of all of them
+    pub const RAFFLE_JACKPOT_WINNING_BPS: usize = LottoGame::JACKPOT_WINNING_BPS
+        + LottoGame::TIER_1_WINNING_BPS
+        + LottoGame::TIER_2_WINNING_BPS
+        + LottoGame::TIER_3_WINNING_BPS;
     pub const JACKPOT_WINNING_AMOUNT_MULTIPLIER: usize = 5000; // JACKPOT_WINNING_BPS/JACKPOT_WINNERS_V1
     pub const TIER_1_WINNING_AMOUNT_MULTIPLIER: usize = 100; // TIER_1_WINNING_BPS/MAX_TIER_1_WINNERS_V1
     pub const TIER_2_WINNING_AMOUNT_MULTIPLIER: usize = 10; // TIER_2_WINNING_BPS/MAX_TIER_2_WINNERS_V1
@@ -91,90 +177,421 @@ impl LottoGame {
         Self::get_address_with_bump(&[authority.as_ref(), &round.to_le_bytes()])
     }
 
+    /// The position in `winning_numbers` of the given tier and index.
+    pub fn winning_numbers_position(tier: u8, index: usize) -> Result<usize> {
+        let (offset, max_winners) = match tier {
+            0 => (0, LottoGame::JACKPOT_WINNERS_V1),
+            1 => (
+                LottoGame::TIER_1_WINNING_NUMBERS_OFFSET,
+                LottoGame::MAX_TIER_1_WINNERS_V1,
+            ),
+            2 => (
+                LottoGame::TIER_2_WINNING_NUMBERS_OFFSET,
+                LottoGame::MAX_TIER_2_WINNERS_V1,
+            ),
+            3 => (
+                LottoGame::TIER_3_WINNING_NUMBERS_OFFSET,
+                LottoGame::MAX_TIER_3_WINNERS_V1,
+            ),
+            _ => return Err(LollysLottoError::InvalidWinningTier.into()),
+        };
+        if index >= max_winners {
+            return Err(LollysLottoError::InvalidWinningNumberIndex.into());
+        }
+        Ok(offset + index)
+    }
+
+    /// The tier and index of the given position in `winning_numbers`.
+    pub fn tier_and_index_by_position(position: usize) -> (u8, usize) {
+        match position {
+            0 => (0, 0),
+            p if p < LottoGame::TIER_2_WINNING_NUMBERS_OFFSET => {
+                (1, p - LottoGame::TIER_1_WINNING_NUMBERS_OFFSET)
+            }
+            p if p < LottoGame::TIER_3_WINNING_NUMBERS_OFFSET => {
+                (2, p - LottoGame::TIER_2_WINNING_NUMBERS_OFFSET)
+            }
+            p => (3, p - LottoGame::TIER_3_WINNING_NUMBERS_OFFSET),
+        }
+    }
+
+    fn check_winning_numbers_set_at(&self, position: usize) -> bool {
+        position < self.winning_numbers_filled as usize
+    }
+
+    fn check_winning_amount_disbursed_at(&self, position: usize) -> bool {
+        self.winning_amount_disbursed[position / 8] & (1 << (position % 8)) != 0
+    }
+
+    fn get_winning_numbers_at(&self, position: usize) -> LottoGameWinningNumbers {
+        if !self.check_winning_numbers_set_at(position) {
+            return LottoGameWinningNumbers::default();
+        }
+        let numbers = unpack_winning_numbers(self.winning_numbers[position]);
+        LottoGameWinningNumbers {
+            number1: numbers[0],
+            number2: numbers[1],
+            number3: numbers[2],
+            number4: numbers[3],
+            number5: numbers[4],
+            jackpot_number: numbers[5],
+            switchboard_random_numbers_updated: WinningNumberUpdateState::Updated,
+            winning_amount_disbursed: if self.check_winning_amount_disbursed_at(position) {
+                WinningAmountDisbursedState::Disbursed
+            } else {
+                WinningAmountDisbursedState::NotDisbursed
+            },
+        }
+    }
+
+    pub fn jackpot_winning_numbers(&self) -> LottoGameWinningNumbers {
+        self.get_winning_numbers_at(0)
+    }
+
     pub fn get_tier_1_winning_numbers_by_index(
         &self,
         index: usize,
     ) -> Result<LottoGameWinningNumbers> {
-        if index > LottoGame::MAX_TIER_1_WINNERS_V1 - 1 {
-            return Err(LollysLottoError::InvalidWinningNumberIndex.into());
-        }
-        Ok(self.tier_1_winning_numbers[index])
+        self.get_winning_numbers_by_tier_and_index(1, index)
     }
 
     pub fn get_tier_2_winning_numbers_by_index(
         &self,
         index: usize,
     ) -> Result<LottoGameWinningNumbers> {
-        if index > LottoGame::MAX_TIER_2_WINNERS_V1 - 1 {
-            return Err(LollysLottoError::InvalidWinningNumberIndex.into());
-        }
-        Ok(self.tier_2_winning_numbers[index])
+        self.get_winning_numbers_by_tier_and_index(2, index)
     }
 
     pub fn get_tier_3_winning_numbers_by_index(
         &self,
         index: usize,
     ) -> Result<LottoGameWinningNumbers> {
-        if index > LottoGame::MAX_TIER_3_WINNERS_V1 - 1 {
-            return Err(LollysLottoError::InvalidWinningNumberIndex.into());
-        }
-        Ok(self.tier_3_winning_numbers[index])
+        self.get_winning_numbers_by_tier_and_index(3, index)
     }
 
-    // write a function to get the winning numbers by tier and index
     pub fn get_winning_numbers_by_tier_and_index(
         &self,
         tier: u8,
         index: usize,
     ) -> Result<LottoGameWinningNumbers> {
-        match tier {
-            0 => Ok(self.jackpot_winning_numbers),
-            1 => self.get_tier_1_winning_numbers_by_index(index),
-            2 => self.get_tier_2_winning_numbers_by_index(index),
-            3 => self.get_tier_3_winning_numbers_by_index(index),
-            _ => Err(LollysLottoError::InvalidWinningNumberIndex.into()),
+        let position = LottoGame::winning_numbers_position(tier, index)?;
+        Ok(self.get_winning_numbers_at(position))
+    }
+
+    /// Looks `key` up in `winning_numbers_index`.
+    /// Returns the position of the combination in `winning_numbers` if it is set,
+    /// or the empty index slot it would be inserted at otherwise.
+    fn find_winning_numbers(&self, key: u32) -> std::result::Result<usize, usize> {
+        let mask = LottoGame::WINNING_NUMBERS_INDEX_SIZE - 1;
+        let mut slot = winning_numbers_index_slot(key);
+        // The index is never more than about half full, so an empty slot is always found
+        loop {
+            match self.winning_numbers_index[slot] {
+                0 => return Err(slot),
+                entry => {
+                    let position = entry as usize - 1;
+                    if self.winning_numbers[position] == key {
+                        return Ok(position);
+                    }
+                }
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    /// The position in `winning_numbers` `lotto_ticket` wins at, if any. A raffle ticket wins
+    /// the jackpot when its `ticket_number` is drawn, a V1 ticket when its numbers are drawn.
+    fn find_winning_position(&self, lotto_ticket: &LottoTicket) -> Result<Option<usize>> {
+        match self.version {
+            LottoGameVersion::V1 => Ok(self
+                .find_winning_numbers(pack_winning_numbers(lotto_ticket.numbers.to_array())?)
+                .ok()),
+            LottoGameVersion::Raffle => Ok((self.check_all_winning_numbers_set()
+                && lotto_ticket.ticket_number == self.raffle_winning_ticket_number)
+                .then_some(0)),
         }
     }
 
-    // write a function to get the index and tier when the winning_numbers are given as input params
+    pub fn get_tier_and_index_by_lotto_ticket(
+        &self,
+        lotto_ticket: &LottoTicket,
+    ) -> Result<(u8, usize)> {
+        self.find_winning_position(lotto_ticket)?
+            .map(LottoGame::tier_and_index_by_position)
+            .ok_or(LollysLottoError::InvalidWinningTicket.into())
+    }
+
     pub fn get_tier_and_index_by_winning_numbers(
         &self,
         winning_numbers: LottoTicketNumbers,
     ) -> Result<(u8, usize)> {
-        if self
-            .jackpot_winning_numbers
-            .validate_winning_numbers(winning_numbers)
-        {
-            return Ok((0, 0));
+        let key = pack_winning_numbers(winning_numbers.to_array())?;
+        self.find_winning_numbers(key)
+            .map(LottoGame::tier_and_index_by_position)
+            .map_err(|_| LollysLottoError::InvalidWinningTicket.into())
+    }
+
+    /// The timestamp after which no more tickets are sold.
+    pub fn sales_close_date(&self) -> i64 {
+        self.end_date - self.sales_close_buffer as i64
+    }
+
+    /// The discount and price of the next ticket. The discount is funded by the DAO share of the
+    /// ticket along with its transfer fee, so it is capped at that share less `max_transfer_fee`,
+    /// the fee the mint withholds on the full ticket price.
+    pub fn discounted_ticket_price(&self, discount: u64, max_transfer_fee: u64) -> (u64, u64) {
+        let dao_share = ticket_sale_share(self.ticket_price, LottoGame::DAO_BPS, self.tickets_sold);
+        let discount = discount.min(dao_share.saturating_sub(max_transfer_fee));
+        (discount, self.ticket_price - discount)
+    }
+
+    /// Issues `lotto_ticket` to the user of `purchase`, who paid the discounted ticket price.
+    /// Every buy instruction sells its tickets through here.
+    pub fn issue_ticket(
+        &mut self,
+        lotto_game: Pubkey,
+        purchase: TicketPurchase,
+        lotto_ticket: &mut Account<LottoTicket>,
+        lotto_ticket_combination: &mut LottoTicketCombination,
+        user_metadata: &mut Account<UserMetadata>,
+        event_emitter: &mut EventEmitter,
+    ) -> Result<()> {
+        let ticket_price = self.ticket_price - purchase.discount;
+        lotto_ticket.user = purchase.user;
+        lotto_ticket.ticket_number = self.tickets_sold;
+        lotto_ticket.lotto_game = lotto_game;
+        lotto_ticket.round = self.round;
+        lotto_ticket.numbers = purchase.numbers;
+        lotto_ticket.ticket_price = ticket_price;
+        lotto_ticket.buy_date = purchase.buy_date;
+        lotto_ticket.check_date = 0;
+        lotto_ticket.is_checked = 0;
+        lotto_ticket.is_duplicated = 0;
+        lotto_ticket.is_winner = 0;
+        lotto_ticket.prize = 0;
+        lotto_ticket.payment_asset = purchase.payment_asset;
+
+        self.liabilities
+            .reserve_ticket_sale(self.version, self.ticket_price, self.tickets_sold)?;
+        // The discount and the transfer fee withheld by the mint are funded by the DAO share of the ticket
+        self.liabilities
+            .release_dao(purchase.discount + purchase.transfer_fee)?;
+        self.tickets_sold += 1;
+        lotto_ticket_combination.record_holder(
+            purchase.lotto_ticket_combination_bump,
+            lotto_game,
+            purchase.numbers,
+            purchase.payer,
+        )?;
+
+        user_metadata.total_tickets_purchased += 1;
+
+        event_emitter.emit_new_event(
+            Some(purchase.buy_date),
+            LollysLottoProgramEventData::BuyLottoTicket(BuyLottoTicketEvent {
+                user: purchase.user,
+                user_metadata: user_metadata.key(),
+                user_ticket_count: user_metadata.total_tickets_purchased,
+                lotto_ticket: lotto_ticket.key(),
+                lotto_game,
+                tickets_sold: self.tickets_sold,
+                round: self.round,
+                ticket_number: lotto_ticket.ticket_number,
+                numbers: purchase.numbers,
+                ticket_price,
+                discount: purchase.discount,
+                transfer_fee: purchase.transfer_fee,
+                payment_asset: purchase.payment_asset,
+                buy_date: purchase.buy_date,
+            }),
+        )
+    }
+
+    pub fn check_claim_deadline_passed(&self, current_timestamp: i64) -> bool {
+        current_timestamp >= self.claim_deadline
+    }
+
+    /// Whether the prize of every winning numbers drawn has been disbursed, whether or not a
+    /// ticket holds them. A raffle without tickets has no winner to pay.
+    pub fn check_all_winning_amounts_disbursed(&self) -> bool {
+        if self.version == LottoGameVersion::Raffle && self.tickets_sold == 0 {
+            return true;
         }
+        (0..self.winning_numbers_filled as usize)
+            .all(|position| self.check_winning_amount_disbursed_at(position))
+    }
 
-        for (index, slot) in self.tier_1_winning_numbers.iter().enumerate() {
-            if slot.validate_winning_numbers(winning_numbers) {
-                return Ok((1, index));
-            }
+    /// Adds `amount` swept from an earlier round to the jackpot of this round/LottoGame instance.
+    pub fn credit_jackpot_rollover(&mut self, amount: u64) -> Result<()> {
+        self.jackpot_rollover = self
+            .jackpot_rollover
+            .checked_add(amount)
+            .ok_or(LollysLottoError::OverflowError)?;
+        self.liabilities.jackpot = self
+            .liabilities
+            .jackpot
+            .checked_add(amount)
+            .ok_or(LollysLottoError::OverflowError)?;
+        Ok(())
+    }
+
+    /// Reserves `amount` funded by the treasury as the minimum jackpot of this round/LottoGame instance.
+    pub fn credit_min_jackpot(&mut self, amount: u64) -> Result<()> {
+        if self.min_jackpot != 0 {
+            return Err(LollysLottoError::InvalidMinJackpot.into());
         }
+        self.min_jackpot = amount;
+        self.liabilities.reserve_prize(0, amount)
+    }
 
-        for (index, slot) in self.tier_2_winning_numbers.iter().enumerate() {
-            if slot.validate_winning_numbers(winning_numbers) {
-                return Ok((2, index));
-            }
+    /// The part of `min_jackpot` the jackpot winner did not need, all of it if the jackpot was never paid.
+    /// It is still reserved in the jackpot liability until it goes back to the treasury.
+    pub fn unused_min_jackpot(&self) -> u64 {
+        let unused = if self.check_winning_amount_disbursed_at(0) {
+            self.min_jackpot.min(self.jackpot_revenue_share())
+        } else {
+            self.min_jackpot
+        };
+        unused.min(self.liabilities.jackpot)
+    }
+
+    /// Adds `amount` topped up by a sponsor to the prize pool of `tier` of this round/LottoGame instance.
+    /// The other tiers are split between their maximum number of winners rather than the actual ones,
+    /// so only the jackpot can be topped up.
+    pub fn credit_sponsored_prize_pool(&mut self, tier: u8, amount: u64) -> Result<()> {
+        if tier != 0 {
+            return Err(LollysLottoError::SponsoredTierNotJackpot.into());
         }
+        self.sponsored_prize_pool[0] = self.sponsored_prize_pool[0]
+            .checked_add(amount)
+            .ok_or(LollysLottoError::OverflowError)?;
+        self.liabilities.reserve_prize(tier, amount)
+    }
 
-        for (index, slot) in self.tier_3_winning_numbers.iter().enumerate() {
-            if slot.validate_winning_numbers(winning_numbers) {
-                return Ok((3, index));
+    /// Fails unless `lotto_ticket` can no longer be paid anything in this round/LottoGame instance,
+    /// i.e. the round is finished, or it is drawn and the ticket is a loser or has been paid.
+    pub fn check_lotto_ticket_closable(&self, lotto_ticket: &LottoTicket) -> Result<()> {
+        match self.state {
+            LottoGameState::Finished => Ok(()),
+            LottoGameState::Drawn | LottoGameState::Paying => {
+                // Other tickets may share the winning numbers, each one is checked for its own payment
+                match self.find_winning_position(lotto_ticket)? {
+                    None => Ok(()),
+                    Some(_) if lotto_ticket.is_winner != 0 => Ok(()),
+                    Some(_) => Err(LollysLottoError::LottoTicketNotSettled.into()),
+                }
             }
+            _ => Err(LollysLottoError::LottoTicketNotSettled.into()),
+        }
+    }
+
+    /// Fails unless the vault holds at least the outstanding liabilities of this round/LottoGame instance.
+    pub fn check_solvency(&self, lotto_game_vault_amount: u64) -> Result<()> {
+        if lotto_game_vault_amount < self.liabilities.total()? {
+            return Err(LollysLottoError::LottoGameVaultInsolvent.into());
         }
+        Ok(())
+    }
+
+    pub fn check_sales_window_over(&self, current_timestamp: i64) -> bool {
+        current_timestamp > self.sales_close_date()
+    }
 
-        Err(LollysLottoError::InvalidWinningTicket.into())
+    pub fn check_game_ended(&self, current_timestamp: i64) -> bool {
+        current_timestamp > self.end_date
     }
 
     pub fn check_game_state_closed(&self) -> bool {
-        self.state == LottoGameState::Closed
+        self.state.is_sales_closed()
+    }
+
+    /// Moves this LottoGame to `next_state` and emits a [LottoGameStateTransitionEvent].
+    /// All lifecycle changes go through here.
+    pub fn transition_state(
+        &mut self,
+        lotto_game: Pubkey,
+        next_state: LottoGameState,
+        event_emitter: &mut EventEmitter,
+        block_time: i64,
+    ) -> Result<()> {
+        let previous_state = self.state;
+        if !previous_state.can_transition_to(next_state) {
+            msg!(
+                "Invalid state transition: {:?} -> {:?}",
+                previous_state,
+                next_state
+            );
+            return Err(LollysLottoError::InvalidStateTransition.into());
+        }
+        self.state = next_state;
+
+        event_emitter.emit_new_event(
+            Some(block_time),
+            LollysLottoProgramEventData::LottoGameStateTransition(LottoGameStateTransitionEvent {
+                lotto_game,
+                round: self.round,
+                previous_state,
+                new_state: next_state,
+            }),
+        )
+    }
+
+    /// Moves a drawing LottoGame to `Drawn` once every winning number is set.
+    pub fn complete_draw_if_filled(
+        &mut self,
+        lotto_game: Pubkey,
+        event_emitter: &mut EventEmitter,
+        block_time: i64,
+    ) -> Result<()> {
+        if self.state == LottoGameState::Drawing && self.check_all_winning_numbers_set() {
+            self.transition_state(lotto_game, LottoGameState::Drawn, event_emitter, block_time)?;
+            if self.version == LottoGameVersion::Raffle {
+                event_emitter.emit_new_event(
+                    Some(block_time),
+                    LollysLottoProgramEventData::DrawRaffleWinner(DrawRaffleWinnerEvent {
+                        lotto_game,
+                        round: self.round,
+                        seed: self.winning_numbers_seed,
+                        tickets_sold: self.tickets_sold,
+                        winning_ticket_number: self.raffle_winning_ticket_number,
+                    }),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves a drawn LottoGame to `Paying` on its first settlement.
+    pub fn start_paying_if_drawn(
+        &mut self,
+        lotto_game: Pubkey,
+        event_emitter: &mut EventEmitter,
+        block_time: i64,
+    ) -> Result<()> {
+        match self.state {
+            LottoGameState::Drawn => self.transition_state(
+                lotto_game,
+                LottoGameState::Paying,
+                event_emitter,
+                block_time,
+            ),
+            LottoGameState::Paying => Ok(()),
+            _ => Err(LollysLottoError::InvalidLottoGameState.into()),
+        }
     }
 
     pub fn current_jackpot_winning_amount(&self) -> u64 {
-        (LottoGame::JACKPOT_WINNING_BPS as u64)
+        self.jackpot_revenue_share()
+            .max(self.min_jackpot)
+            .checked_add(self.jackpot_rollover)
+            .unwrap()
+            .checked_add(self.sponsored_prize_pool[0])
+            .unwrap()
+    }
+
+    /// The jackpot funded by ticket sales alone.
+    fn jackpot_revenue_share(&self) -> u64 {
+        (self.version.winning_bps()[0] as u64)
             .checked_mul(self.ticket_price)
             .unwrap()
             .checked_div(LottoGame::JACKPOT_WINNERS_V1 as u64)
@@ -194,7 +611,7 @@ impl LottoGame {
     }
 
     pub fn current_tier_1_winning_amount(&self) -> u64 {
-        (LottoGame::TIER_1_WINNING_BPS as u64)
+        (self.version.winning_bps()[1] as u64)
             .checked_mul(self.ticket_price)
             .unwrap()
             .checked_div(LottoGame::MAX_TIER_1_WINNERS_V1 as u64)
@@ -214,7 +631,7 @@ impl LottoGame {
     }
 
     pub fn current_tier_2_winning_amount(&self) -> u64 {
-        (LottoGame::TIER_2_WINNING_BPS as u64)
+        (self.version.winning_bps()[2] as u64)
             .checked_mul(self.ticket_price)
             .unwrap()
             .checked_div(LottoGame::MAX_TIER_2_WINNERS_V1 as u64)
@@ -234,7 +651,7 @@ impl LottoGame {
     }
 
     pub fn current_tier_3_winning_amount(&self) -> u64 {
-        (LottoGame::TIER_3_WINNING_BPS as u64)
+        (self.version.winning_bps()[3] as u64)
             .checked_mul(self.ticket_price)
             .unwrap()
             .checked_div(LottoGame::MAX_TIER_3_WINNERS_V1 as u64)
@@ -295,214 +712,183 @@ impl LottoGame {
         }
     }
 
-    pub fn update_winning_numbers(
+    pub fn check_winning_numbers_seed_set(&self) -> bool {
+        self.winning_numbers_seed_set == 1
+    }
+
+    pub fn check_randomness_requested(&self) -> bool {
+        self.randomness_request != Pubkey::default()
+    }
+
+    pub fn check_randomness_request_expired(&self, current_timestamp: i64) -> bool {
+        self.check_randomness_requested()
+            && !self.check_winning_numbers_seed_set()
+            && current_timestamp
+                >= self.randomness_requested_at + LottoGame::RANDOMNESS_REQUEST_TIMEOUT
+    }
+
+    pub fn set_randomness_request(
         &mut self,
-        numbers: [u8; 6],
-    ) -> AnchorResult<(bool, bool, [i64; 4])> {
-        let is_updated: bool = false;
-        let (is_duplicate, duplicate_indices) = self.check_duplicate(numbers);
-        if is_duplicate {
-            return Ok((is_duplicate, is_updated, duplicate_indices));
-        }
-
-        let (is_updated, update_indices) = self.mark_as_updated(numbers);
-        Ok((is_duplicate, is_updated, update_indices))
-    }
-
-    fn check_duplicate(&self, numbers: [u8; 6]) -> (bool, [i64; 4]) {
-        let mut duplicate_indices = [-1; 4]; // Indices for jackpot, tier1, tier2, tier3
-
-        // Check the jackpot separately
-        if self
-            .jackpot_winning_numbers
-            .switchboard_random_numbers_updated
-            == WinningNumberUpdateState::Updated
-            && self.jackpot_winning_numbers.number1 == numbers[0]
-            && self.jackpot_winning_numbers.number2 == numbers[1]
-            && self.jackpot_winning_numbers.number3 == numbers[2]
-            && self.jackpot_winning_numbers.number4 == numbers[3]
-            && self.jackpot_winning_numbers.number5 == numbers[4]
-            && self.jackpot_winning_numbers.jackpot_number == numbers[5]
-        {
-            duplicate_indices[0] = 0; // Jackpot index set as 0 because there's only one jackpot
-            return (true, duplicate_indices);
-        }
-
-        // Check Tier 1 for duplicates
-        for (slot_index, slot) in self.tier_1_winning_numbers.iter().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::Updated
-                && slot.number1 == numbers[0]
-                && slot.number2 == numbers[1]
-                && slot.number3 == numbers[2]
-                && slot.number4 == numbers[3]
-                && slot.number5 == numbers[4]
-                && slot.jackpot_number == numbers[5]
-            {
-                duplicate_indices[1] = slot_index as i64;
-                return (true, duplicate_indices);
-            }
-        }
+        randomness_request: Pubkey,
+        request_slot: u64,
+        requested_at: i64,
+    ) {
+        self.randomness_request = randomness_request;
+        self.randomness_request_slot = request_slot;
+        self.randomness_requested_at = requested_at;
+    }
 
-        // Check Tier 2 for duplicates
-        for (slot_index, slot) in self.tier_2_winning_numbers.iter().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::Updated
-                && slot.number1 == numbers[0]
-                && slot.number2 == numbers[1]
-                && slot.number3 == numbers[2]
-                && slot.number4 == numbers[3]
-                && slot.number5 == numbers[4]
-                && slot.jackpot_number == numbers[5]
-            {
-                duplicate_indices[2] = slot_index as i64;
-                return (true, duplicate_indices);
+    pub fn check_all_winning_numbers_set(&self) -> bool {
+        match self.version {
+            LottoGameVersion::V1 => {
+                self.winning_numbers_filled as usize >= LottoGame::MAX_WINNING_NUMBERS_V1
             }
+            // A raffle draws a single winning ticket number instead of winning combinations
+            LottoGameVersion::Raffle => self.winning_numbers_filled > 0,
         }
+    }
 
-        // Check Tier 3 for duplicates
-        for (slot_index, slot) in self.tier_3_winning_numbers.iter().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::Updated
-                && slot.number1 == numbers[0]
-                && slot.number2 == numbers[1]
-                && slot.number3 == numbers[2]
-                && slot.number4 == numbers[3]
-                && slot.number5 == numbers[4]
-                && slot.jackpot_number == numbers[5]
-            {
-                duplicate_indices[3] = slot_index as i64;
-                return (true, duplicate_indices);
-            }
+    pub fn set_winning_numbers_seed(&mut self, randomness: &[u8]) -> Result<()> {
+        if self.check_winning_numbers_seed_set() {
+            return Err(LollysLottoError::WinningNumbersSeedAlreadySet.into());
         }
-
-        // If no duplicates are found
-        (false, duplicate_indices)
+        self.winning_numbers_seed = randomness
+            .try_into()
+            .map_err(|_| LollysLottoError::InvalidRandomnessLength)?;
+        self.winning_numbers_nonce = 0;
+        self.winning_numbers_seed_set = 1;
+        Ok(())
     }
 
-    fn mark_as_updated(&mut self, numbers: [u8; 6]) -> (bool, [i64; 4]) {
-        let mut update_indices = [-1; 4]; // Indices for jackpot, tier1, tier2, tier3
-
-        // Attempt to update the jackpot
-        if self
-            .jackpot_winning_numbers
-            .switchboard_random_numbers_updated
-            == WinningNumberUpdateState::NotUpdated
-        {
-            // Not updated
-            self.jackpot_winning_numbers.number1 = numbers[0];
-            self.jackpot_winning_numbers.number2 = numbers[1];
-            self.jackpot_winning_numbers.number3 = numbers[2];
-            self.jackpot_winning_numbers.number4 = numbers[3];
-            self.jackpot_winning_numbers.number5 = numbers[4];
-            self.jackpot_winning_numbers.jackpot_number = numbers[5];
-            self.jackpot_winning_numbers
-                .switchboard_random_numbers_updated = WinningNumberUpdateState::Updated; // Mark as updated
-            update_indices[0] = 0; // Jackpot index set as 0 because there's only one jackpot
-            return (true, update_indices);
-        }
-
-        // Attempt to update Tier 1
-        for (slot_index, slot) in self.tier_1_winning_numbers.iter_mut().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::NotUpdated {
-                // Not updated
-                slot.number1 = numbers[0];
-                slot.number2 = numbers[1];
-                slot.number3 = numbers[2];
-                slot.number4 = numbers[3];
-                slot.number5 = numbers[4];
-                slot.jackpot_number = numbers[5];
-                slot.switchboard_random_numbers_updated = WinningNumberUpdateState::Updated; // Mark as updated
-                update_indices[1] = slot_index as i64;
-                return (true, update_indices);
-            }
+    /// Derives up to `batch_size` winning combinations from `winning_numbers_seed`,
+    /// continuing from `winning_numbers_nonce`. Combinations that are already
+    /// present in the game are skipped.
+    /// Returns the number of combinations added and the number of duplicates skipped.
+    pub fn expand_winning_numbers(&mut self, batch_size: usize) -> Result<(u16, u16)> {
+        if !self.check_winning_numbers_seed_set() {
+            return Err(LollysLottoError::WinningNumbersSeedNotSet.into());
+        }
+        if self.check_all_winning_numbers_set() {
+            return Err(LollysLottoError::AllWinningNumbersSet.into());
+        }
+        if self.version == LottoGameVersion::Raffle {
+            self.raffle_winning_ticket_number =
+                derive_raffle_winning_ticket_number(&self.winning_numbers_seed, self.tickets_sold);
+            self.winning_numbers_nonce = 1;
+            self.winning_numbers_filled = 1;
+            return Ok((1, 0));
         }
 
-        // Attempt to update Tier 2
-        for (slot_index, slot) in self.tier_2_winning_numbers.iter_mut().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::NotUpdated {
-                // Not updated
-                slot.number1 = numbers[0];
-                slot.number2 = numbers[1];
-                slot.number3 = numbers[2];
-                slot.number4 = numbers[3];
-                slot.number5 = numbers[4];
-                slot.jackpot_number = numbers[5];
-                slot.switchboard_random_numbers_updated = WinningNumberUpdateState::Updated; // Mark as updated
-                update_indices[2] = slot_index as i64;
-                return (true, update_indices);
+        let mut winning_numbers_added: u16 = 0;
+        let mut duplicates_skipped: u16 = 0;
+        for _ in 0..batch_size {
+            if self.check_all_winning_numbers_set() {
+                break;
+            }
+            let numbers = derive_winning_numbers(
+                &self.winning_numbers_seed,
+                self.winning_numbers_nonce,
+                self.max_numbers_in_ticket,
+            );
+            self.winning_numbers_nonce = self
+                .winning_numbers_nonce
+                .checked_add(1)
+                .ok_or(LollysLottoError::OverflowError)?;
+
+            let (is_duplicate, is_updated, _) = self.update_winning_numbers(numbers)?;
+            if is_duplicate {
+                duplicates_skipped += 1;
+            } else if is_updated {
+                winning_numbers_added += 1;
             }
         }
+        Ok((winning_numbers_added, duplicates_skipped))
+    }
 
-        // Attempt to update Tier 3
-        for (slot_index, slot) in self.tier_3_winning_numbers.iter_mut().enumerate() {
-            if slot.switchboard_random_numbers_updated == WinningNumberUpdateState::NotUpdated {
-                // Not updated
-                slot.number1 = numbers[0];
-                slot.number2 = numbers[1];
-                slot.number3 = numbers[2];
-                slot.number4 = numbers[3];
-                slot.number5 = numbers[4];
-                slot.jackpot_number = numbers[5];
-                slot.switchboard_random_numbers_updated = WinningNumberUpdateState::Updated; // Mark as updated
-                update_indices[3] = slot_index as i64;
-                return (true, update_indices);
+    /// Adds `numbers` to the next free winning number slot, unless they are already a winning combination.
+    /// Returns whether the numbers are a duplicate, whether they were added, and the index
+    /// per tier (jackpot, tier 1, tier 2, tier 3) they were found at or added to, -1 elsewhere.
+    pub fn update_winning_numbers(
+        &mut self,
+        numbers: [u8; 6],
+    ) -> AnchorResult<(bool, bool, [i64; 4])> {
+        let mut tier_indices = [-1; 4];
+        let key = pack_winning_numbers(numbers)?;
+        match self.find_winning_numbers(key) {
+            Ok(position) => {
+                let (tier, index) = LottoGame::tier_and_index_by_position(position);
+                tier_indices[tier as usize] = index as i64;
+                Ok((true, false, tier_indices))
+            }
+            Err(slot) => {
+                let position = self.winning_numbers_filled as usize;
+                if position >= LottoGame::MAX_WINNING_NUMBERS_V1 {
+                    return Ok((false, false, tier_indices));
+                }
+                self.winning_numbers[position] = key;
+                self.winning_numbers_index[slot] = (position + 1) as u16;
+                self.winning_numbers_filled += 1;
+                let (tier, index) = LottoGame::tier_and_index_by_position(position);
+                tier_indices[tier as usize] = index as i64;
+                Ok((false, true, tier_indices))
             }
         }
-
-        // If no slots were available for update
-        (false, update_indices)
     }
 
     pub fn get_amount_to_be_disbursed(
-        &mut self,
-        winning_tier: i64,
-        winning_number_index: i64,
+        &self,
+        winning_tier: u8,
+        winning_number_index: usize,
         winning_numbers: LottoTicketNumbers,
         lotto_game_vault_amount: u64,
     ) -> Result<Decimal> {
         let total_winning_pool =
             Decimal::new(self.ticket_price as i64, 0) * Decimal::new(self.tickets_sold as i64, 0);
 
-        let winning_numbers_data = match winning_tier {
-            0 => &mut self.jackpot_winning_numbers,
-            1..=3 => match winning_tier {
-                1 => &mut self.tier_1_winning_numbers[winning_number_index as usize],
-                2 => &mut self.tier_2_winning_numbers[winning_number_index as usize],
-                3 => &mut self.tier_3_winning_numbers[winning_number_index as usize],
-                _ => return Err(LollysLottoError::InvalidWinningTicket.into()),
-            },
-            _ => return Err(LollysLottoError::InvalidWinningTicket.into()),
-        };
-
-        if !winning_numbers_data.validate_if_winning_numbers_set() {
+        let position = LottoGame::winning_numbers_position(winning_tier, winning_number_index)?;
+        if !self.check_winning_numbers_set_at(position) {
             return Err(LollysLottoError::WinningNumbersNotSet.into());
         }
 
-        if !winning_numbers_data.validate_winning_numbers(winning_numbers) {
+        // The winner of a raffle is drawn by ticket number, its numbers are never drawn
+        if self.version == LottoGameVersion::V1
+            && self.winning_numbers[position] != pack_winning_numbers(winning_numbers.to_array())?
+        {
             return Err(LollysLottoError::InvalidWinningTicket.into());
         }
 
         let amount_to_be_disbursed = match winning_tier {
             0 => calculate_winning_amount(
                 total_winning_pool,
-                LottoGame::JACKPOT_WINNING_BPS as i64,
+                self.version.winning_bps()[0] as i64,
                 1,
-                lotto_game_vault_amount,
-            ),
+                self.min_jackpot,
+                self.sponsored_prize_pool[0],
+                lotto_game_vault_amount.saturating_sub(self.jackpot_rollover),
+            )
+            .map(|amount| amount + Decimal::from(self.jackpot_rollover)),
             1 => calculate_winning_amount(
                 total_winning_pool,
                 LottoGame::TIER_1_WINNING_BPS as i64,
                 LottoGame::MAX_TIER_1_WINNERS_V1 as i64,
+                0,
+                0,
                 lotto_game_vault_amount,
             ),
             2 => calculate_winning_amount(
                 total_winning_pool,
                 LottoGame::TIER_2_WINNING_BPS as i64,
                 LottoGame::MAX_TIER_2_WINNERS_V1 as i64,
+                0,
+                0,
                 lotto_game_vault_amount,
             ),
             3 => calculate_winning_amount(
                 total_winning_pool,
                 LottoGame::TIER_3_WINNING_BPS as i64,
                 LottoGame::MAX_TIER_3_WINNERS_V1 as i64,
+                0,
+                0,
                 lotto_game_vault_amount,
             ),
             _ => return Err(LollysLottoError::InvalidWinningTicket.into()),
@@ -512,18 +898,59 @@ impl LottoGame {
 
         Ok(amount_to_be_disbursed)
     }
+
+    /// Marks the winning amount of the given tier and index as disbursed.
+    /// Fails if the winning numbers are not updated or the amount was already disbursed.
+    pub fn mark_winning_amount_disbursed(&mut self, tier: u8, index: usize) -> Result<()> {
+        let position = LottoGame::winning_numbers_position(tier, index)?;
+        let (not_updated_error, already_disbursed_error) = match tier {
+            0 => (
+                LollysLottoError::JackpotWinningNumbersNotUpdated,
+                LollysLottoError::JackpotAmountAlreadyDisbursed,
+            ),
+            1 => (
+                LollysLottoError::Tier1WinningNumbersNotUpdated,
+                LollysLottoError::Tier1AmountAlreadyDisbursed,
+            ),
+            2 => (
+                LollysLottoError::Tier2WinningNumbersNotUpdated,
+                LollysLottoError::Tier2AmountAlreadyDisbursed,
+            ),
+            _ => (
+                LollysLottoError::Tier3WinningNumbersNotUpdated,
+                LollysLottoError::Tier3AmountAlreadyDisbursed,
+            ),
+        };
+
+        if !self.check_winning_numbers_set_at(position) {
+            return Err(not_updated_error.into());
+        }
+        if self.check_winning_amount_disbursed_at(position) {
+            return Err(already_disbursed_error.into());
+        }
+        self.winning_amount_disbursed[position / 8] |= 1 << (position % 8);
+
+        Ok(())
+    }
 }
 
+/// Share of one winner in the ticket-funded pool of a tier, at least `min_winning_amount`,
+/// plus `sponsored_amount`, which only the single jackpot winner is paid.
 fn calculate_winning_amount(
     total_winning_pool: Decimal,
     winning_bps: i64,
     max_winners: i64,
+    min_winning_amount: u64,
+    sponsored_amount: u64,
     vault_amount: u64,
 ) -> Result<Decimal> {
     let winning_pool = Decimal::new(winning_bps, 4)
         .checked_mul(Decimal::new(total_winning_pool.to_i64().unwrap(), 0))
         .ok_or(LollysLottoError::MathError)?
         .checked_div(Decimal::new(max_winners, 0))
+        .ok_or(LollysLottoError::MathError)?
+        .max(Decimal::from(min_winning_amount))
+        .checked_add(Decimal::from(sponsored_amount))
         .ok_or(LollysLottoError::MathError)?;
 
     if vault_amount < winning_pool.to_u64().unwrap() {
@@ -533,8 +960,64 @@ fn calculate_winning_amount(
     }
 }
 
+/// Derives the winning ticket number of a raffle from its randomness seed, uniformly from
+/// `0..tickets_sold`. A raffle without tickets has no winner, 0 is returned.
+pub fn derive_raffle_winning_ticket_number(seed: &[u8; 32], tickets_sold: u64) -> u64 {
+    if tickets_sold == 0 {
+        return 0;
+    }
+    let hash = hashv(&[seed.as_ref(), b"raffle"]).to_bytes();
+    let value = u128::from_le_bytes(hash[..16].try_into().unwrap());
+    // The modulo bias of a 128-bit value over a 64-bit range is negligible
+    (value % tickets_sold as u128) as u64
+}
+
+/// Derives the winning combination for `nonce` from a round's randomness seed.
+/// Each number is taken from two bytes of `sha256(seed || nonce)`, reduced to
+/// the inclusive range `0..=max_numbers_in_ticket[i]`.
+pub fn derive_winning_numbers(
+    seed: &[u8; 32],
+    nonce: u64,
+    max_numbers_in_ticket: [u8; 6],
+) -> [u8; 6] {
+    let hash = hashv(&[seed.as_ref(), nonce.to_le_bytes().as_ref()]).to_bytes();
+    let mut numbers = [0u8; 6];
+    for (i, number) in numbers.iter_mut().enumerate() {
+        let value = u16::from_le_bytes([hash[2 * i], hash[2 * i + 1]]);
+        *number = (value % (max_numbers_in_ticket[i] as u16 + 1)) as u8;
+    }
+    numbers
+}
+
+/// Packs a winning combination into a single u32 key: number1..number5 take 5 bits each,
+/// from the least significant bit up, and the jackpot number the 6 bits above them.
+pub fn pack_winning_numbers(numbers: [u8; 6]) -> Result<u32> {
+    if numbers[..5].iter().any(|&number| number > 0x1F) || numbers[5] > 0x3F {
+        return Err(LollysLottoError::InvalidNumbersInTicket.into());
+    }
+    Ok(numbers
+        .iter()
+        .enumerate()
+        .fold(0, |key, (i, &number)| key | ((number as u32) << (5 * i))))
+}
+
+/// Inverse of [pack_winning_numbers].
+pub fn unpack_winning_numbers(key: u32) -> [u8; 6] {
+    let mut numbers = [0u8; 6];
+    for (i, number) in numbers.iter_mut().enumerate() {
+        let mask = if i == 5 { 0x3F } else { 0x1F };
+        *number = ((key >> (5 * i)) & mask) as u8;
+    }
+    numbers
+}
+
+/// The `winning_numbers_index` slot a lookup of `key` starts probing at (Fibonacci hashing).
+fn winning_numbers_index_slot(key: u32) -> usize {
+    (key.wrapping_mul(0x9E37_79B1) >> (32 - LottoGame::WINNING_NUMBERS_INDEX_BITS)) as usize
+}
+
 /// Compile-time size check.
-// const _: [u8; 9072] = [0u8; std::mem::size_of::<LottoGame>()];
+const _: [u8; LottoGame::SIZE_V1] = [0u8; std::mem::size_of::<LottoGame>()];
 
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
 #[repr(C)]
@@ -569,14 +1052,148 @@ impl LottoGameWinningNumbers {
     }
 }
 
+/// The amounts of a LottoGame vault reserved for each prize and fee bucket.
+/// Reserved on every ticket sale according to the bucket's BPS share and released on every disbursement.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+#[repr(C)]
+pub struct LottoGameLiabilities {
+    pub jackpot: u64,
+    pub tier_1: u64,
+    pub tier_2: u64,
+    pub tier_3: u64,
+    pub buy_and_burn: u64,
+    pub dao: u64,
+    pub protocol_fees: u64,
+}
+unsafe impl Pod for LottoGameLiabilities {}
+unsafe impl Zeroable for LottoGameLiabilities {}
+
+impl LottoGameLiabilities {
+    pub const SIZE: usize = 8 * 7;
+
+    pub fn total(&self) -> Result<u64> {
+        [
+            self.jackpot,
+            self.tier_1,
+            self.tier_2,
+            self.tier_3,
+            self.buy_and_burn,
+            self.dao,
+            self.protocol_fees,
+        ]
+        .iter()
+        .try_fold(0u64, |total, &liability| total.checked_add(liability))
+        .ok_or(LollysLottoError::OverflowError.into())
+    }
+
+    /// Reserves the shares of the ticket sold after `tickets_sold` tickets.
+    /// Each bucket holds the BPS share of the whole ticket sales, rounded down once,
+    /// so the reserves never drift from the amounts paid out of them.
+    pub fn reserve_ticket_sale(
+        &mut self,
+        version: LottoGameVersion,
+        ticket_price: u64,
+        tickets_sold: u64,
+    ) -> Result<()> {
+        let [jackpot_bps, tier_1_bps, tier_2_bps, tier_3_bps] = version.winning_bps();
+        let buckets = [
+            (&mut self.jackpot, jackpot_bps),
+            (&mut self.tier_1, tier_1_bps),
+            (&mut self.tier_2, tier_2_bps),
+            (&mut self.tier_3, tier_3_bps),
+            (&mut self.buy_and_burn, LottoGame::BUY_AND_BURN_BPS),
+            (&mut self.dao, LottoGame::DAO_BPS),
+            (&mut self.protocol_fees, LottoGame::PROTOCOL_FEES_BPS),
+        ];
+        for (liability, bps) in buckets {
+            *liability = liability
+                .checked_add(ticket_sale_share(ticket_price, bps, tickets_sold))
+                .ok_or(LollysLottoError::OverflowError)?;
+        }
+        Ok(())
+    }
+
+    fn prize_liability(&mut self, tier: u8) -> Result<&mut u64> {
+        match tier {
+            0 => Ok(&mut self.jackpot),
+            1 => Ok(&mut self.tier_1),
+            2 => Ok(&mut self.tier_2),
+            3 => Ok(&mut self.tier_3),
+            _ => Err(LollysLottoError::InvalidWinningTier.into()),
+        }
+    }
+
+    /// Reserves `amount` added to the prize bucket of `tier` on top of the ticket sales.
+    pub fn reserve_prize(&mut self, tier: u8, amount: u64) -> Result<()> {
+        let liability = self.prize_liability(tier)?;
+        *liability = liability
+            .checked_add(amount)
+            .ok_or(LollysLottoError::OverflowError)?;
+        Ok(())
+    }
+
+    /// Releases `amount` paid out of the prize bucket of `tier`.
+    pub fn release_prize(&mut self, tier: u8, amount: u64) -> Result<()> {
+        release_liability(self.prize_liability(tier)?, amount)
+    }
+
+    /// Releases `amount` paid out of the buy and burn bucket.
+    pub fn release_buy_and_burn(&mut self, amount: u64) -> Result<()> {
+        release_liability(&mut self.buy_and_burn, amount)
+    }
+
+    /// Releases `amount` of the DAO bucket given back as a staker discount.
+    pub fn release_dao(&mut self, amount: u64) -> Result<()> {
+        release_liability(&mut self.dao, amount)
+    }
+
+    /// Releases `amount` paid out of the protocol fees bucket.
+    pub fn release_protocol_fees(&mut self, amount: u64) -> Result<()> {
+        release_liability(&mut self.protocol_fees, amount)
+    }
+}
+
+/// The `bps` share of the ticket sold after `tickets_sold` tickets, see
+/// [LottoGameLiabilities::reserve_ticket_sale].
+fn ticket_sale_share(ticket_price: u64, bps: usize, tickets_sold: u64) -> u64 {
+    let share =
+        |tickets: u64| (tickets as u128 * ticket_price as u128 * bps as u128 / 10000) as u64;
+    share(tickets_sold + 1) - share(tickets_sold)
+}
+
+fn release_liability(liability: &mut u64, amount: u64) -> Result<()> {
+    *liability = liability
+        .checked_sub(amount)
+        .ok_or(LollysLottoError::LiabilityExceeded)?;
+    Ok(())
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
 #[repr(u16)]
 pub enum LottoGameVersion {
     V1,
+    /// The draw picks a single winning `ticket_number`, which is paid the jackpot
+    /// funded by the shares of every tier.
+    Raffle,
 }
 unsafe impl Pod for LottoGameVersion {}
 unsafe impl Zeroable for LottoGameVersion {}
 
+impl LottoGameVersion {
+    /// The BPS of the ticket sales paid to the Jackpot, Tier 1, Tier 2 and Tier 3 winners.
+    pub fn winning_bps(&self) -> [usize; LottoGame::MAX_WINNING_TIERS_V1] {
+        match self {
+            LottoGameVersion::V1 => [
+                LottoGame::JACKPOT_WINNING_BPS,
+                LottoGame::TIER_1_WINNING_BPS,
+                LottoGame::TIER_2_WINNING_BPS,
+                LottoGame::TIER_3_WINNING_BPS,
+            ],
+            LottoGameVersion::Raffle => [LottoGame::RAFFLE_JACKPOT_WINNING_BPS, 0, 0, 0],
+        }
+    }
+}
+
 impl Default for LottoGameVersion {
     fn default() -> Self {
         LottoGameVersion::V1
@@ -586,10 +1203,21 @@ impl Default for LottoGameVersion {
 #[repr(u32)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
 pub enum LottoGameState {
+    /// Not yet started.
     NotStarted,
+    /// Tickets are on sale.
     Open,
+    /// Ticket sales have stopped, waiting for the draw.
     Closed,
+    /// Settlement is over.
     Finished,
+    // States below were added after `Finished`, appended to keep the stored discriminants.
+    /// Randomness requested, winning numbers are being drawn.
+    Drawing,
+    /// All winning numbers are drawn.
+    Drawn,
+    /// Winners are being settled.
+    Paying,
 }
 unsafe impl Pod for LottoGameState {}
 unsafe impl Zeroable for LottoGameState {}
@@ -600,6 +1228,26 @@ impl Default for LottoGameState {
     }
 }
 
+impl LottoGameState {
+    pub fn can_transition_to(&self, next_state: LottoGameState) -> bool {
+        matches!(
+            (self, next_state),
+            (LottoGameState::NotStarted, LottoGameState::Open)
+                | (LottoGameState::Open, LottoGameState::Closed)
+                | (LottoGameState::Closed, LottoGameState::Drawing)
+                | (LottoGameState::Drawing, LottoGameState::Drawn)
+                | (LottoGameState::Drawn, LottoGameState::Paying)
+                | (LottoGameState::Drawn, LottoGameState::Finished)
+                | (LottoGameState::Paying, LottoGameState::Finished)
+        )
+    }
+
+    /// Ticket sales are over and the ticket count is final.
+    pub fn is_sales_closed(&self) -> bool {
+        !matches!(self, LottoGameState::NotStarted | LottoGameState::Open)
+    }
+}
+
 impl PDAIdentifier for LottoGame {
     const IDENT: &'static [u8] = b"lotto-game";
 
@@ -655,6 +1303,19 @@ impl LottoGameVault {
         get_associated_token_address(&Self::signer_address(lotto_game), &USDC_MINT_DEVNET)
     }
 
+    /// This PDA signer's associated token account of `mint`, held by `token_program`.
+    pub fn vault_address_for_mint(
+        lotto_game: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            &Self::signer_address(lotto_game),
+            &mint,
+            &token_program,
+        )
+    }
+
     pub fn signer_address(lotto_game: Pubkey) -> Pubkey {
         Self::get_address(&[lotto_game.as_ref()])
     }
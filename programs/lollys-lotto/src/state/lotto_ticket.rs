@@ -31,6 +31,8 @@ pub struct LottoTicket {
     pub _padding2: [u8; 2],
     /// The amount the user has been paid for this ticket if this is the winning ticket.
     pub prize: u64,
+    /// The asset the user paid this ticket with, `ticket_price` is always in USDC.
+    pub payment_asset: PaymentAsset,
 }
 
 impl PDAIdentifier for LottoTicket {
@@ -83,6 +85,39 @@ impl LottoTicket {
     }
 }
 
+/// A ticket being bought through one of the buy instructions, issued by
+/// [LottoGame::issue_ticket](super::LottoGame::issue_ticket).
+#[derive(Debug, Copy, Clone)]
+pub struct TicketPurchase {
+    /// The user the ticket is bought for.
+    pub user: Pubkey,
+    /// Pays the rent of the LottoTicketCombination when this is the first ticket with `numbers`.
+    pub payer: Pubkey,
+    pub lotto_ticket_combination_bump: u8,
+    pub numbers: LottoTicketNumbers,
+    /// Off the ticket price, funded by the DAO share of the ticket.
+    pub discount: u64,
+    /// Withheld by a Token-2022 transfer fee of the LottoGame mint when the ticket was paid.
+    pub transfer_fee: u64,
+    pub payment_asset: PaymentAsset,
+    pub buy_date: i64,
+}
+
+/// The asset a ticket was paid with. SOL and LOLLY are swapped to USDC when the ticket is bought.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub enum PaymentAsset {
+    Usdc,
+    Sol,
+    Lolly,
+}
+
+impl Default for PaymentAsset {
+    fn default() -> Self {
+        PaymentAsset::Usdc
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
 #[repr(C)]
 pub struct LottoTicketNumbers {
@@ -104,6 +139,17 @@ impl LottoTicketNumbers {
         number5: 9,
         jackpot_number: 49,
     };
+
+    pub fn to_array(&self) -> [u8; 6] {
+        [
+            self.number1,
+            self.number2,
+            self.number3,
+            self.number4,
+            self.number5,
+            self.jackpot_number,
+        ]
+    }
 }
 
 pub fn validate_for_max_min_numbers(numbers: LottoTicketNumbers) -> bool {
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use super::LottoTicketNumbers;
+use crate::errors::LollysLottoError;
+use crate::pda_identifier::PDAIdentifier;
+
+/// Counts the tickets bought with the same numbers in a round/LottoGame instance.
+/// Winning tickets with the same numbers split the prize of their winning numbers.
+#[account]
+#[derive(Debug, Copy)]
+#[repr(C)]
+pub struct LottoTicketCombination {
+    pub bump: u8,
+    /// The LottoGame instance the tickets were bought in.
+    pub lotto_game: Pubkey,
+    /// The numbers of the tickets.
+    pub numbers: LottoTicketNumbers,
+    /// The number of tickets bought with `numbers`.
+    pub holders: u32,
+    /// The number of those tickets settled as winners.
+    pub holders_settled: u32,
+    /// The prize of every holder, set when the first holder is settled.
+    pub prize_per_holder: u64,
+    /// Paid the rent of this account and gets it back when the account is closed.
+    pub payer: Pubkey,
+}
+
+impl PDAIdentifier for LottoTicketCombination {
+    const IDENT: &'static [u8] = b"lotto-ticket-combination";
+
+    fn program_id() -> &'static Pubkey {
+        &crate::ID
+    }
+}
+
+impl LottoTicketCombination {
+    pub const SIZE: usize = 1 + 32 + 6 + 4 + 4 + 8 + 32;
+
+    pub fn address(lotto_game: Pubkey, numbers: LottoTicketNumbers) -> Pubkey {
+        Self::address_with_bump(lotto_game, numbers).0
+    }
+
+    pub fn address_with_bump(lotto_game: Pubkey, numbers: LottoTicketNumbers) -> (Pubkey, u8) {
+        Self::get_address_with_bump(&[
+            lotto_game.as_ref(),
+            numbers.number1.to_le_bytes().as_ref(),
+            numbers.number2.to_le_bytes().as_ref(),
+            numbers.number3.to_le_bytes().as_ref(),
+            numbers.number4.to_le_bytes().as_ref(),
+            numbers.number5.to_le_bytes().as_ref(),
+            numbers.jackpot_number.to_le_bytes().as_ref(),
+        ])
+    }
+
+    /// Counts a ticket bought with `numbers`, initializing the account for the first one,
+    /// whose `payer` paid the rent.
+    pub fn record_holder(
+        &mut self,
+        bump: u8,
+        lotto_game: Pubkey,
+        numbers: LottoTicketNumbers,
+        payer: Pubkey,
+    ) -> Result<()> {
+        if self.holders == 0 {
+            self.bump = bump;
+            self.lotto_game = lotto_game;
+            self.numbers = numbers;
+            self.payer = payer;
+        }
+        self.holders = self
+            .holders
+            .checked_add(1)
+            .ok_or(LollysLottoError::OverflowError)?;
+        Ok(())
+    }
+
+    /// Settles one holder of `winning_amount`, the prize of the winning numbers, which is split
+    /// evenly between all holders. Returns the prize of the holder and whether every holder is settled.
+    pub fn settle_holder(&mut self, winning_amount: u64) -> Result<(u64, bool)> {
+        if self.holders_settled >= self.holders {
+            return Err(LollysLottoError::AlreadyDeclaredWinner.into());
+        }
+        if self.holders_settled == 0 {
+            self.prize_per_holder = winning_amount / self.holders as u64;
+        }
+        self.holders_settled += 1;
+        Ok((self.prize_per_holder, self.holders_settled == self.holders))
+    }
+}
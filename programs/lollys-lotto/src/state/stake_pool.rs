@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+
+use crate::{
+    constants::{LOLLY_MINT, USDC_MINT_DEVNET},
+    errors::LollysLottoError,
+    pda_identifier::PDAIdentifier,
+    state::{LottoGame, StakePosition},
+};
+
+/// Pool of LOLLY staked with `authority`, earning the protocol fees of its rounds.
+#[account]
+#[derive(Debug)]
+pub struct StakePool {
+    pub bump: u8,
+    pub authority: Pubkey,
+    /// Total LOLLY staked across every StakePosition of this pool.
+    pub total_staked: u64,
+    /// USDC earned per staked LOLLY since the pool was created, scaled by `REWARD_PRECISION`.
+    pub reward_per_share: u128,
+    /// USDC distributed while nothing was staked, paid out with the next distribution.
+    pub undistributed_rewards: u64,
+    /// Cumulative USDC distributed to stakers.
+    pub total_rewards_distributed: u64,
+    /// Seconds a position must wait after its last stake before it can unstake.
+    pub unstake_cooldown: i64,
+    /// Ticket price discounts, by ascending `min_staked`. A `min_staked` of 0 = unused tier.
+    pub discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeDiscountTier {
+    /// LOLLY a position must have staked to get the discount.
+    pub min_staked: u64,
+    pub discount_bps: u16,
+}
+
+impl PDAIdentifier for StakePool {
+    const IDENT: &'static [u8] = b"stake-pool";
+
+    fn program_id() -> &'static Pubkey {
+        &crate::ID
+    }
+}
+
+impl StakePool {
+    pub const MAX_DISCOUNT_TIERS: usize = 3;
+    pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+    /// Discounts are funded by the DAO share of the ticket, so they can't exceed it.
+    pub const MAX_DISCOUNT_BPS: u16 = LottoGame::DAO_BPS as u16;
+
+    pub fn address(authority: Pubkey) -> Pubkey {
+        Self::get_address(&[authority.as_ref()])
+    }
+
+    pub fn address_with_bump(authority: Pubkey) -> (Pubkey, u8) {
+        Self::get_address_with_bump(&[authority.as_ref()])
+    }
+
+    /// LOLLY token account holding the stake, owned by StakePool PDA
+    pub fn stake_vault(authority: Pubkey) -> Pubkey {
+        get_associated_token_address(&Self::address(authority), &LOLLY_MINT)
+    }
+
+    /// USDC token account holding the rewards, owned by StakePool PDA
+    pub fn reward_vault(authority: Pubkey) -> Pubkey {
+        get_associated_token_address(&Self::address(authority), &USDC_MINT_DEVNET)
+    }
+
+    pub fn set_config(
+        &mut self,
+        unstake_cooldown: i64,
+        discount_tiers: [StakeDiscountTier; StakePool::MAX_DISCOUNT_TIERS],
+    ) -> Result<()> {
+        if unstake_cooldown < 0 {
+            return Err(LollysLottoError::InvalidStakePoolConfig.into());
+        }
+        let mut previous_tier = StakeDiscountTier::default();
+        for tier in discount_tiers.iter().filter(|tier| tier.min_staked != 0) {
+            if tier.discount_bps > Self::MAX_DISCOUNT_BPS
+                || tier.min_staked <= previous_tier.min_staked
+                || tier.discount_bps < previous_tier.discount_bps
+            {
+                return Err(LollysLottoError::InvalidStakePoolConfig.into());
+            }
+            previous_tier = *tier;
+        }
+        self.unstake_cooldown = unstake_cooldown;
+        self.discount_tiers = discount_tiers;
+        Ok(())
+    }
+
+    /// Discount of the highest tier reached by `staked`.
+    pub fn discount_bps(&self, staked: u64) -> u16 {
+        self.discount_tiers
+            .iter()
+            .filter(|tier| tier.min_staked != 0 && staked >= tier.min_staked)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Discount on `ticket_price` for the LOLLY `user` has staked, 0 without a StakePosition.
+    pub fn staker_discount(
+        stake_pool: Option<&Account<StakePool>>,
+        stake_position: Option<&Account<StakePosition>>,
+        user: Pubkey,
+        ticket_price: u64,
+    ) -> Result<u64> {
+        match (stake_pool, stake_position) {
+            (Some(stake_pool), Some(stake_position)) => {
+                if stake_position.user != user || stake_position.stake_pool != stake_pool.key() {
+                    return Err(LollysLottoError::InvalidStakePosition.into());
+                }
+                let discount_bps = stake_pool.discount_bps(stake_position.staked_amount);
+                Ok((ticket_price as u128 * discount_bps as u128 / 10000) as u64)
+            }
+            (None, None) => Ok(0),
+            _ => Err(LollysLottoError::InvalidStakePosition.into()),
+        }
+    }
+
+    /// Shares `amount` between the LOLLY staked now.
+    pub fn distribute_rewards(&mut self, amount: u64) -> Result<()> {
+        let amount = amount
+            .checked_add(self.undistributed_rewards)
+            .ok_or(LollysLottoError::MathError)?;
+        if self.total_staked == 0 {
+            self.undistributed_rewards = amount;
+            return Ok(());
+        }
+        self.undistributed_rewards = 0;
+        self.reward_per_share = (amount as u128)
+            .checked_mul(Self::REWARD_PRECISION)
+            .and_then(|rewards| rewards.checked_div(self.total_staked as u128))
+            .and_then(|increment| self.reward_per_share.checked_add(increment))
+            .ok_or(LollysLottoError::MathError)?;
+        self.total_rewards_distributed = self
+            .total_rewards_distributed
+            .checked_add(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::LollysLottoError, pda_identifier::PDAIdentifier, state::StakePool};
+
+/// LOLLY staked by `user` in `stake_pool`.
+#[account]
+#[derive(Debug)]
+pub struct StakePosition {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub stake_pool: Pubkey,
+    pub staked_amount: u64,
+    /// `staked_amount * reward_per_share` of the pool when the rewards were last settled.
+    pub reward_debt: u128,
+    /// Settled rewards not yet claimed.
+    pub pending_rewards: u64,
+    pub total_rewards_claimed: u64,
+    pub last_staked_at: i64,
+}
+
+impl PDAIdentifier for StakePosition {
+    const IDENT: &'static [u8] = b"stake-position";
+
+    fn program_id() -> &'static Pubkey {
+        &crate::ID
+    }
+}
+
+impl StakePosition {
+    pub fn address(stake_pool: Pubkey, user: Pubkey) -> Pubkey {
+        Self::get_address(&[stake_pool.as_ref(), user.as_ref()])
+    }
+
+    pub fn address_with_bump(stake_pool: Pubkey, user: Pubkey) -> (Pubkey, u8) {
+        Self::get_address_with_bump(&[stake_pool.as_ref(), user.as_ref()])
+    }
+
+    fn accrued_rewards(&self, reward_per_share: u128) -> Result<u128> {
+        (self.staked_amount as u128)
+            .checked_mul(reward_per_share)
+            .map(|accrued| accrued / StakePool::REWARD_PRECISION)
+            .ok_or(LollysLottoError::MathError.into())
+    }
+
+    /// Moves the rewards accrued since the last settlement to `pending_rewards`.
+    pub fn settle_rewards(&mut self, stake_pool: &StakePool) -> Result<()> {
+        let accrued = self.accrued_rewards(stake_pool.reward_per_share)?;
+        let earned = accrued
+            .checked_sub(self.reward_debt)
+            .and_then(|earned| u64::try_from(earned).ok())
+            .ok_or(LollysLottoError::MathError)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(earned)
+            .ok_or(LollysLottoError::MathError)?;
+        self.reward_debt = accrued;
+        Ok(())
+    }
+
+    pub fn stake(&mut self, stake_pool: &mut StakePool, amount: u64, now: i64) -> Result<()> {
+        self.settle_rewards(stake_pool)?;
+        self.staked_amount = self
+            .staked_amount
+            .checked_add(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        stake_pool.total_staked = stake_pool
+            .total_staked
+            .checked_add(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        self.reward_debt = self.accrued_rewards(stake_pool.reward_per_share)?;
+        self.last_staked_at = now;
+        Ok(())
+    }
+
+    pub fn unstake(&mut self, stake_pool: &mut StakePool, amount: u64, now: i64) -> Result<()> {
+        if now < self.last_staked_at + stake_pool.unstake_cooldown {
+            return Err(LollysLottoError::UnstakeCooldownNotOver.into());
+        }
+        self.settle_rewards(stake_pool)?;
+        self.staked_amount = self
+            .staked_amount
+            .checked_sub(amount)
+            .ok_or(LollysLottoError::InsufficientStake)?;
+        stake_pool.total_staked = stake_pool
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(LollysLottoError::MathError)?;
+        self.reward_debt = self.accrued_rewards(stake_pool.reward_per_share)?;
+        Ok(())
+    }
+
+    /// Settles and takes the pending rewards, returns the amount to pay out.
+    pub fn take_rewards(&mut self, stake_pool: &StakePool) -> Result<u64> {
+        self.settle_rewards(stake_pool)?;
+        let rewards = self.pending_rewards;
+        self.pending_rewards = 0;
+        self.total_rewards_claimed = self
+            .total_rewards_claimed
+            .checked_add(rewards)
+            .ok_or(LollysLottoError::MathError)?;
+        Ok(rewards)
+    }
+}
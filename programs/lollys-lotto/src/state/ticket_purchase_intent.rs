@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use super::LottoTicketNumbers;
+
+/// A ticket purchase the user signs off-chain so a relayer can submit it on their behalf.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct TicketPurchaseIntent {
+    pub lotto_game: Pubkey,
+    pub round: u64,
+    pub numbers: LottoTicketNumbers,
+    /// The most the user is willing to pay for the ticket, in USDC.
+    pub max_ticket_price: u64,
+    /// USDC paid to the relayer on top of the ticket price.
+    pub relayer_fee: u64,
+    /// The intent can't be submitted from this time on.
+    pub expiry: i64,
+    /// Must match `UserMetadata::intent_nonce` at submission.
+    pub nonce: u64,
+}
+
+impl TicketPurchaseIntent {
+    pub const DOMAIN: &'static [u8] = b"lollys-lotto:ticket-purchase-intent:";
+
+    /// The bytes the user signs with the Ed25519 key of their wallet.
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Self::DOMAIN.to_vec();
+        message.extend_from_slice(&self.try_to_vec().unwrap());
+        message
+    }
+
+    pub fn check_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expiry
+    }
+}
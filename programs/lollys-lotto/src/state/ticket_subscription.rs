@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+
+use super::{derive_winning_numbers, LottoTicket, LottoTicketNumbers};
+use crate::constants::USDC_MINT_DEVNET;
+use crate::pda_identifier::PDAIdentifier;
+
+/// Escrows quote tokens of `mint` to buy a ticket in each of the next `rounds_remaining` rounds of `authority`.
+#[account]
+#[derive(Debug, Copy)]
+#[repr(C)]
+pub struct TicketSubscription {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub user_metadata: Pubkey,
+    /// Authority of the LottoGame instances this subscription plays.
+    pub authority: Pubkey,
+    pub subscription_id: u64,
+    /// The numbers played every round, unused with quick pick.
+    pub numbers: LottoTicketNumbers,
+    /// Whether fresh numbers are derived for every round.
+    pub quick_pick: u8,
+    pub rounds_remaining: u64,
+    pub rounds_fulfilled: u64,
+    /// The lowest round a ticket can still be bought in, guards against buying twice per round.
+    pub next_round: u64,
+    /// The most this subscription pays for a single ticket, in `mint`.
+    pub max_ticket_price: u64,
+    /// Paid to the cranker in `mint` for every fulfilled round.
+    pub cranker_fee: u64,
+    pub created_at: i64,
+    /// Quote mint of the escrow, only LottoGame instances of this mint are played.
+    pub mint: Pubkey,
+}
+
+impl PDAIdentifier for TicketSubscription {
+    const IDENT: &'static [u8] = b"ticket-subscription";
+
+    fn program_id() -> &'static Pubkey {
+        &crate::ID
+    }
+}
+
+impl TicketSubscription {
+    pub fn address(user_metadata: Pubkey, authority: Pubkey, subscription_id: u64) -> Pubkey {
+        Self::get_address(&[
+            user_metadata.as_ref(),
+            authority.as_ref(),
+            subscription_id.to_le_bytes().as_ref(),
+        ])
+    }
+
+    pub fn address_with_bump(
+        user_metadata: Pubkey,
+        authority: Pubkey,
+        subscription_id: u64,
+    ) -> (Pubkey, u8) {
+        Self::get_address_with_bump(&[
+            user_metadata.as_ref(),
+            authority.as_ref(),
+            subscription_id.to_le_bytes().as_ref(),
+        ])
+    }
+
+    pub fn vault_address(ticket_subscription: Pubkey) -> Pubkey {
+        get_associated_token_address(&ticket_subscription, &USDC_MINT_DEVNET)
+    }
+
+    /// Escrow of a subscription of `mint`, held by `token_program`.
+    pub fn vault_address_for_mint(
+        ticket_subscription: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Pubkey {
+        get_associated_token_address_with_program_id(&ticket_subscription, &mint, &token_program)
+    }
+
+    /// Quote tokens escrowed for `rounds` rounds.
+    pub fn escrow_amount(rounds: u64, max_ticket_price: u64, cranker_fee: u64) -> Option<u64> {
+        max_ticket_price
+            .checked_add(cranker_fee)?
+            .checked_mul(rounds)
+    }
+
+    /// Lamports escrowed to reimburse the cranker the rent of a ticket every round.
+    pub fn ticket_rent(rent: &Rent) -> u64 {
+        rent.minimum_balance(8 + std::mem::size_of::<LottoTicket>())
+    }
+
+    /// The numbers played in `round`. Quick picks are derived from the subscription address
+    /// and the round so crankers can compute the ticket address up front.
+    pub fn numbers_for_round(
+        &self,
+        ticket_subscription: Pubkey,
+        round: u64,
+        max_numbers_in_ticket: [u8; 6],
+    ) -> LottoTicketNumbers {
+        if self.quick_pick == 0 {
+            return self.numbers;
+        }
+        let numbers = derive_winning_numbers(
+            &ticket_subscription.to_bytes(),
+            round,
+            max_numbers_in_ticket,
+        );
+        LottoTicketNumbers {
+            number1: numbers[0],
+            number2: numbers[1],
+            number3: numbers[2],
+            number4: numbers[3],
+            number5: numbers[4],
+            jackpot_number: numbers[5],
+        }
+    }
+}
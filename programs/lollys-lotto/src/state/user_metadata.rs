@@ -1,5 +1,7 @@
 pub use anchor_lang::prelude::*;
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
 
 use crate::constants::USDC_MINT_DEVNET;
 use crate::pda_identifier::PDAIdentifier;
@@ -13,11 +15,21 @@ pub struct UserMetadata {
     pub created_timestamp: i64,
     pub tier: UserTier,
     pub total_tickets_purchased: u64,
+    /// Lifetime totals summed over every quote mint. What is claimable in a mint is the
+    /// balance of the rewards vault of that mint.
     pub total_amount_won: u64,
     pub total_amount_claimed: u64,
     pub last_claimed_at: i64,
     pub referral_count: u64,
     pub referral_revenue: u64,
+    /// The time after which the unclaimed rewards in the user rewards vaults expire.
+    /// Pushed back to the claim deadline of the latest round a prize was won in.
+    pub claim_deadline: i64,
+    pub total_amount_expired: u64,
+    /// Optional delegate allowed to claim the rewards of this user. Pubkey::default() = none.
+    pub claim_authority: Pubkey,
+    /// Nonce the next relayed ticket purchase intent of this user must be signed with.
+    pub intent_nonce: u64,
 }
 
 impl PDAIdentifier for UserMetadata {
@@ -41,9 +53,30 @@ impl UserMetadata {
         get_associated_token_address(&Self::address(user), &USDC_MINT_DEVNET)
     }
 
+    /// Rewards vault of prizes won in a LottoGame of `mint`, held by `token_program`.
+    pub fn user_rewards_vault_address_for_mint(
+        user: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Pubkey {
+        get_associated_token_address_with_program_id(&Self::address(user), &mint, &token_program)
+    }
+
     pub fn from_buffer(buf: &mut &[u8]) -> Result<Self> {
         Self::try_deserialize(buf)
     }
+
+    /// Credits a prize won in a round whose claim deadline is `claim_deadline`.
+    pub fn credit_winning_amount(&mut self, amount: u64, claim_deadline: i64) {
+        self.total_amount_won += amount;
+        self.claim_deadline = self.claim_deadline.max(claim_deadline);
+    }
+
+    /// Whether `claimant` may claim the rewards of this user, i.e. is the user or their claim authority.
+    pub fn check_claimant(&self, claimant: Pubkey) -> bool {
+        claimant == self.user
+            || (self.claim_authority != Pubkey::default() && claimant == self.claim_authority)
+    }
 }
 
 #[repr(u64)]
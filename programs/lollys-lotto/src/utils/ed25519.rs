@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::errors::LollysLottoError;
+
+/// Layout of the Ed25519 precompile instruction data, see `solana_sdk::ed25519_instruction`.
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+
+/// Checks that the instruction right before the current one is an Ed25519 precompile instruction
+/// verifying a single signature of `signer` over `message`.
+/// The runtime rejects the transaction if that signature is invalid, so this only has to
+/// make sure the precompile checked the expected key and message.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    if current_index == 0 {
+        return Err(LollysLottoError::InvalidIntentSignature.into());
+    }
+    let ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return Err(LollysLottoError::InvalidIntentSignature.into());
+    }
+
+    let data = &ix.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE || data[0] != 1 {
+        return Err(LollysLottoError::InvalidIntentSignature.into());
+    }
+    let read_u16 = |offset: usize| {
+        let offset = SIGNATURE_OFFSETS_START + offset;
+        u16::from_le_bytes([data[offset], data[offset + 1]])
+    };
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_data_offset = read_u16(8) as usize;
+    let message_data_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+
+    // The signature, key and message must live in the precompile instruction itself
+    if [
+        signature_instruction_index,
+        public_key_instruction_index,
+        message_instruction_index,
+    ]
+    .iter()
+    .any(|index| *index != u16::MAX)
+    {
+        return Err(LollysLottoError::InvalidIntentSignature.into());
+    }
+
+    let public_key = data.get(public_key_offset..public_key_offset + PUBKEY_SERIALIZED_SIZE);
+    let signed_message = data.get(message_data_offset..message_data_offset + message_data_size);
+    if public_key != Some(signer.as_ref()) || signed_message != Some(message) {
+        return Err(LollysLottoError::InvalidIntentSignature.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+
+/// Grows an account of `T` created before fields were appended to `T` to `space` bytes,
+/// the space new accounts of `T` are created with. `payer` funds the extra rent and the
+/// appended fields are zeroed. Accounts already `space` bytes long are left as they are.
+/// Returns the previous size of the account.
+pub fn migrate_account<'info, T: AccountDeserialize + Discriminator + Owner>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<usize> {
+    if *account.owner != T::owner() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    if account.try_borrow_data()?.get(..8) != Some(&T::DISCRIMINATOR[..]) {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    let previous_space = account.data_len();
+    if previous_space < space {
+        let rent_due = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(account.lamports());
+        if rent_due > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        account.realloc(space, true)?;
+    }
+
+    // CHECK: The account must read as the current layout of `T`
+    T::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(previous_space)
+}
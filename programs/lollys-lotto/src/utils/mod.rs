@@ -1,5 +1,13 @@
+pub mod ed25519;
 pub mod lollys_num;
+pub mod migrate;
+pub mod quote_token;
+pub mod swap_adapter;
 pub mod switchboard;
 
+pub use ed25519::*;
 pub use lollys_num::*;
+pub use migrate::*;
+pub use quote_token::*;
+pub use swap_adapter::*;
 pub use switchboard::*;
@@ -0,0 +1,118 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeConfig},
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::errors::LollysLottoError;
+
+/// Transfers `amount` of the LottoGame mint with `transfer_checked` and returns the amount `to`
+/// was credited, which is short of `amount` when the mint withholds a Token-2022 transfer fee.
+pub fn transfer_quote_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<u64> {
+    let amount_before = to.amount;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )?;
+    to.reload()?;
+    Ok(to
+        .amount
+        .checked_sub(amount_before)
+        .ok_or(LollysLottoError::MathError)?)
+}
+
+/// Moves the transfer fees withheld in `token_account` to its mint, a Token-2022 account
+/// can't be closed while it holds any. Does nothing for mints without a transfer fee.
+pub fn harvest_withheld_transfer_fees<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+) -> Result<()> {
+    if token_program.key() != spl_token_2022::ID {
+        return Ok(());
+    }
+    let has_transfer_fee = {
+        let mint_info = mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?
+            .get_extension::<TransferFeeConfig>()
+            .is_ok()
+    };
+    if !has_transfer_fee {
+        return Ok(());
+    }
+    invoke(
+        &harvest_withheld_tokens_to_mint(
+            &spl_token_2022::ID,
+            &mint.key(),
+            &[&token_account.key()],
+        )?,
+        &[mint.to_account_info(), token_account.to_account_info()],
+    )?;
+    Ok(())
+}
+
+/// Transfer fee `mint` withholds on a transfer that credits exactly `net_amount`,
+/// 0 for mints without a transfer fee.
+pub fn transfer_fee_for_net_amount<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    net_amount: u64,
+) -> Result<u64> {
+    if token_program.key() != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(LollysLottoError::MathError)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Transfer fee `mint` withholds on a transfer of `amount`, 0 for mints without a transfer fee.
+pub fn transfer_fee_for_amount<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    amount: u64,
+) -> Result<u64> {
+    if token_program.key() != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(LollysLottoError::MathError)?),
+        Err(_) => Ok(0),
+    }
+}
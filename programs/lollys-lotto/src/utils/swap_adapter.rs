@@ -0,0 +1,38 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+/// Invokes `swap_program` with a route built off-chain: its accounts are passed as-is and
+/// `signer` is marked as signer wherever it appears. The route is not interpreted, so callers
+/// must validate the outcome by the balance changes of their own token accounts.
+pub fn invoke_swap<'info>(
+    swap_program: &AccountInfo<'info>,
+    route_accounts: &[AccountInfo<'info>],
+    signer: Pubkey,
+    signer_seeds: &[&[&[u8]]],
+    data: Vec<u8>,
+) -> Result<()> {
+    let accounts: Vec<AccountMeta> = route_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: *acc.key == signer || acc.is_signer,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+
+    let mut account_infos = route_accounts.to_vec();
+    account_infos.push(swap_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: *swap_program.key,
+            accounts,
+            data,
+        },
+        &account_infos,
+        signer_seeds,
+    )?;
+    Ok(())
+}
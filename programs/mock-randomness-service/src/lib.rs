@@ -0,0 +1,19 @@
+//! Stands in for the Solana Randomness Service in the localnet tests. Every
+//! request is accepted without creating any accounts; the tests write the
+//! service's `State` and request accounts directly and invoke the callback themselves.
+use anchor_lang::prelude::*;
+
+declare_id!("RANDMo5gFnqnXJW5Z52KNmd24sAo95KAd5VbiCtq5Rh");
+
+#[program]
+pub mod mock_randomness_service {
+    use super::*;
+
+    pub fn fallback<'info>(
+        _program_id: &Pubkey,
+        _accounts: &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+    ) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+//! A minimal constant-product (x * y = k) pool, only meant to stand in for
+//! Jupiter and other DEXes in the localnet tests. No fees, no LP tokens:
+//! liquidity is seeded by transferring tokens straight into the pool vaults.
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+declare_id!("DgP2a1r3UcPJYgB5fbc6jHxv27vBiJ8kSRwJbms2w3VD");
+
+#[program]
+pub mod mock_swap {
+    use super::*;
+
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.bump = ctx.bumps.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.vault_a = ctx.accounts.vault_a.key();
+        pool.vault_b = ctx.accounts.vault_b.key();
+        Ok(())
+    }
+
+    /// Swaps `amount_in` of the mint of `pool_source_vault` for the mint of `pool_destination_vault`.
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let reserve_in = ctx.accounts.pool_source_vault.amount as u128;
+        let reserve_out = ctx.accounts.pool_destination_vault.amount as u128;
+        let amount_out =
+            (reserve_out * amount_in as u128 / (reserve_in + amount_in as u128)) as u64;
+        if amount_out == 0 || amount_out < min_amount_out {
+            return Err(MockSwapError::SlippageExceeded.into());
+        }
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.pool_source_vault.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_destination_vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&[
+                    Pool::SEED,
+                    pool.mint_a.as_ref(),
+                    pool.mint_b.as_ref(),
+                    &[pool.bump],
+                ]],
+            ),
+            amount_out,
+        )?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Pool {
+    pub bump: u8,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+}
+
+impl Pool {
+    pub const SEED: &'static [u8] = b"pool";
+
+    pub fn address(mint_a: Pubkey, mint_b: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[Self::SEED, mint_a.as_ref(), mint_b.as_ref()], &crate::ID).0
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Pool>(),
+        seeds = [Pool::SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub mint_a: Account<'info, Mint>,
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool,
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool,
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        seeds = [Pool::SEED, pool.mint_a.as_ref(), pool.mint_b.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool_source_vault.key() == pool.vault_a || pool_source_vault.key() == pool.vault_b,
+        constraint = pool_source_vault.mint == source.mint,
+    )]
+    pub pool_source_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool_destination_vault.key() == pool.vault_a || pool_destination_vault.key() == pool.vault_b,
+        constraint = pool_destination_vault.key() != pool_source_vault.key(),
+        constraint = pool_destination_vault.mint == destination.mint,
+    )]
+    pub pool_destination_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum MockSwapError {
+    #[msg("Swap returned less than the minimum amount out")]
+    SlippageExceeded,
+}